@@ -0,0 +1,200 @@
+use std::fmt;
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::reduction::{bkz_memory_cost, BkzSchedule, Estimates};
+
+/// The attack that determined a security estimate.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AttackKind {
+    /// The length bound is so large that a solution can be found in polynomial time (e.g., $\beta\_\infty \geq q/2$ or $\beta\_2 \geq q$).
+    Trivial,
+    /// Lattice reduction on the full lattice.
+    LatticeReduction,
+    /// Lattice reduction on the sublattice obtained by dropping `dropped_columns` columns (i.e., fixing the corresponding coordinates of the solution to zero).
+    DimensionReduction { dropped_columns: usize },
+    /// The primal attack on LWE using `samples` samples, solving uSVP in Kannan's embedding lattice with lattice reduction.
+    /// `sample_limited` indicates that the attack uses all available samples, i.e., that it would benefit from more samples.
+    /// `secret_scaling` is the factor by which the secret coordinates are scaled for small secrets [BG14], if any (see [`LWE::secret_scaling`](crate::lwe::LWE::secret_scaling)).
+    PrimalUsvp {
+        samples: usize,
+        sample_limited: bool,
+        secret_scaling: Option<f64>,
+    },
+    /// The two-step primal attack on LWE using `samples` samples: BKZ with the reported block size, followed by a single sieve in dimension `sieve_dim` on the last Gram-Schmidt vectors [ADH+19].
+    PrimalUsvpTwoStep {
+        samples: usize,
+        sample_limited: bool,
+        sieve_dim: usize,
+        secret_scaling: Option<f64>,
+    },
+    /// Dense sublattice discovery on overstretched NTRU lattices [DvW21].
+    DenseSublatticeDiscovery,
+    /// The hybrid attack on LWE with sparse secrets [HG07], guessing `guessed` coordinates of the secret with a meet-in-the-middle search and recovering the others with the primal attack.
+    Hybrid { guessed: usize },
+    /// The generalized birthday attack [Wag02] on SIS with small $\ell\_\infty$ bounds, with $2^\texttt{levels}$ lists.
+    Combinatorial { levels: u32 },
+}
+
+impl Display for AttackKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AttackKind::Trivial => write!(f, "trivial"),
+            AttackKind::LatticeReduction => write!(f, "lattice reduction"),
+            AttackKind::DimensionReduction { dropped_columns } => {
+                write!(f, "lattice reduction ({dropped_columns} columns dropped)")
+            }
+            AttackKind::PrimalUsvp {
+                samples,
+                sample_limited,
+                secret_scaling,
+            } => {
+                write!(f, "primal uSVP ({samples} samples")?;
+                if *sample_limited {
+                    write!(f, ", sample-limited")?;
+                }
+                if let Some(scaling) = secret_scaling {
+                    write!(f, ", secret scaled by {scaling:.2}")?;
+                }
+                write!(f, ")")
+            }
+            AttackKind::PrimalUsvpTwoStep {
+                samples,
+                sample_limited,
+                sieve_dim,
+                secret_scaling,
+            } => {
+                write!(f, "primal uSVP ({samples} samples, sieve dim {sieve_dim}")?;
+                if *sample_limited {
+                    write!(f, ", sample-limited")?;
+                }
+                if let Some(scaling) = secret_scaling {
+                    write!(f, ", secret scaled by {scaling:.2}")?;
+                }
+                write!(f, ")")
+            }
+            AttackKind::DenseSublatticeDiscovery => write!(f, "dense sublattice discovery"),
+            AttackKind::Hybrid { guessed } => write!(f, "hybrid ({guessed} coordinates guessed)"),
+            AttackKind::Combinatorial { levels } => {
+                write!(f, "generalized birthday ({} lists)", 1u64 << levels)
+            }
+        }
+    }
+}
+
+/// Resource constraints on the attacker. Attacks violating these constraints are not considered when estimating security.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostConstraint {
+    /// Upper bound on the $\log\_2$ of the memory available to the attacker, or `None` for unbounded memory.
+    pub max_log2_memory: Option<f64>,
+}
+
+impl CostConstraint {
+    pub fn with_max_log2_memory(max_log2_memory: f64) -> Self {
+        CostConstraint {
+            max_log2_memory: Some(max_log2_memory),
+        }
+    }
+
+    /// Return true iff BKZ with block size `block_size` satisfies the constraint under the cost model `est`.
+    pub fn is_satisfied(&self, est: Estimates, block_size: usize) -> bool {
+        self.max_log2_memory
+            .is_none_or(|max_log2_memory| bkz_memory_cost(est, block_size) <= max_log2_memory)
+    }
+
+    /// Return the largest block size in `[2, max_block_size]` that satisfies the constraint under the cost model `est`, or `None` if there is none.
+    pub fn max_block_size(&self, est: Estimates, max_block_size: usize) -> Option<usize> {
+        (2..=max_block_size)
+            .rev()
+            .find(|&b| self.is_satisfied(est, b))
+    }
+}
+
+/// The cost of a single attack on an instance, under a given cost model.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AttackEstimate {
+    pub attack: AttackKind,
+    pub estimate: Estimates,
+    /// (Final) block size used by lattice reduction (0 for trivial instances).
+    pub block_size: usize,
+    /// Block size schedule used by lattice reduction.
+    pub schedule: BkzSchedule,
+    /// Dimension of the lattice the reduction is run on (0 for trivial instances).
+    pub dim: usize,
+    /// $\log\_2$ of the time cost of the attack.
+    pub log2_cost_time: f64,
+    /// $\log\_2$ of the memory cost of the attack, if known.
+    pub log2_cost_memory: Option<f64>,
+}
+
+impl AttackEstimate {
+    pub(crate) fn trivial(estimate: Estimates) -> Self {
+        AttackEstimate {
+            attack: AttackKind::Trivial,
+            estimate,
+            block_size: 0,
+            schedule: BkzSchedule::Fixed,
+            dim: 0,
+            log2_cost_time: 0.,
+            log2_cost_memory: None,
+        }
+    }
+
+    /// Return the estimate with the lowest time cost, if any.
+    pub fn best(estimates: &[AttackEstimate]) -> Option<&AttackEstimate> {
+        estimates
+            .iter()
+            .min_by(|a, b| a.log2_cost_time.total_cmp(&b.log2_cost_time))
+    }
+
+    fn fmt_header(f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "| {:<44} | {:<18} | {:>10} | {:<16} | {:>6} | {:>10} | {:>12} |",
+            "attack", "estimator", "block size", "schedule", "dim", "log2(time)", "log2(memory)"
+        )?;
+        writeln!(
+            f,
+            "|{:-<46}|{:-<20}|{:->12}|{:-<18}|{:->8}|{:->12}|{:->14}|",
+            "", "", "", "", "", "", ""
+        )
+    }
+
+    fn fmt_row(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let memory = self
+            .log2_cost_memory
+            .map_or("-".to_string(), |m| format!("{m:.2}"));
+        writeln!(
+            f,
+            "| {:<44} | {:<18} | {:>10} | {:<16} | {:>6} | {:>10.2} | {:>12} |",
+            self.attack.to_string(),
+            self.estimate.name(),
+            self.block_size,
+            self.schedule.to_string(),
+            self.dim,
+            self.log2_cost_time,
+            memory
+        )
+    }
+}
+
+impl Display for AttackEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Self::fmt_header(f)?;
+        self.fmt_row(f)
+    }
+}
+
+/// A list of [`AttackEstimate`]s for the same instance, rendered as a table by its `Display` implementation.
+pub struct AttackReport<'a>(pub &'a [AttackEstimate]);
+
+impl Display for AttackReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        AttackEstimate::fmt_header(f)?;
+        for estimate in self.0 {
+            estimate.fmt_row(f)?;
+        }
+        Ok(())
+    }
+}
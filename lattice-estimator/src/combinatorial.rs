@@ -0,0 +1,78 @@
+//! Combinatorial attacks on SIS with small $\ell\_\infty$ bounds, based on the generalized birthday algorithm [Wag02].
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+
+/// Largest $\ell\_\infty$ bound for which [`SIS::estimate_all`](crate::sis::SIS::estimate_all) considers the combinatorial attack; for larger bounds, lattice reduction is always cheaper.
+pub const COMBINATORIAL_MAX_LENGTH_BOUND: f64 = 2.;
+
+/// $\log\_2$ of the time and memory cost of the generalized birthday algorithm with $2^a$ lists, where $a$ = `levels`, on `SIS\[n, m, q, beta_inf\]` (in the $\ell\_\infty$ norm), or `None` if the lists are too small.
+/// The columns are split into $2^a$ blocks of $\lfloor m / 2^a \rfloor$ columns, and each list contains (up to) $L$ vectors with coefficients in $[-\beta\_\infty, \beta\_\infty]$ supported on one block.
+/// Each of the $a$ merging levels zeroes $\log\_2 L$ bits of the syndrome and the final merge zeroes $2 \log\_2 L$ bits, so a solution is found if $L \geq q^{n / (a + 1)}$, at cost $2^a L$.
+fn generalized_birthday_cost(
+    n: usize,
+    m: usize,
+    log_q: f64,
+    beta_inf: u64,
+    levels: u32,
+) -> Option<f64> {
+    let block = m >> levels;
+    if block == 0 {
+        return None;
+    }
+    let log2_list_size = n as f64 * log_q / (levels + 1) as f64;
+    let log2_max_list_size = block as f64 * ((2 * beta_inf + 1) as f64).log2();
+    (log2_list_size <= log2_max_list_size).then_some(levels as f64 + log2_list_size)
+}
+
+/// Return $\log\_2$ of the time cost of the generalized birthday attack on `SIS\[n, m, q, beta_inf\]` (in the $\ell\_\infty$ norm) with the optimal number of levels, together with that number, or `None` if there are not enough columns for any number of levels.
+pub fn combinatorial_sis_attack(
+    n: usize,
+    m: usize,
+    q: &BigUint,
+    beta_inf: f64,
+) -> Option<(u32, f64)> {
+    let log_q = q.to_f64().unwrap().log2();
+    let beta_inf = beta_inf.floor() as u64;
+    if beta_inf == 0 {
+        return None;
+    }
+    (0..=m.ilog2())
+        .filter_map(|levels| {
+            generalized_birthday_cost(n, m, log_q, beta_inf, levels).map(|cost| (levels, cost))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Return $\log\_2$ of the time cost of the generalized birthday attack on `SIS\[n, m, q, beta_inf\]` (in the $\ell\_\infty$ norm) with the optimal number of levels, see [`combinatorial_sis_attack`], or infinity if it does not apply.
+pub fn combinatorial_sis_cost(n: usize, m: usize, q: &BigUint, beta_inf: f64) -> f64 {
+    combinatorial_sis_attack(n, m, q, beta_inf).map_or(f64::INFINITY, |(_, cost)| cost)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_generalized_birthday_cost() {
+        // Birthday attack with two lists: L = q^{n/2}
+        assert_eq!(
+            generalized_birthday_cost(10, 1000, 16., 1, 1),
+            Some(1. + 80.)
+        );
+        // Not enough columns to fill the lists
+        assert_eq!(generalized_birthday_cost(10, 100, 16., 1, 1), None);
+        assert_eq!(generalized_birthday_cost(10, 100, 16., 1, 7), None);
+    }
+
+    #[test]
+    fn test_combinatorial_sis_cost() {
+        let q = BigUint::from(1u64 << 16);
+        // More levels pay off with more columns
+        let (levels_narrow, cost_narrow) = combinatorial_sis_attack(16, 1024, &q, 1.).unwrap();
+        let (levels_wide, cost_wide) = combinatorial_sis_attack(16, 1 << 16, &q, 1.).unwrap();
+        assert!(levels_narrow < levels_wide);
+        assert!(cost_wide < cost_narrow);
+        assert!(combinatorial_sis_cost(16, 1024, &q, 2.) <= cost_narrow);
+        assert_eq!(combinatorial_sis_cost(16, 1024, &q, 0.5), f64::INFINITY);
+    }
+}
@@ -1,6 +1,8 @@
 use std::fmt;
 use std::fmt::{Debug, Display};
 
+use crate::norms::Norm;
+
 pub struct LatticeEstimatorError {
     pub(crate) message: String,
 }
@@ -22,3 +24,37 @@ impl From<String> for LatticeEstimatorError {
         LatticeEstimatorError { message }
     }
 }
+
+/// Errors returned by the native estimator, see e.g. [`SIS::security_level_internal`](crate::sis::SIS::security_level_internal).
+#[derive(Clone, Debug, PartialEq)]
+pub enum EstimatorError {
+    /// The block size is outside the range supported by a cost model or simulator (e.g., smaller than 2).
+    UnsupportedBlockSize(usize),
+    /// The attack does not support instances in the given norm.
+    UnsupportedNorm(Norm),
+    /// No parameters of the attack succeed, e.g., lattice reduction does not find a solution for any block size.
+    NonConvergent(String),
+    /// The instance or the arguments are invalid, with the reason.
+    InvalidParameters(String),
+}
+
+impl Display for EstimatorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EstimatorError::UnsupportedBlockSize(block_size) => {
+                write!(f, "unsupported block size {block_size}")
+            }
+            EstimatorError::UnsupportedNorm(norm) => write!(f, "unsupported norm {norm}"),
+            EstimatorError::NonConvergent(context) => write!(f, "no attack succeeds: {context}"),
+            EstimatorError::InvalidParameters(context) => {
+                write!(f, "invalid parameters: {context}")
+            }
+        }
+    }
+}
+
+impl From<EstimatorError> for LatticeEstimatorError {
+    fn from(error: EstimatorError) -> Self {
+        LatticeEstimatorError::from(error.to_string())
+    }
+}
@@ -0,0 +1,35 @@
+from estimator import *
+from math import log2
+from sage.all import oo  # +Infinity
+
+Logging.set_level(Logging.CRITICAL)
+
+# Cost models of the Rust estimator (by name) and their counterparts in the lattice-estimator
+COST_MODELS = {
+    "bdgl16": RC.BDGL16,
+    "adps16": RC.ADPS16,
+    "kyber-classical": RC.Kyber,
+    "matzov-classical": RC.MATZOV,
+    "chaloy21": RC.ChaLoy21,
+    "chengu12": RC.CheNgu12,
+    "laamospol14": RC.LaaMosPol14,
+}
+
+
+def sage_version():
+    from sage.env import SAGE_VERSION
+    return SAGE_VERSION
+
+
+def sis_lattice_cost(n, q, length_bound, m, norm, cost_model):
+    params = SIS.Parameters(n=n, q=q, length_bound=length_bound, m=m, norm=2 if norm == "L2" else oo)
+    res = SIS.lattice(params, red_cost_model=COST_MODELS[cost_model])
+    return log2(res["rop"])
+
+
+def lwe_primal_usvp_cost(n, q, error_std_dev, m, cost_model):
+    # Normal form: the secret is distributed as the error
+    Xe = ND.DiscreteGaussian(error_std_dev)
+    params = LWE.Parameters(n=n, q=q, Xs=Xe, Xe=Xe, m=oo if m is None else m)
+    res = LWE.primal_usvp(params, red_cost_model=COST_MODELS[cost_model])
+    return log2(res["rop"])
@@ -0,0 +1,299 @@
+//! Regression fixtures for the native estimator.
+//! A [`Fixture`] pairs an SIS or LWE instance and a cost model with the expected $\log\_2$ of the cost of the best attack, a tolerance in bits and the source of the expected cost.
+//! The native estimate is [`SIS::security_level_internal`] or [`LWE::security_level_internal`] (with unbounded memory and a single BKZ run).
+//! There are two fixture files:
+//! - [`FIXTURES_PATH`] holds the costs computed by the [lattice-estimator](https://github.com/malb/lattice-estimator) (`SIS.lattice` and `LWE.primal_usvp`) via SageMath, together with the SageMath version and lattice-estimator commit, see [`SageFixtures`]. It is written by [`record_sage`] and validates the native estimator.
+//! - [`SNAPSHOT_PATH`] is a JSON array of fixtures with the costs of the native estimator itself, written by [`record_current`] (source [`RECORDED_SOURCE`]). It only catches unintended drift.
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::str::FromStr;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::attack::CostConstraint;
+use crate::errors::LatticeEstimatorError;
+use crate::lwe::{SecretDist, LWE};
+use crate::reduction::{BkzSchedule, Estimates};
+use crate::sage_util::sagemath_eval;
+use crate::sis::SIS;
+
+/// Path of the fixtures computed with the lattice-estimator by [`record_sage`].
+pub const FIXTURES_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/estimates.json");
+
+/// Path of the snapshot of the native estimator written by [`record_current`], which also provides the instances for [`record_sage`].
+pub const SNAPSHOT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/snapshot.json");
+
+/// Source of expected costs recorded from the native estimator by [`record_current`].
+pub const RECORDED_SOURCE: &str = "native estimator (record_current)";
+
+/// The instance of a [`Fixture`], tagged with its `problem` (`"SIS"` or `"LWE"`).
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "problem")]
+pub enum FixtureParams {
+    SIS(SIS),
+    LWE(LWE),
+}
+
+/// An instance and cost model together with the expected $\log\_2$ of the cost of the best attack.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Fixture {
+    pub params: FixtureParams,
+    /// Name of the cost model, as parsed by [`Estimates::from_str`].
+    pub estimator_name: String,
+    pub expected_log2_cost: f64,
+    /// Largest difference (in bits) between the native and the expected cost for the fixture to pass.
+    pub tolerance_bits: f64,
+    /// Where `expected_log2_cost` comes from, i.e., [`RECORDED_SOURCE`] or the lattice-estimator commit and SageMath version.
+    pub source: String,
+}
+
+/// The contents of [`FIXTURES_PATH`]: fixtures whose expected costs were computed by the lattice-estimator at commit `estimator_commit` with SageMath `sage_version`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SageFixtures {
+    pub sage_version: String,
+    pub estimator_commit: String,
+    pub fixtures: Vec<Fixture>,
+}
+
+impl Fixture {
+    /// Return the native estimate of $\log\_2$ of the cost of the best attack, or the estimator error (e.g., if no attack succeeds).
+    pub fn estimate(&self) -> Result<f64, LatticeEstimatorError> {
+        let est = Estimates::from_str(&self.estimator_name)?;
+        let constraint = CostConstraint::default();
+        let attack = match &self.params {
+            FixtureParams::SIS(sis) => {
+                sis.security_level_internal(est, &constraint, BkzSchedule::Fixed)
+            }
+            FixtureParams::LWE(lwe) => {
+                lwe.security_level_internal(est, &constraint, BkzSchedule::Fixed)
+            }
+        };
+        Ok(attack?.log2_cost_time)
+    }
+
+    /// Return $\log\_2$ of the cost of the same attack computed by the lattice-estimator under the same cost model, via SageMath.
+    /// LWE instances must have a secret in normal form.
+    pub fn estimate_sage(&self) -> Result<f64, LatticeEstimatorError> {
+        let eval = match &self.params {
+            FixtureParams::SIS(sis) => format!(
+                "sis_lattice_cost({}, {}, {}, {}, \"{}\", \"{}\")",
+                sis.h, sis.q, sis.length_bound, sis.w, sis.norm, self.estimator_name
+            ),
+            FixtureParams::LWE(lwe) => {
+                if lwe.secret_dist != SecretDist::NormalForm {
+                    return Err(LatticeEstimatorError::from(format!(
+                        "{lwe}: only secrets in normal form are supported"
+                    )));
+                }
+                format!(
+                    "lwe_primal_usvp_cost({}, {}, {}, {}, \"{}\")",
+                    lwe.n,
+                    lwe.q,
+                    lwe.error_std_dev,
+                    lwe.max_samples
+                        .map_or("None".to_string(), |m| m.to_string()),
+                    self.estimator_name
+                )
+            }
+        };
+        sagemath_eval(eval, SIS::parse_f64).map_err(|e| LatticeEstimatorError::from(e.to_string()))
+    }
+
+    /// Return an error describing the mismatch if the native estimate differs from the expected cost by more than the tolerance.
+    pub fn check(&self) -> Result<(), LatticeEstimatorError> {
+        let lambda = self.estimate()?;
+        if (lambda - self.expected_log2_cost).abs() <= self.tolerance_bits {
+            return Ok(());
+        }
+        Err(LatticeEstimatorError::from(format!(
+            "{:?} under {}: log2(cost) = {lambda}, expected {} +/- {}",
+            self.params, self.estimator_name, self.expected_log2_cost, self.tolerance_bits
+        )))
+    }
+}
+
+/// Read the fixtures in the JSON file at `path`.
+pub fn load(path: impl AsRef<Path>) -> Result<Vec<Fixture>, LatticeEstimatorError> {
+    read_json(path.as_ref())
+}
+
+/// Write `fixtures` to the JSON file at `path`.
+pub fn save(path: impl AsRef<Path>, fixtures: &[Fixture]) -> Result<(), LatticeEstimatorError> {
+    write_json(path.as_ref(), fixtures)
+}
+
+/// Read the fixtures computed with the lattice-estimator in the JSON file at `path`.
+pub fn load_sage(path: impl AsRef<Path>) -> Result<SageFixtures, LatticeEstimatorError> {
+    read_json(path.as_ref())
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, LatticeEstimatorError> {
+    let json = fs::read_to_string(path)
+        .map_err(|e| format!("cannot read fixtures {}: {e}", path.display()))?;
+    serde_json::from_str(&json).map_err(|e| {
+        LatticeEstimatorError::from(format!("invalid fixtures {}: {e}", path.display()))
+    })
+}
+
+fn write_json<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<(), LatticeEstimatorError> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("cannot serialize fixtures: {e}"))?;
+    fs::write(path, json + "\n").map_err(|e| {
+        LatticeEstimatorError::from(format!("cannot write fixtures {}: {e}", path.display()))
+    })
+}
+
+/// Return the commit of the lattice-estimator checkout used by [`sagemath_eval`].
+fn estimator_commit() -> Result<String, LatticeEstimatorError> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("lattice-estimator");
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(&root)
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .map_err(|e| format!("cannot run git in {}: {e}", root.display()))?;
+    if !output.status.success() {
+        return Err(LatticeEstimatorError::from(format!(
+            "{} is not a lattice-estimator checkout: {}",
+            root.display(),
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Compute the expected costs of the instances in [`SNAPSHOT_PATH`] with the lattice-estimator (see [`Fixture::estimate_sage`], rounded to two decimals), write them to [`FIXTURES_PATH`] together with the SageMath version and lattice-estimator commit, and return them.
+/// The tolerances of the snapshot are kept.
+pub fn record_sage() -> Result<SageFixtures, LatticeEstimatorError> {
+    let sage_version = sagemath_eval("sage_version()".to_string(), |s| {
+        Ok::<_, std::convert::Infallible>(s.trim().to_string())
+    })
+    .map_err(|e| LatticeEstimatorError::from(e.to_string()))?;
+    let estimator_commit = estimator_commit()?;
+    let mut fixtures = load(SNAPSHOT_PATH)?;
+    for fixture in &mut fixtures {
+        let lambda = fixture.estimate_sage()?;
+        fixture.expected_log2_cost = (lambda * 100.).round() / 100.;
+        fixture.source = format!("lattice-estimator {estimator_commit} (SageMath {sage_version})");
+    }
+    let sage = SageFixtures {
+        sage_version,
+        estimator_commit,
+        fixtures,
+    };
+    write_json(Path::new(FIXTURES_PATH), &sage)?;
+    Ok(sage)
+}
+
+/// Replace the expected costs of the fixtures in [`SNAPSHOT_PATH`] by the current native estimates (rounded to two decimals), and return the updated fixtures.
+/// The changes to the file document the drift of the native estimator; the tolerances are kept and the sources are set to [`RECORDED_SOURCE`].
+pub fn record_current() -> Result<Vec<Fixture>, LatticeEstimatorError> {
+    let mut fixtures = load(SNAPSHOT_PATH)?;
+    for fixture in &mut fixtures {
+        let lambda = fixture.estimate()?;
+        if (lambda - fixture.expected_log2_cost).abs() > fixture.tolerance_bits {
+            log::warn!(
+                "{:?} under {}: log2(cost) drifted from {} to {lambda}",
+                fixture.params,
+                fixture.estimator_name,
+                fixture.expected_log2_cost
+            );
+        }
+        fixture.expected_log2_cost = (lambda * 100.).round() / 100.;
+        fixture.source = RECORDED_SOURCE.to_string();
+    }
+    save(SNAPSHOT_PATH, &fixtures)?;
+    Ok(fixtures)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::norms::Norm;
+
+    use super::*;
+
+    fn check_all(fixtures: &[Fixture]) {
+        let failures: Vec<String> = fixtures
+            .iter()
+            .filter_map(|fixture| fixture.check().err())
+            .map(|e| e.to_string())
+            .collect();
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    #[test]
+    #[ignore = "fixtures/estimates.json has to be generated with record_sage"]
+    fn test_fixtures() {
+        let sage = load_sage(FIXTURES_PATH).unwrap();
+        assert!(!sage.sage_version.is_empty() && !sage.estimator_commit.is_empty());
+        assert!(sage.fixtures.len() >= 20);
+        assert!(sage
+            .fixtures
+            .iter()
+            .all(|fixture| fixture.source != RECORDED_SOURCE));
+        check_all(&sage.fixtures);
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let fixtures = load(SNAPSHOT_PATH).unwrap();
+        assert!(fixtures.len() >= 20);
+        check_all(&fixtures);
+    }
+
+    #[test]
+    fn test_instance_serialization() {
+        let sis = SIS::new(512, 12289u64.into(), 5833.9, 1024, Norm::L2).with_label("falcon512");
+        let json = serde_json::to_string(&sis).unwrap();
+        assert_eq!(
+            json,
+            r#"{"h":512,"w":1024,"q":"12289","length_bound":5833.9,"norm":"L2","label":"falcon512"}"#
+        );
+        let sis_: SIS = serde_json::from_str(&json).unwrap();
+        assert_eq!(sis_.to_string(), sis.to_string());
+        assert_eq!(sis_.label(), Some("falcon512"));
+
+        let lwe = LWE::with_unlimited_samples(1024, (1u128 << 100).into(), 3.2)
+            .with_secret_dist(SecretDist::SparseTernary { hamming_weight: 64 });
+        let json = serde_json::to_string(&lwe).unwrap();
+        let lwe_: LWE = serde_json::from_str(&json).unwrap();
+        assert_eq!(lwe_.to_string(), lwe.to_string());
+        // The secret distribution defaults to the normal form
+        let lwe: LWE =
+            serde_json::from_str(r#"{"n":512,"q":"3329","error_std_dev":1.2,"max_samples":512}"#)
+                .unwrap();
+        assert_eq!(
+            lwe.to_string(),
+            LWE::new(512, 3329u64.into(), 1.2, 512).to_string()
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn record_sage_fixtures() {
+        let sage = record_sage().unwrap();
+        println!(
+            "lattice-estimator {} (SageMath {})",
+            sage.estimator_commit, sage.sage_version
+        );
+        for fixture in sage.fixtures {
+            println!(
+                "{:?} under {}: {}",
+                fixture.params, fixture.estimator_name, fixture.expected_log2_cost
+            );
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn record_fixtures() {
+        for fixture in record_current().unwrap() {
+            println!(
+                "{:?} under {}: {}",
+                fixture.params, fixture.estimator_name, fixture.expected_log2_cost
+            );
+        }
+    }
+}
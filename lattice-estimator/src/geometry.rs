@@ -0,0 +1,114 @@
+//! Expected lengths of short vectors in random lattices and BKZ-reduced bases.
+//! Volumes and lengths are given in the log domain, as their $\log\_2$.
+use std::f64::consts::{E, PI};
+
+use crate::reduction::bkz_delta;
+use crate::simulator::gsa_log2_sq_norm;
+
+/// Largest block size returned by [`root_hermite_to_blocksize`].
+pub const MAX_BLOCK_SIZE: usize = 1 << 20;
+
+/// Return $\log\_2$ of the length of the shortest vector of a `dim`-dimensional lattice with volume $2^\texttt{log2\\_volume}$ predicted by the Gaussian heuristic, i.e., of $\frac{\Gamma(d/2 + 1)^{1/d}}{\sqrt{\pi}} \mathrm{vol}^{1/d}$.
+pub fn gaussian_heuristic(log2_volume: f64, dim: usize) -> f64 {
+    let d = dim as f64;
+    ((d / 2. + 1.).ln_gamma().0 / d - 0.5 * PI.ln()) / 2f64.ln() + log2_volume / d
+}
+
+/// Return $\log\_2$ of the asymptotic approximation $\sqrt{d / (2 \pi e)} \mathrm{vol}^{1/d}$ of the Gaussian heuristic, see [`gaussian_heuristic`].
+pub fn gaussian_heuristic_asymptotic(log2_volume: f64, dim: usize) -> f64 {
+    let d = dim as f64;
+    0.5 * (d / (2. * PI * E)).log2() + log2_volume / d
+}
+
+/// Return $\log\_2$ of the expected length $\delta^d \mathrm{vol}^{1/d}$ of the first vector of a BKZ-`block_size`-reduced basis of a `d`-dimensional lattice with volume $2^\texttt{log2\\_volume}$.
+pub fn expected_bkz_first_length(d: usize, block_size: usize, log2_volume: f64) -> f64 {
+    d as f64 * bkz_delta(block_size).log2() + log2_volume / d as f64
+}
+
+/// Return $\log\_2$ of the expected length $\delta^{d - 1 - 2i} \mathrm{vol}^{1/d}$ of the `i`-th Gram-Schmidt vector of a BKZ-`block_size`-reduced basis of a `d`-dimensional lattice with volume $2^\texttt{log2\\_volume}$ under the geometric series assumption, see [`gsa_log2_sq_norm`].
+pub fn expected_gram_schmidt_length(
+    d: usize,
+    block_size: usize,
+    log2_volume: f64,
+    i: usize,
+) -> f64 {
+    0.5 * gsa_log2_sq_norm(d, log2_volume, block_size, i)
+}
+
+/// Return $\log\_2$ of the expected length $\sigma \sqrt{k}$ of the projection onto a `dim`-dimensional subspace of a vector whose coordinates have standard deviation $\sigma = 2^\texttt{log2\\_std\\_dev}$.
+pub fn expected_projected_length(log2_std_dev: f64, dim: usize) -> f64 {
+    log2_std_dev + 0.5 * (dim as f64).log2()
+}
+
+/// Return the smallest block size $\beta \geq 2$ for which BKZ achieves a root Hermite factor of at most `delta`, i.e., the inverse of [`bkz_delta`], or [`MAX_BLOCK_SIZE`] if no block size up to it does.
+pub fn root_hermite_to_blocksize(delta: f64) -> usize {
+    // bkz_delta is non-increasing in the block size, so binary search for the first block size achieving delta
+    let (mut lo, mut hi) = (2, MAX_BLOCK_SIZE);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if bkz_delta(mid) <= delta {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    lo
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gaussian_heuristic() {
+        // For the unit-volume lattice, the Gaussian heuristic is the radius of the ball of volume 1
+        for dim in [1, 2, 10, 100, 1000] {
+            let d = dim as f64;
+            let log2_ball_volume = 0.5 * d * PI.log2() + d * gaussian_heuristic(0., dim)
+                - (d / 2. + 1.).ln_gamma().0 / 2f64.ln();
+            assert!(
+                log2_ball_volume.abs() < 1e-9,
+                "dim {dim}: {log2_ball_volume}"
+            );
+        }
+        // Z^2 has volume 1, and a disk of area 1 has radius 1/sqrt(pi)
+        assert!((gaussian_heuristic(0., 2) - (1. / PI.sqrt()).log2()).abs() < 1e-12);
+        // Scaling the lattice by 2 scales the volume by 2^d and the lengths by 2
+        assert!((gaussian_heuristic(100., 100) - gaussian_heuristic(0., 100) - 1.).abs() < 1e-12);
+        // The asymptotic approximation is accurate in large dimensions
+        assert!(
+            (gaussian_heuristic(50., 1000) - gaussian_heuristic_asymptotic(50., 1000)).abs() < 0.01
+        );
+    }
+
+    #[test]
+    fn test_expected_bkz_first_length() {
+        let log2_length = expected_bkz_first_length(500, 100, 500. * 12.);
+        assert_eq!(log2_length, 500. * bkz_delta(100).log2() + 12.);
+        assert!(expected_bkz_first_length(500, 200, 6000.) < log2_length);
+    }
+
+    #[test]
+    fn test_expected_gram_schmidt_length() {
+        // The Gram-Schmidt lengths decrease geometrically with ratio delta^2, and their product is the volume
+        let (d, block_size, log2_volume) = (500, 100, 500. * 12.);
+        let lengths: Vec<f64> = (0..d)
+            .map(|i| expected_gram_schmidt_length(d, block_size, log2_volume, i))
+            .collect();
+        for w in lengths.windows(2) {
+            assert!((w[0] - w[1] - 2. * bkz_delta(block_size).log2()).abs() < 1e-9);
+        }
+        assert!((lengths.iter().sum::<f64>() - log2_volume).abs() < 1e-6);
+        // Projecting onto k coordinates keeps sqrt(k) standard deviations
+        assert_eq!(expected_projected_length(3., 16), 5.);
+    }
+
+    #[test]
+    fn test_root_hermite_to_blocksize() {
+        for block_size in 2..=2000 {
+            assert_eq!(root_hermite_to_blocksize(bkz_delta(block_size)), block_size);
+        }
+        assert_eq!(root_hermite_to_blocksize(2.), 2);
+        assert_eq!(root_hermite_to_blocksize(1.), MAX_BLOCK_SIZE);
+    }
+}
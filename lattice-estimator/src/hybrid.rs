@@ -0,0 +1,211 @@
+//! The hybrid lattice-reduction and meet-in-the-middle attack on LWE with sparse ternary secrets [HG07].
+use std::f64::consts::SQRT_2;
+
+use crate::attack::{AttackEstimate, AttackKind, CostConstraint};
+use crate::geometry::expected_gram_schmidt_length;
+use crate::lwe::{SecretDist, LWE};
+use crate::reduction::{
+    bkz_cost, bkz_memory_cost, find_optimal_block_size, log2_add, BkzSchedule, Estimates,
+};
+
+/// $\log\_2 \binom{n}{k}$.
+pub(crate) fn log2_binomial(n: usize, k: usize) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+    let k = k.min(n - k);
+    (0..k)
+        .map(|i| ((n - i) as f64 / (i + 1) as f64).log2())
+        .sum()
+}
+
+/// $\log\_2$ of the probability that `k` coordinates of a secret of dimension `n` with `hamming_weight` non-zero coefficients contain exactly `weight` non-zero coefficients.
+fn log2_weight_probability(n: usize, hamming_weight: usize, k: usize, weight: usize) -> f64 {
+    log2_binomial(hamming_weight, weight) + log2_binomial(n - hamming_weight, k - weight)
+        - log2_binomial(n, k)
+}
+
+/// $\log\_2$ of the probability that Babai's nearest plane algorithm on a BKZ-`block_size`-reduced basis of a `d`-dimensional lattice with volume $2^\texttt{log2\\_volume}$ recovers an error whose coordinates have standard deviation `sigma`, i.e., $\prod\_i \mathrm{erf}(\lVert b\_i^\* \rVert / (2 \sqrt{2} \sigma))$ [LP11], for the Gram-Schmidt lengths predicted by the GSA (see [`expected_gram_schmidt_length`]).
+fn log2_nearest_plane_probability(
+    d: usize,
+    block_size: usize,
+    log2_volume: f64,
+    sigma: f64,
+) -> f64 {
+    (0..d)
+        .map(|i| {
+            let length = expected_gram_schmidt_length(d, block_size, log2_volume, i).exp2();
+            (length / (2. * SQRT_2 * sigma)).erf().log2()
+        })
+        .sum()
+}
+
+/// Return the cost of the hybrid attack guessing the last `k` coordinates of a sparse ternary secret, or `None` if the lattice part does not succeed.
+/// The guessed part of the secret has $w = \lfloor h k / n \rceil$ non-zero coordinates, which a meet-in-the-middle search over both halves finds in time $\sqrt{\binom{k}{w} 2^w}$ (times the cost of Babai's nearest plane algorithm in dimension $d$).
+/// The lattice part is the embedding lattice of the LWE instance of dimension $n - k$ whose secret has the remaining $h - w$ non-zero coordinates, with as many samples as the primal attack on it uses.
+/// The attack succeeds if the guessed part has weight $w$, and if the nearest plane algorithm on the BKZ-reduced basis of the lattice part recovers the error, so that both halves of the search collide (admissibility), see [`log2_nearest_plane_probability`]; it is repeated according to the product of both probabilities.
+/// The block size is chosen to minimize the total cost, trading the cost of BKZ for the success probability of the nearest plane algorithm.
+fn hybrid_attack_cost_k(
+    lwe: &LWE,
+    hamming_weight: usize,
+    est: Estimates,
+    k: usize,
+) -> Option<AttackEstimate> {
+    let n = lwe.n;
+    let weight = (hamming_weight as f64 * k as f64 / n as f64).round() as usize;
+    let remaining = LWE {
+        n: n - k,
+        q: lwe.q.clone(),
+        error_std_dev: lwe.error_std_dev,
+        max_samples: lwe.max_samples,
+        secret_dist: SecretDist::SparseTernary {
+            hamming_weight: hamming_weight - weight,
+        },
+    };
+    let lattice = remaining
+        .security_level_internal(est, &CostConstraint::default(), BkzSchedule::Fixed)
+        .ok()?;
+    let AttackKind::PrimalUsvp { samples, .. } = lattice.attack else {
+        return None;
+    };
+
+    let d = lattice.dim;
+    let (log_q, log2_volume) = (remaining.log_q(), remaining.log2_embedding_volume(samples));
+    let log2_search_space = log2_binomial(k, weight) + weight as f64;
+    let log2_mitm = 0.5 * log2_search_space + 2. * (d as f64).log2();
+    let log2_weight = log2_weight_probability(n, hamming_weight, k, weight);
+    // Stronger reduction is more expensive, but makes the nearest plane algorithm more likely to succeed
+    let cost = |block_size: usize| {
+        let log2_nearest_plane =
+            log2_nearest_plane_probability(d, block_size, log2_volume, lwe.error_std_dev);
+        log2_add(bkz_cost(est, block_size, d, log_q, log2_volume), log2_mitm)
+            - log2_weight
+            - log2_nearest_plane
+    };
+    let (block_size, log2_cost_time) = find_optimal_block_size(cost, 2, d);
+    log2_cost_time.is_finite().then(|| AttackEstimate {
+        attack: AttackKind::Hybrid { guessed: k },
+        block_size,
+        log2_cost_time,
+        log2_cost_memory: Some(bkz_memory_cost(est, block_size).max(0.5 * log2_search_space)),
+        ..lattice
+    })
+}
+
+/// Return the cheapest hybrid attack on `lwe` under the cost model `est`, optimizing over the number of guessed coordinates, or `None` if the instance is invalid, the secret is not sparse ternary or no attack succeeds.
+pub fn hybrid_attack_cost(lwe: &LWE, est: Estimates) -> Option<AttackEstimate> {
+    let SecretDist::SparseTernary { hamming_weight } = lwe.secret_dist else {
+        return None;
+    };
+    // At least one coordinate must be guessed and one left to the lattice part
+    if lwe.n < 2 || hamming_weight > lwe.n {
+        return None;
+    }
+    lwe.check_params().ok()?;
+    // Guessing trades lattice reduction cost (decreasing in k) for search cost (increasing in k)
+    let cost = |k: usize| {
+        hybrid_attack_cost_k(lwe, hamming_weight, est, k)
+            .map_or(f64::INFINITY, |attack| attack.log2_cost_time)
+    };
+    let (k, lambda) = find_optimal_block_size(cost, 1, lwe.n - 1);
+    if lambda.is_infinite() {
+        return None;
+    }
+    hybrid_attack_cost_k(lwe, hamming_weight, est, k)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_log2_binomial() {
+        assert_eq!(log2_binomial(10, 0), 0.);
+        assert!((log2_binomial(10, 3) - 120f64.log2()).abs() < 1e-12);
+        assert!((log2_binomial(10, 7) - 120f64.log2()).abs() < 1e-12);
+        assert_eq!(log2_binomial(3, 4), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_hybrid_sparse_secret() {
+        let dense = LWE::new(1024, (1u64 << 32).into(), 3.2, 1024);
+        assert!(hybrid_attack_cost(&dense, Estimates::Matzov).is_none());
+
+        let sparse = LWE::new(1024, (1u64 << 32).into(), 3.2, 1024)
+            .with_secret_dist(SecretDist::SparseTernary { hamming_weight: 64 });
+        let primal = sparse
+            .security_level_internal(
+                Estimates::Matzov,
+                &CostConstraint::default(),
+                BkzSchedule::Fixed,
+            )
+            .unwrap();
+        let hybrid = hybrid_attack_cost(&sparse, Estimates::Matzov).unwrap();
+        println!("primal: {primal:?}\nhybrid: {hybrid:?}");
+        assert!(matches!(hybrid.attack, AttackKind::Hybrid { .. }));
+        assert!(hybrid.log2_cost_time < primal.log2_cost_time - 10.);
+        assert_eq!(sparse.best_attack().unwrap().attack, hybrid.attack);
+    }
+
+    #[test]
+    fn test_nearest_plane_probability() {
+        let log2_probability = |block_size: usize, sigma: f64| {
+            log2_nearest_plane_probability(1000, block_size, 500. * 12., sigma)
+        };
+        assert!(log2_probability(300, 3.2) < 0.);
+        // Stronger reduction balances the profile, and larger errors are harder to recover
+        assert!(log2_probability(400, 3.2) > log2_probability(300, 3.2));
+        assert!(log2_probability(300, 6.4) < log2_probability(300, 3.2));
+    }
+
+    #[test]
+    fn test_hybrid_nearest_plane_cost() {
+        // The nearest plane probability raises the cost above that of the weight guess alone
+        let sparse = LWE::new(1024, (1u64 << 32).into(), 3.2, 1024)
+            .with_secret_dist(SecretDist::SparseTernary { hamming_weight: 64 });
+        let AttackKind::Hybrid { guessed } = hybrid_attack_cost(&sparse, Estimates::Matzov)
+            .unwrap()
+            .attack
+        else {
+            panic!("the hybrid attack must guess coordinates");
+        };
+        for k in [128, 256, guessed] {
+            let hybrid = hybrid_attack_cost_k(&sparse, 64, Estimates::Matzov, k).unwrap();
+            let weight = (64. * k as f64 / 1024.).round() as usize;
+            let remaining = LWE::new(1024 - k, (1u64 << 32).into(), 3.2, 1024).with_secret_dist(
+                SecretDist::SparseTernary {
+                    hamming_weight: 64 - weight,
+                },
+            );
+            let samples = hybrid.dim - (1024 - k) - 1;
+            let bkz = bkz_cost(
+                Estimates::Matzov,
+                hybrid.block_size,
+                hybrid.dim,
+                32.,
+                remaining.log2_embedding_volume(samples),
+            );
+            let log2_mitm =
+                0.5 * (log2_binomial(k, weight) + weight as f64) + 2. * (hybrid.dim as f64).log2();
+            let weight_only =
+                log2_add(bkz, log2_mitm) - log2_weight_probability(1024, 64, k, weight);
+            assert!(hybrid.log2_cost_time > weight_only, "k = {k}");
+        }
+    }
+
+    #[test]
+    fn test_hybrid_invalid_params() {
+        let sparse = |n: usize, q: u64, hamming_weight: usize| {
+            LWE::new(n, q.into(), 3.2, 1024)
+                .with_secret_dist(SecretDist::SparseTernary { hamming_weight })
+        };
+        assert!(hybrid_attack_cost(&sparse(0, 1 << 32, 0), Estimates::Matzov).is_none());
+        assert!(hybrid_attack_cost(&sparse(1, 1 << 32, 1), Estimates::Matzov).is_none());
+        assert!(hybrid_attack_cost(&sparse(1024, 1, 64), Estimates::Matzov).is_none());
+        assert!(hybrid_attack_cost(&sparse(64, 1 << 32, 128), Estimates::Matzov).is_none());
+        assert!(sparse(1, 1 << 32, 1)
+            .estimate_all(&[Estimates::Matzov], &CostConstraint::default())
+            .iter()
+            .all(|attack| !matches!(attack.attack, AttackKind::Hybrid { .. })));
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt;
+use std::fmt::{Debug, Display};
+
+use num_traits::ToPrimitive;
+
+use crate::errors::EstimatorError;
+use crate::norms::Norm;
+use crate::sis::SIS;
+
+/// k-SIS parameters [BF11]: given `k` short solutions (hints) of the SIS instance `sis`, find a short solution outside of their span.
+/// For $k = 0$, this is `sis` itself.
+pub struct KSIS {
+    pub sis: SIS,
+    pub k: usize,
+}
+
+impl Display for KSIS {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "KSIS[k={}, {}]", self.k, self.sis)
+    }
+}
+
+impl Debug for KSIS {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "KSIS[k={}, {}]", self.k, self.sis)
+    }
+}
+
+/// The security estimate of a k-SIS instance, see [`KSIS::security_level`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KSISEstimate {
+    /// Security of the underlying SIS instance, ignoring the hints.
+    pub raw_security: f64,
+    /// Security of the SIS instance that k-SIS reduces from, which lower-bounds the security of k-SIS.
+    pub reduced_security: f64,
+    /// $\log\_2$ of the $\ell\_2$ length bound of the reduced SIS instance.
+    pub log2_reduced_length_bound: f64,
+    /// Whether the reduction is vacuous, i.e., $k \geq m$ or the reduced length bound is at least $q$, in which case the reduced security is 0.
+    pub trivial: bool,
+}
+
+impl KSIS {
+    pub fn new(sis: SIS, k: usize) -> Self {
+        KSIS { sis, k }
+    }
+
+    /// Return $\log\_2$ of the $\ell\_2$ length bound of the SIS instance that k-SIS reduces from [BF11]: a solution of k-SIS is projected orthogonally to the hints (scaled by the determinant of their Gram matrix to stay integral), which multiplies its length by up to $(\sqrt{k} \beta)^k$ by Hadamard's inequality, where $\beta$ is the $\ell\_2$ length bound of the hints.
+    /// For the $\ell\_\infty$ norm, $\beta = \sqrt{m} \beta\_\infty$.
+    pub fn log2_reduced_length_bound(&self) -> f64 {
+        let sis = &self.sis;
+        let log2_beta = match sis.norm {
+            Norm::L2 => sis.length_bound.log2(),
+            Norm::Linf => sis.length_bound.log2() + 0.5 * (sis.w as f64).log2(),
+        };
+        if self.k == 0 {
+            return log2_beta;
+        }
+        let k = self.k as f64;
+        log2_beta + k * (0.5 * k.log2() + log2_beta)
+    }
+
+    /// Return the security of the underlying SIS instance (see [`SIS::security_level`]) and the security of the SIS instance with $m - k$ columns and the length bound [`KSIS::log2_reduced_length_bound`] that k-SIS reduces from, which is a conservative estimate of the security of k-SIS.
+    /// If $k \geq m$, the hints span the kernel (or the instance has no columns left) and the reduction is vacuous; the same holds if the reduced length bound is at least $q$. In both cases, the reduced security is 0 and the estimate is flagged as trivial.
+    /// Returns the errors of [`SIS::security_level`] on either instance.
+    pub fn security_level(&self) -> Result<KSISEstimate, EstimatorError> {
+        let sis = &self.sis;
+        let raw_security = sis.security_level()?;
+        let log2_reduced_length_bound = self.log2_reduced_length_bound();
+        if self.k == 0 {
+            return Ok(KSISEstimate {
+                raw_security,
+                reduced_security: raw_security,
+                log2_reduced_length_bound,
+                trivial: sis.is_trivial(),
+            });
+        }
+        let log_q = sis.q.to_f64().unwrap().log2();
+        if self.k >= sis.w || log2_reduced_length_bound >= log_q {
+            return Ok(KSISEstimate {
+                raw_security,
+                reduced_security: 0.,
+                log2_reduced_length_bound,
+                trivial: true,
+            });
+        }
+        let reduced = SIS::new(
+            sis.h,
+            sis.q.clone(),
+            log2_reduced_length_bound.exp2(),
+            sis.w - self.k,
+            Norm::L2,
+        );
+        Ok(KSISEstimate {
+            raw_security,
+            reduced_security: reduced.security_level()?,
+            log2_reduced_length_bound,
+            trivial: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use num_bigint::BigUint;
+
+    use super::*;
+
+    fn sis() -> SIS {
+        SIS::new(128, BigUint::from(1u64 << 60), 256., 2048, Norm::L2)
+    }
+
+    #[test]
+    fn test_ksis_k_zero() {
+        let estimate = KSIS::new(sis(), 0).security_level().unwrap();
+        assert_eq!(estimate.raw_security, sis().security_level().unwrap());
+        assert_eq!(estimate.reduced_security, estimate.raw_security);
+        assert!(!estimate.trivial);
+    }
+
+    #[test]
+    fn test_ksis_monotone() {
+        let mut previous = KSIS::new(sis(), 0).security_level().unwrap();
+        for k in 1..=5 {
+            let estimate = KSIS::new(sis(), k).security_level().unwrap();
+            println!("k = {k}: {estimate:?}");
+            assert_eq!(estimate.raw_security, previous.raw_security);
+            assert!(estimate.log2_reduced_length_bound > previous.log2_reduced_length_bound);
+            assert!(estimate.reduced_security <= previous.reduced_security);
+            assert!(!estimate.trivial);
+            previous = estimate;
+        }
+        assert!(previous.reduced_security < previous.raw_security);
+    }
+
+    #[test]
+    fn test_ksis_trivial() {
+        for k in [2048, 4096] {
+            let estimate = KSIS::new(sis(), k).security_level().unwrap();
+            assert!(estimate.trivial);
+            assert_eq!(estimate.reduced_security, 0.);
+        }
+        // The reduced length bound exceeds q
+        assert!(KSIS::new(sis(), 40).security_level().unwrap().trivial);
+    }
+}
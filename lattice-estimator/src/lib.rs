@@ -1,8 +1,27 @@
 #![feature(try_trait_v2)]
 #![feature(int_roundings)]
+#![feature(float_gamma)]
+#![feature(float_erf)]
 
+pub mod attack;
+pub mod combinatorial;
 pub mod errors;
+pub mod fixtures;
+pub mod geometry;
+pub mod hybrid;
+pub mod ksis;
+pub mod lwe;
+pub mod lwr;
+pub mod mlwe;
 pub mod msis;
 pub mod norms;
+pub mod ntru;
+pub mod param_search;
+pub mod presets;
+pub mod reduction;
 pub mod sage_util;
+mod serde_biguint;
+pub mod short_vectors;
+pub mod simulator;
 pub mod sis;
+pub mod table;
@@ -0,0 +1,647 @@
+use std::fmt;
+use std::fmt::{Debug, Display};
+
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+
+use serde::{Deserialize, Serialize};
+
+use crate::attack::{AttackEstimate, AttackKind, CostConstraint};
+use crate::errors::EstimatorError;
+use crate::geometry::{expected_gram_schmidt_length, expected_projected_length};
+use crate::hybrid::hybrid_attack_cost;
+use crate::reduction::{
+    bkz_cost, bkz_memory_cost, find_optimal_block_size, log2_add, svp_cost, BkzSchedule, Estimates,
+};
+
+/// Distribution of the LWE secret.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum SecretDist {
+    /// The secret is distributed as the error (normal form).
+    #[default]
+    NormalForm,
+    /// Uniformly random secret modulo $q$; the attacks first transform the instance to normal form.
+    UniformModQ,
+    /// Uniformly random ternary secret with exactly `hamming_weight` non-zero coefficients.
+    SparseTernary { hamming_weight: usize },
+    /// Ternary secret whose coefficients are independently non-zero with probability `p_nonzero` (and then $\pm 1$ with equal probability).
+    Ternary { p_nonzero: f64 },
+    /// Centered binomial distribution with parameter `eta`, i.e., the difference of the sums of two vectors of `eta` uniform bits, as in Kyber.
+    CBD { eta: usize },
+    /// Discrete Gaussian distribution with standard deviation `sigma`.
+    Gaussian { sigma: f64 },
+}
+
+/// Return the probability that a discrete Gaussian with standard deviation `sigma` (centered at 0) is 0.
+fn discrete_gaussian_zero_probability(sigma: f64) -> f64 {
+    if sigma.is_nan() || sigma <= 0. {
+        return 1.;
+    }
+    let tail = (10. * sigma).ceil() as i64 + 1;
+    let mass: f64 = (-tail..=tail)
+        .map(|k| (-((k * k) as f64) / (2. * sigma * sigma)).exp())
+        .sum();
+    1. / mass
+}
+
+impl SecretDist {
+    /// Return the standard deviation of the coefficients of a secret of dimension `n`, for an LWE instance with modulus $2^\texttt{log\\_q}$ and error standard deviation `error_std_dev`.
+    pub fn std_dev(&self, n: usize, log_q: f64, error_std_dev: f64) -> f64 {
+        match *self {
+            SecretDist::NormalForm => error_std_dev,
+            SecretDist::UniformModQ => ((2. * log_q).exp2() - 1.).sqrt() / 12f64.sqrt(),
+            SecretDist::SparseTernary { hamming_weight } => {
+                (hamming_weight as f64 / n as f64).sqrt()
+            }
+            SecretDist::Ternary { p_nonzero } => p_nonzero.sqrt(),
+            SecretDist::CBD { eta } => (eta as f64 / 2.).sqrt(),
+            SecretDist::Gaussian { sigma } => sigma,
+        }
+    }
+
+    /// Return the expected fraction of non-zero coefficients of a secret of dimension `n`, for an LWE instance with modulus $2^\texttt{log\\_q}$ and error standard deviation `error_std_dev`.
+    pub fn density(&self, n: usize, log_q: f64, error_std_dev: f64) -> f64 {
+        match *self {
+            SecretDist::NormalForm => 1. - discrete_gaussian_zero_probability(error_std_dev),
+            SecretDist::UniformModQ => 1. - (-log_q).exp2(),
+            SecretDist::SparseTernary { hamming_weight } => hamming_weight as f64 / n as f64,
+            SecretDist::Ternary { p_nonzero } => p_nonzero,
+            // The probability of 0 is binom(2 eta, eta) / 4^eta
+            SecretDist::CBD { eta } => {
+                1. - (0..eta)
+                    .map(|i| (2 * eta - i) as f64 / ((eta - i) as f64 * 4.))
+                    .product::<f64>()
+            }
+            SecretDist::Gaussian { sigma } => 1. - discrete_gaussian_zero_probability(sigma),
+        }
+    }
+
+    /// Return true iff the coefficients of the secret are small, i.e., the distribution is not the normal form or uniform modulo $q$.
+    pub fn is_small(&self) -> bool {
+        !matches!(self, SecretDist::NormalForm | SecretDist::UniformModQ)
+    }
+}
+
+/// LWE parameters for instances $(A, b = A s + e) \in \mathbb{Z}\_\texttt{q}^{m\times\texttt{n}} \times \mathbb{Z}\_\texttt{q}^m$, where the entries of $e$ are distributed with standard deviation `error_std_dev` and $s$ is distributed according to `secret_dist`.
+/// The number of samples $m$ available to the adversary is at most `max_samples`, or unlimited if `max_samples` is `None`.
+/// The serialized form has the fields `n`, `q` (a decimal string), `error_std_dev`, `max_samples` (`null` for unlimited samples) and `secret_dist` (defaulting to the normal form).
+#[derive(Serialize, Deserialize)]
+pub struct LWE {
+    pub(crate) n: usize,
+    #[serde(with = "crate::serde_biguint")]
+    pub(crate) q: BigUint,
+    pub(crate) error_std_dev: f64,
+    pub(crate) max_samples: Option<usize>,
+    #[serde(default)]
+    pub(crate) secret_dist: SecretDist,
+}
+
+impl Display for LWE {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "LWE[n={}, q={}, error_std_dev={}, max_samples={}, secret_dist={:?}]",
+            self.n,
+            self.q,
+            self.error_std_dev,
+            self.max_samples
+                .map_or("unlimited".to_string(), |m| m.to_string()),
+            self.secret_dist
+        )
+    }
+}
+
+impl Debug for LWE {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "LWE[n={}, q={}, error_std_dev={}, max_samples={}, secret_dist={:?}]",
+            self.n,
+            self.q,
+            self.error_std_dev,
+            self.max_samples
+                .map_or("unlimited".to_string(), |m| m.to_string()),
+            self.secret_dist
+        )
+    }
+}
+
+/// Number of samples (as a multiple of $n$) considered by the attacks if the number of samples is unlimited; the primal attack never benefits from more samples in practice.
+const UNLIMITED_SAMPLES_FACTOR: usize = 8;
+
+impl LWE {
+    /// Construct an LWE instance with at most `max_samples` samples.
+    pub const fn new(n: usize, q: BigUint, error_std_dev: f64, max_samples: usize) -> Self {
+        LWE {
+            n,
+            q,
+            error_std_dev,
+            max_samples: Some(max_samples),
+            secret_dist: SecretDist::NormalForm,
+        }
+    }
+
+    /// Construct an LWE instance with an unlimited number of samples.
+    pub const fn with_unlimited_samples(n: usize, q: BigUint, error_std_dev: f64) -> Self {
+        LWE {
+            n,
+            q,
+            error_std_dev,
+            max_samples: None,
+            secret_dist: SecretDist::NormalForm,
+        }
+    }
+
+    /// Return the same instance with secret distribution `secret_dist`.
+    pub fn with_secret_dist(self, secret_dist: SecretDist) -> Self {
+        LWE {
+            secret_dist,
+            ..self
+        }
+    }
+
+    /// Return the largest number of samples the attacks may use.
+    fn sample_bound(&self) -> usize {
+        self.max_samples
+            .unwrap_or(UNLIMITED_SAMPLES_FACTOR * self.n)
+    }
+
+    pub(crate) fn log_q(&self) -> f64 {
+        self.q.to_f64().unwrap().log2()
+    }
+
+    /// Return the factor $\nu = \sigma\_e / \sigma\_s$ by which the primal attacks scale the secret coordinates of the embedding lattice [BG14], or `None` if they do not rescale.
+    /// Rescaling makes the secret and error coordinates of the target vector equally large, and increases the volume of the lattice by $\nu^n$.
+    /// It is applied iff the secret is small (see [`SecretDist::is_small`]) and its standard deviation $\sigma\_s$ is below that of the error $\sigma\_e$; otherwise, the attacks treat the secret as distributed as the error, which only overestimates their success.
+    pub fn secret_scaling(&self) -> Option<f64> {
+        if !self.secret_dist.is_small() {
+            return None;
+        }
+        let std_dev = self
+            .secret_dist
+            .std_dev(self.n, self.log_q(), self.error_std_dev);
+        (std_dev > 0. && std_dev < self.error_std_dev).then(|| self.error_std_dev / std_dev)
+    }
+
+    /// Return $\log\_2$ of the volume of the (rescaled, see [`LWE::secret_scaling`]) embedding lattice with `m` samples, i.e., $q^m \nu^n$.
+    pub(crate) fn log2_embedding_volume(&self, m: usize) -> f64 {
+        m as f64 * self.log_q() + self.n as f64 * self.secret_scaling().map_or(0., f64::log2)
+    }
+
+    /// Return true iff the primal uSVP attack with BKZ block size `block_size` using `m` samples succeeds, i.e., iff the projection of the embedded error vector on the last `block_size` Gram-Schmidt vectors is shorter than the `d - block_size`-th Gram-Schmidt vector [ADPS16]:
+    /// $\sigma \sqrt{\beta} \leq \delta^{2\beta - d - 1} q^{m/d}$, where $d = n + m + 1$.
+    /// This is [`LWE::two_step_succeeds`] with a sieving dimension equal to the block size.
+    fn usvp_succeeds(&self, block_size: usize, m: usize) -> bool {
+        self.two_step_succeeds(block_size, block_size, m)
+    }
+
+    /// Return true iff a sieve in dimension `sieve_dim` on the last `sieve_dim` Gram-Schmidt vectors of the BKZ-`block_size`-reduced embedding lattice with `m` samples recovers the projection of the embedded error vector, i.e., iff that projection is shorter than the `d - sieve_dim`-th Gram-Schmidt vector [ADH+19]:
+    /// $\sigma \sqrt{\eta} \leq \delta^{2\eta - d - 1} q^{m/d}$, where $d = n + m + 1$ and $\delta$ is the root Hermite factor of BKZ-$\beta$.
+    /// Both sides are compared in the log domain, see [`expected_projected_length`] and [`expected_gram_schmidt_length`].
+    fn two_step_succeeds(&self, block_size: usize, sieve_dim: usize, m: usize) -> bool {
+        let d = self.n + m + 1;
+        let log2_volume = self.log2_embedding_volume(m);
+        expected_projected_length(self.error_std_dev.log2(), sieve_dim)
+            <= expected_gram_schmidt_length(d, block_size, log2_volume, d - sieve_dim)
+    }
+
+    /// Return an error if the instance is invalid, i.e., unless $n \geq 1$, $q \geq 2$, the error has positive standard deviation and at least one sample is available.
+    pub(crate) fn check_params(&self) -> Result<(), EstimatorError> {
+        if self.n == 0 || self.max_samples == Some(0) {
+            return Err(EstimatorError::InvalidParameters(format!(
+                "{self}: the dimension and number of samples must be positive"
+            )));
+        }
+        if self.q < BigUint::from(2u64) {
+            return Err(EstimatorError::InvalidParameters(format!(
+                "{self}: the modulus must be at least 2"
+            )));
+        }
+        if self.error_std_dev.is_nan() || self.error_std_dev <= 0. {
+            return Err(EstimatorError::InvalidParameters(format!(
+                "{self}: the error standard deviation must be positive"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Return a native estimate of the hardness of `LWE\[n, q, error_std_dev, m\]` against the primal uSVP attack under the cost model `est`.
+    /// Small secrets are exploited by rescaling the secret part of the embedding lattice by [`LWE::secret_scaling`], which changes its volume accordingly.
+    /// Returns [`EstimatorError::InvalidParameters`] for invalid instances, and [`EstimatorError::NonConvergent`] if the attack does not succeed for any block size satisfying `constraint`.
+    /// For each block size, the attack uses the number of samples (at most `max_samples`) minimizing its cost, and the attack is reported as limited by the number of samples if it uses all of them, see [`SIS::security_level_internal`](crate::sis::SIS::security_level_internal) for the meaning of `constraint` and `schedule`.
+    pub fn security_level_internal(
+        &self,
+        est: Estimates,
+        constraint: &CostConstraint,
+        schedule: BkzSchedule,
+    ) -> Result<AttackEstimate, EstimatorError> {
+        self.check_params()?;
+        let non_convergent = || {
+            EstimatorError::NonConvergent(format!(
+                "the primal attack does not solve {self} under {est} with {constraint:?}"
+            ))
+        };
+        let max_block_size = constraint
+            .max_block_size(est, self.n + self.sample_bound() + 1)
+            .ok_or_else(non_convergent)?;
+        let (block_size, lambda) = find_optimal_block_size(
+            |block_size| {
+                if !constraint.is_satisfied(est, block_size) {
+                    return f64::INFINITY;
+                }
+                self.best_num_samples(est, schedule, block_size)
+                    .map_or(f64::INFINITY, |(_, cost)| cost)
+            },
+            2,
+            max_block_size,
+        );
+        if lambda.is_infinite() {
+            return Err(non_convergent());
+        }
+        let (m, _) = self
+            .best_num_samples(est, schedule, block_size)
+            .ok_or_else(non_convergent)?;
+        Ok(AttackEstimate {
+            attack: AttackKind::PrimalUsvp {
+                samples: m,
+                sample_limited: self.max_samples == Some(m),
+                secret_scaling: self.secret_scaling(),
+            },
+            estimate: est,
+            block_size,
+            schedule,
+            dim: self.n + m + 1,
+            log2_cost_time: lambda,
+            log2_cost_memory: Some(bkz_memory_cost(est, block_size)),
+        })
+    }
+
+    /// Return the estimates of the primal uSVP attack (using a single BKZ run) and of the two-step primal attack (see [`primal_usvp_two_step`]) satisfying `constraint` under each of the cost models in `estimators`, skipping cost models under which no such attack succeeds.
+    /// For sparse ternary secrets, this includes the estimates of the hybrid attack, see [`hybrid_attack_cost`].
+    pub fn estimate_all(
+        &self,
+        estimators: &[Estimates],
+        constraint: &CostConstraint,
+    ) -> Vec<AttackEstimate> {
+        estimators
+            .iter()
+            .flat_map(|est| {
+                let primal = self
+                    .security_level_internal(*est, constraint, BkzSchedule::Fixed)
+                    .ok();
+                let satisfies_constraint = |attack: &AttackEstimate| {
+                    constraint.max_log2_memory.is_none_or(|max_log2_memory| {
+                        attack
+                            .log2_cost_memory
+                            .is_none_or(|memory| memory <= max_log2_memory)
+                    })
+                };
+                let two_step = primal_usvp_two_step(self, *est).filter(satisfies_constraint);
+                let hybrid = hybrid_attack_cost(self, *est).filter(satisfies_constraint);
+                primal.into_iter().chain(two_step).chain(hybrid)
+            })
+            .collect()
+    }
+
+    /// Return the cheapest attack under the default cost models [`Estimates::DEFAULT`] with unbounded memory, or `None` if no attack succeeds.
+    pub fn best_attack(&self) -> Option<AttackEstimate> {
+        AttackEstimate::best(&self.estimate_all(&Estimates::DEFAULT, &CostConstraint::default()))
+            .cloned()
+    }
+
+    /// Return lambda such that LWE_{n, q, error_std_dev} (with at most `max_samples` samples) is 2^lambda-hard, i.e., the time cost of [`LWE::best_attack`].
+    /// Returns [`EstimatorError::InvalidParameters`] for invalid instances, and [`EstimatorError::NonConvergent`] if no attack succeeds.
+    pub fn security_level(&self) -> Result<f64, EstimatorError> {
+        self.check_params()?;
+        self.best_attack()
+            .map(|attack| attack.log2_cost_time)
+            .ok_or_else(|| EstimatorError::NonConvergent(format!("no attack solves {self}")))
+    }
+
+    /// Return the number of samples (at most `max_samples`) and sieving dimension for which the two-step primal attack with block size `block_size` succeeds at the lowest cost, together with that cost, or `None` if it does not succeed for any number of samples and sieving dimension in `[block_size, d]`.
+    /// A sieving dimension equal to the block size means that the last BKZ tour already finds the solution, so no separate sieve is needed.
+    /// A separate sieve is only considered if all Gram-Schmidt norms of the GSA profile are between $1$ and $q$, i.e., if it coincides with the Z-shape profile (see [`zgsa_simulator`](crate::simulator::zgsa_simulator)): otherwise, the GSA overestimates the norms of the last Gram-Schmidt vectors.
+    fn best_two_step(&self, est: Estimates, block_size: usize) -> Option<(usize, usize, f64)> {
+        let log_q = self.log_q();
+        (block_size.saturating_sub(self.n + 1).max(1)..=self.sample_bound())
+            .filter_map(|m| {
+                let d = self.n + m + 1;
+                let log2_volume = self.log2_embedding_volume(m);
+                let bkz = bkz_cost(est, block_size, d, log_q, log2_volume);
+                if self.usvp_succeeds(block_size, m) {
+                    return Some((m, block_size, bkz));
+                }
+                // The unit vectors of the secret part are scaled by the rescaling factor
+                let log2_min_norm = self.secret_scaling().map_or(0., f64::log2);
+                if expected_gram_schmidt_length(d, block_size, log2_volume, 0) > log_q
+                    || expected_gram_schmidt_length(d, block_size, log2_volume, d - 1)
+                        < log2_min_norm
+                {
+                    return None;
+                }
+                // The projection of the error grows more slowly than the Gram-Schmidt norms with the sieving dimension, so success is monotone in it
+                let (mut lo, mut hi) = (block_size + 1, d + 1);
+                while lo < hi {
+                    let mid = lo + (hi - lo) / 2;
+                    if self.two_step_succeeds(block_size, mid, m) {
+                        hi = mid;
+                    } else {
+                        lo = mid + 1;
+                    }
+                }
+                let sieve_dim = lo;
+                (sieve_dim <= d).then(|| {
+                    let cost = log2_add(bkz, svp_cost(est, sieve_dim, d, log_q));
+                    (m, sieve_dim, cost)
+                })
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+    }
+
+    /// Return the number of samples (at most `max_samples`) for which the primal uSVP attack with block size `block_size` (reached according to `schedule`) succeeds at the lowest cost, together with that cost, or `None` if it does not succeed for any number of samples.
+    fn best_num_samples(
+        &self,
+        est: Estimates,
+        schedule: BkzSchedule,
+        block_size: usize,
+    ) -> Option<(usize, f64)> {
+        let log_q = self.log_q();
+        (block_size.saturating_sub(self.n + 1).max(1)..=self.sample_bound())
+            .filter(|&m| self.usvp_succeeds(block_size, m))
+            .map(|m| {
+                let d = self.n + m + 1;
+                let cost = schedule.cost(est, block_size, d, log_q, self.log2_embedding_volume(m));
+                (m, cost)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+}
+
+/// Return a native estimate of the hardness of `lwe` against the two-step primal attack under the cost model `est`, optimizing jointly over the BKZ block size $\beta$, the sieving dimension $\eta \geq \beta$ and the number of samples, or `None` if the instance is invalid or the attack does not succeed.
+/// The attack runs BKZ-$\beta$ (a single run) followed by one sieve in dimension $\eta$, see [`LWE::two_step_succeeds`]; with $\eta = \beta$ it is the primal uSVP attack of [`LWE::security_level_internal`].
+/// The memory cost is that of the larger of the two.
+pub fn primal_usvp_two_step(lwe: &LWE, est: Estimates) -> Option<AttackEstimate> {
+    lwe.check_params().ok()?;
+    let max_block_size = lwe.n + lwe.sample_bound() + 1;
+    let (block_size, lambda) = find_optimal_block_size(
+        |block_size| {
+            lwe.best_two_step(est, block_size)
+                .map_or(f64::INFINITY, |(_, _, cost)| cost)
+        },
+        2,
+        max_block_size,
+    );
+    if lambda.is_infinite() {
+        return None;
+    }
+    let (m, sieve_dim, _) = lwe.best_two_step(est, block_size)?;
+    Some(AttackEstimate {
+        attack: AttackKind::PrimalUsvpTwoStep {
+            samples: m,
+            sample_limited: lwe.max_samples == Some(m),
+            sieve_dim,
+            secret_scaling: lwe.secret_scaling(),
+        },
+        estimate: est,
+        block_size,
+        schedule: BkzSchedule::Fixed,
+        dim: lwe.n + m + 1,
+        log2_cost_time: lambda,
+        log2_cost_memory: Some(bkz_memory_cost(est, sieve_dim)),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::attack::AttackReport;
+
+    #[test]
+    fn test_lwe_security_level_monotonic() {
+        // More noise and larger dimensions make LWE harder
+        let lambdas = [1., 2., 4.].map(|sigma| {
+            LWE::new(512, 12289u64.into(), sigma, 1024)
+                .security_level()
+                .unwrap()
+        });
+        assert!(lambdas[0] < lambdas[1] && lambdas[1] < lambdas[2]);
+
+        let lambdas = [256, 512, 1024].map(|n| {
+            LWE::new(n, 12289u64.into(), 3.2, 2 * n)
+                .security_level()
+                .unwrap()
+        });
+        assert!(lambdas[0] < lambdas[1] && lambdas[1] < lambdas[2]);
+    }
+
+    #[test]
+    fn test_lwe_num_samples() {
+        // With many more samples than useful, the attack should not use all of them
+        let lwe = LWE::new(256, 12289u64.into(), 3.2, 4096);
+        let est = lwe
+            .security_level_internal(
+                Estimates::BDGL16,
+                &CostConstraint::default(),
+                BkzSchedule::Fixed,
+            )
+            .unwrap();
+        assert!(matches!(
+            est.attack,
+            AttackKind::PrimalUsvp {
+                sample_limited: false,
+                ..
+            }
+        ));
+        assert!(est.dim < 256 + 4096 + 1);
+    }
+
+    #[test]
+    fn test_lwe_max_samples() {
+        let primal = |lwe: &LWE| {
+            lwe.security_level_internal(
+                Estimates::BDGL16,
+                &CostConstraint::default(),
+                BkzSchedule::Fixed,
+            )
+            .unwrap()
+        };
+        let unlimited = primal(&LWE::with_unlimited_samples(512, 3329u64.into(), 1.));
+
+        let limited = primal(&LWE::new(512, 3329u64.into(), 1., 512));
+        assert!(limited.log2_cost_time >= unlimited.log2_cost_time);
+
+        // With few samples, the attack uses all of them
+        let limited = primal(&LWE::new(512, 3329u64.into(), 1., 256));
+        println!("{}", AttackReport(&[unlimited.clone(), limited.clone()]));
+        assert!(limited.log2_cost_time > unlimited.log2_cost_time);
+        assert!(matches!(
+            limited.attack,
+            AttackKind::PrimalUsvp {
+                samples: 256,
+                sample_limited: true,
+                secret_scaling: None,
+            }
+        ));
+
+        // The estimate converges to the unlimited one as the number of samples grows
+        let estimates = [128, 256, 512, 1024, 2048, 4096]
+            .map(|m| primal(&LWE::new(512, 3329u64.into(), 1., m)).log2_cost_time);
+        for w in estimates.windows(2) {
+            assert!(w[0] >= w[1]);
+        }
+        assert_eq!(estimates[5], unlimited.log2_cost_time);
+    }
+
+    #[test]
+    fn test_primal_usvp_two_step() {
+        for lwe in [
+            LWE::new(512, 3329u64.into(), 1.5f64.sqrt(), 512),
+            LWE::new(768, 3329u64.into(), 1., 768),
+            LWE::with_unlimited_samples(256, 12289u64.into(), 3.2),
+        ] {
+            for est in Estimates::DEFAULT {
+                let single = lwe
+                    .security_level_internal(est, &CostConstraint::default(), BkzSchedule::Fixed)
+                    .unwrap();
+                let two_step = primal_usvp_two_step(&lwe, est).unwrap();
+                println!(
+                    "{lwe}\n{}",
+                    AttackReport(&[single.clone(), two_step.clone()])
+                );
+                assert!(two_step.log2_cost_time <= single.log2_cost_time + 1e-9);
+                let AttackKind::PrimalUsvpTwoStep { sieve_dim, .. } = two_step.attack else {
+                    panic!("unexpected attack {}", two_step.attack);
+                };
+                assert!(sieve_dim >= two_step.block_size);
+            }
+        }
+    }
+
+    #[test]
+    fn test_primal_usvp_two_step_kyber512() {
+        // The lattice-estimator reports rop ≈ 2^140.3 (β = 391, η = 421) for the two-step attack on Kyber-512 under the MATZOV cost model
+        let kyber512 = LWE::new(512, 3329u64.into(), 1.5f64.sqrt(), 512);
+        let two_step = primal_usvp_two_step(&kyber512, Estimates::Matzov).unwrap();
+        println!("{}", AttackReport(&[two_step.clone()]));
+        assert!((two_step.log2_cost_time - 140.3).abs() <= 1.);
+        assert_eq!(kyber512.security_level(), Ok(two_step.log2_cost_time));
+    }
+
+    #[test]
+    fn test_security_level_internal_errors() {
+        let estimate = |lwe: LWE| {
+            lwe.security_level_internal(
+                Estimates::BDGL16,
+                &CostConstraint::default(),
+                BkzSchedule::Fixed,
+            )
+        };
+        for lwe in [
+            LWE::new(0, 3329u64.into(), 1., 512),
+            LWE::new(512, 3329u64.into(), 1., 0),
+            LWE::new(512, 1u64.into(), 1., 512),
+            LWE::new(512, 3329u64.into(), -1., 512),
+        ] {
+            assert!(matches!(
+                lwe.security_level(),
+                Err(EstimatorError::InvalidParameters(_))
+            ));
+            assert!(matches!(
+                estimate(lwe),
+                Err(EstimatorError::InvalidParameters(_))
+            ));
+        }
+        // The error is as large as the modulus
+        let lwe = LWE::new(512, 3329u64.into(), 3329., 512);
+        assert!(matches!(
+            lwe.security_level(),
+            Err(EstimatorError::NonConvergent(_))
+        ));
+        assert!(matches!(
+            estimate(lwe),
+            Err(EstimatorError::NonConvergent(_))
+        ));
+    }
+
+    #[test]
+    fn test_secret_dist() {
+        let (n, log_q, sigma) = (512, 12., 3.2);
+        let std_dev = |dist: SecretDist| dist.std_dev(n, log_q, sigma);
+        let density = |dist: SecretDist| dist.density(n, log_q, sigma);
+        assert_eq!(std_dev(SecretDist::NormalForm), sigma);
+        assert!((std_dev(SecretDist::UniformModQ) - 4096. / 12f64.sqrt()).abs() < 1e-3);
+        assert_eq!(
+            std_dev(SecretDist::SparseTernary {
+                hamming_weight: 128
+            }),
+            0.5
+        );
+        assert_eq!(std_dev(SecretDist::Ternary { p_nonzero: 0.25 }), 0.5);
+        assert_eq!(std_dev(SecretDist::CBD { eta: 2 }), 1.);
+        assert_eq!(std_dev(SecretDist::Gaussian { sigma: 2. }), 2.);
+
+        assert_eq!(
+            density(SecretDist::SparseTernary {
+                hamming_weight: 128
+            }),
+            0.25
+        );
+        assert_eq!(density(SecretDist::Ternary { p_nonzero: 2. / 3. }), 2. / 3.);
+        // binom(4, 2) / 16 = 3/8
+        assert_eq!(density(SecretDist::CBD { eta: 2 }), 5. / 8.);
+        assert_eq!(density(SecretDist::CBD { eta: 3 }), 1. - 20. / 64.);
+        assert!((density(SecretDist::UniformModQ) - (1. - 1. / 4096.)).abs() < 1e-12);
+        // A wide Gaussian is almost never 0, a narrow one almost always
+        let wide = density(SecretDist::Gaussian { sigma: 100. });
+        assert!((wide - (1. - 1. / (100. * (2. * std::f64::consts::PI).sqrt()))).abs() < 1e-6);
+        assert!(density(SecretDist::Gaussian { sigma: 0.1 }) < 1e-10);
+        assert_eq!(
+            density(SecretDist::NormalForm),
+            density(SecretDist::Gaussian { sigma })
+        );
+    }
+
+    #[test]
+    fn test_secret_scaling() {
+        let lwe =
+            |secret_dist| LWE::new(512, 12289u64.into(), 3.2, 1024).with_secret_dist(secret_dist);
+        let normal_form = lwe(SecretDist::NormalForm);
+        assert_eq!(normal_form.secret_scaling(), None);
+
+        // A uniform secret is not rescaled and gives the same estimate as the normal form
+        let uniform = lwe(SecretDist::UniformModQ);
+        assert_eq!(uniform.secret_scaling(), None);
+        assert_eq!(uniform.best_attack(), normal_form.best_attack());
+
+        // A ternary secret is rescaled and gives a lower estimate than the normal form
+        let ternary = lwe(SecretDist::Ternary { p_nonzero: 2. / 3. });
+        let scaling = ternary.secret_scaling().unwrap();
+        assert!((scaling - 3.2 / (2f64 / 3.).sqrt()).abs() < 1e-12);
+        let attack = ternary.best_attack().unwrap();
+        println!("{}", AttackReport(std::slice::from_ref(&attack)));
+        assert!(attack.log2_cost_time <= normal_form.security_level().unwrap());
+        assert!(matches!(
+            attack.attack,
+            AttackKind::PrimalUsvp { secret_scaling: Some(s), .. }
+                | AttackKind::PrimalUsvpTwoStep { secret_scaling: Some(s), .. }
+                if s == scaling
+        ));
+        assert!(attack.attack.to_string().contains("secret scaled by"));
+        let primal = |lwe: &LWE| {
+            lwe.security_level_internal(
+                Estimates::BDGL16,
+                &CostConstraint::default(),
+                BkzSchedule::Fixed,
+            )
+            .unwrap()
+            .log2_cost_time
+        };
+        assert!(primal(&ternary) < primal(&normal_form));
+
+        // A secret larger than the error is not rescaled
+        assert_eq!(
+            lwe(SecretDist::Gaussian { sigma: 4. }).secret_scaling(),
+            None
+        );
+    }
+}
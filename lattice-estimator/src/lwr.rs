@@ -0,0 +1,211 @@
+use std::fmt;
+use std::fmt::{Debug, Display};
+
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+use crate::attack::{AttackEstimate, AttackReport};
+use crate::errors::EstimatorError;
+use crate::lwe::{SecretDist, LWE};
+
+/// Smallest ratio $q/p$ for which [`LWR::security_level`] considers the reduction to LWE meaningful.
+/// Below, the rounding error takes at most 3 values, which is far from the Gaussian error assumed by the LWE estimates.
+pub const MIN_MODULUS_RATIO: f64 = 4.;
+
+/// LWR parameters for instances $(A, b = \lfloor (p/q) A s \rceil) \in \mathbb{Z}\_\texttt{q}^{m\times\texttt{n}} \times \mathbb{Z}\_\texttt{p}^m$, where $s$ is distributed according to `secret_dist`.
+/// The number of samples $m$ available to the adversary is at most `m`, or unlimited if `m` is `None`.
+#[derive(Serialize, Deserialize)]
+pub struct LWR {
+    pub(crate) n: usize,
+    #[serde(with = "crate::serde_biguint")]
+    pub(crate) q: BigUint,
+    #[serde(with = "crate::serde_biguint")]
+    pub(crate) p: BigUint,
+    #[serde(default)]
+    pub(crate) secret_dist: SecretDist,
+    pub(crate) m: Option<usize>,
+}
+
+impl Display for LWR {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "LWR[n={}, q={}, p={}, m={}, secret_dist={:?}]",
+            self.n,
+            self.q,
+            self.p,
+            self.m.map_or("unlimited".to_string(), |m| m.to_string()),
+            self.secret_dist
+        )
+    }
+}
+
+impl Debug for LWR {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// The security estimate of an LWR instance, see [`LWR::security_level`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LWREstimate {
+    /// The cheapest attack on the equivalent LWE instance.
+    pub attack: AttackEstimate,
+    /// $\log\_2$ of the time cost of `attack`.
+    pub log2_cost_time: f64,
+    /// Standard deviation of the rounding error of the equivalent LWE instance, see [`LWR::error_std_dev`].
+    pub error_std_dev: f64,
+    /// Whether $q/p$ is below [`MIN_MODULUS_RATIO`], in which case the estimate is not meaningful.
+    pub small_modulus_ratio: bool,
+}
+
+impl Display for LWREstimate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "equivalent LWE error_std_dev = {:.4}, log2(time) = {:.2}",
+            self.error_std_dev, self.log2_cost_time
+        )?;
+        if self.small_modulus_ratio {
+            writeln!(
+                f,
+                "warning: q/p < {MIN_MODULUS_RATIO}, the reduction to LWE is not meaningful"
+            )?;
+        }
+        write!(f, "{}", AttackReport(std::slice::from_ref(&self.attack)))
+    }
+}
+
+impl LWR {
+    /// Construct an LWR instance with at most `m` samples.
+    pub const fn new(n: usize, q: BigUint, p: BigUint, m: usize) -> Self {
+        LWR {
+            n,
+            q,
+            p,
+            secret_dist: SecretDist::NormalForm,
+            m: Some(m),
+        }
+    }
+
+    /// Construct an LWR instance with an unlimited number of samples.
+    pub const fn with_unlimited_samples(n: usize, q: BigUint, p: BigUint) -> Self {
+        LWR {
+            n,
+            q,
+            p,
+            secret_dist: SecretDist::NormalForm,
+            m: None,
+        }
+    }
+
+    /// Return the same instance with secret distribution `secret_dist`.
+    pub fn with_secret_dist(self, secret_dist: SecretDist) -> Self {
+        LWR {
+            secret_dist,
+            ..self
+        }
+    }
+
+    fn modulus_ratio(&self) -> f64 {
+        self.q.to_f64().unwrap() / self.p.to_f64().unwrap()
+    }
+
+    /// Return the standard deviation $\sqrt{((q/p)^2 - 1) / 12}$ of the rounding error, i.e., of the uniform distribution on $q/p$ consecutive integers.
+    pub fn error_std_dev(&self) -> f64 {
+        let ratio = self.modulus_ratio();
+        (ratio * ratio - 1.).max(0.).sqrt() / 12f64.sqrt()
+    }
+
+    /// Return true iff $q/p$ is below [`MIN_MODULUS_RATIO`], i.e., if the rounding error is too far from a Gaussian for the reduction to LWE to be meaningful.
+    pub fn has_small_modulus_ratio(&self) -> bool {
+        self.modulus_ratio() < MIN_MODULUS_RATIO
+    }
+
+    /// Return the equivalent LWE instance: scaling $b$ by $q/p$ yields $A s + e$ modulo $q$, with a rounding error $e$ of standard deviation [`LWR::error_std_dev`].
+    pub fn to_lwe(&self) -> LWE {
+        LWE {
+            n: self.n,
+            q: self.q.clone(),
+            error_std_dev: self.error_std_dev(),
+            max_samples: self.m,
+            secret_dist: self.secret_dist,
+        }
+    }
+
+    /// Return an error if the instance is invalid, i.e., unless $2 \leq p < q$ and the equivalent LWE instance is valid.
+    fn check_params(&self) -> Result<(), EstimatorError> {
+        if self.p < BigUint::from(2u64) || self.p >= self.q {
+            return Err(EstimatorError::InvalidParameters(format!(
+                "{self}: the rounding modulus must satisfy 2 <= p < q"
+            )));
+        }
+        self.to_lwe().check_params()
+    }
+
+    /// Return the cheapest attack on the equivalent LWE instance (see [`LWE::best_attack`]), together with the standard deviation of its error, flagging small ratios $q/p$ (see [`LWR::has_small_modulus_ratio`]).
+    /// Returns [`EstimatorError::InvalidParameters`] for invalid instances, and [`EstimatorError::NonConvergent`] if no attack succeeds.
+    pub fn security_level(&self) -> Result<LWREstimate, EstimatorError> {
+        self.check_params()?;
+        let small_modulus_ratio = self.has_small_modulus_ratio();
+        if small_modulus_ratio {
+            log::warn!("{self}: q/p < {MIN_MODULUS_RATIO}, the reduction to LWE is not meaningful");
+        }
+        let attack = self
+            .to_lwe()
+            .best_attack()
+            .ok_or_else(|| EstimatorError::NonConvergent(format!("no attack solves {self}")))?;
+        Ok(LWREstimate {
+            log2_cost_time: attack.log2_cost_time,
+            attack,
+            error_std_dev: self.error_std_dev(),
+            small_modulus_ratio,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lwr_equivalent_lwe() {
+        let lwr = LWR::new(512, (1u64 << 30).into(), (1u64 << 20).into(), 512);
+        let estimate = lwr.security_level().unwrap();
+        let error_std_dev = ((1u64 << 20) as f64 - 1.).sqrt() / 12f64.sqrt();
+        assert!((estimate.error_std_dev - error_std_dev).abs() < 1e-9);
+        assert!(!estimate.small_modulus_ratio);
+
+        let lwe = LWE::new(512, (1u64 << 30).into(), error_std_dev, 512);
+        assert_eq!(Some(estimate.attack.clone()), lwe.best_attack());
+        assert_eq!(Ok(estimate.log2_cost_time), lwe.security_level());
+        println!("{estimate}");
+    }
+
+    #[test]
+    fn test_lwr_small_modulus_ratio() {
+        let lwr = LWR::new(512, (1u64 << 12).into(), (1u64 << 11).into(), 512);
+        let estimate = lwr.security_level().unwrap();
+        assert!(estimate.small_modulus_ratio);
+        assert!(estimate.to_string().contains("warning"));
+
+        let lwr = LWR::new(512, 3329u64.into(), 832u64.into(), 512);
+        assert!(!lwr.has_small_modulus_ratio());
+    }
+
+    #[test]
+    fn test_lwr_invalid_params() {
+        for lwr in [
+            LWR::new(0, 3329u64.into(), 832u64.into(), 512),
+            LWR::new(512, 3329u64.into(), 1u64.into(), 512),
+            LWR::new(512, 3329u64.into(), 3329u64.into(), 512),
+            LWR::new(512, 3329u64.into(), 832u64.into(), 0),
+        ] {
+            assert!(matches!(
+                lwr.security_level(),
+                Err(EstimatorError::InvalidParameters(_))
+            ));
+        }
+    }
+}
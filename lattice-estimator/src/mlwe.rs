@@ -0,0 +1,160 @@
+use std::fmt;
+use std::fmt::{Debug, Display};
+
+use num_bigint::BigUint;
+
+use crate::attack::{AttackEstimate, CostConstraint};
+use crate::errors::EstimatorError;
+use crate::lwe::{SecretDist, LWE};
+use crate::reduction::{BkzSchedule, Estimates};
+
+/// MLWE parameters for instances $(A, b = A s + e) \in R\_q^{\texttt{num\\_samples}\times\texttt{rank}} \times R\_q^\texttt{num\\_samples}$ where $R\_q = \mathbb{Z}\_\texttt{q}\[X\]/(X^\texttt{d}+1)$, the coefficients of $e$ are distributed with standard deviation `error_std_dev` and those of $s$ according to `secret_dist`.
+pub struct MLWE {
+    pub rank: usize,
+    pub d: usize,
+    pub q: BigUint,
+    pub error_std_dev: f64,
+    pub secret_dist: SecretDist,
+    pub num_samples: usize,
+}
+
+impl Display for MLWE {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "MLWE[rank={}, d={}, q={}, error_std_dev={}, num_samples={}, secret_dist={:?}]",
+            self.rank, self.d, self.q, self.error_std_dev, self.num_samples, self.secret_dist
+        )
+    }
+}
+
+impl Debug for MLWE {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "MLWE[rank={}, d={}, q={}, error_std_dev={}, num_samples={}, secret_dist={:?}]",
+            self.rank, self.d, self.q, self.error_std_dev, self.num_samples, self.secret_dist
+        )
+    }
+}
+
+/// Module-LWE instance, see [`MLWE`].
+pub type ModuleLWE = MLWE;
+
+impl MLWE {
+    /// Construct an MLWE instance of module rank `module_rank` over $R\_q = \mathbb{Z}\_\texttt{q}\[X\]/(X^\texttt{ring\\_degree}+1)$ with `module_rank` samples (i.e., a square matrix $A$, as in Kyber) and a secret distributed as the error.
+    pub fn new(ring_degree: usize, module_rank: usize, q: BigUint, error_std_dev: f64) -> Self {
+        assert!(
+            ring_degree.is_power_of_two(),
+            "ring degree must be a power of two, got {ring_degree}"
+        );
+        MLWE {
+            rank: module_rank,
+            d: ring_degree,
+            q,
+            error_std_dev,
+            secret_dist: SecretDist::NormalForm,
+            num_samples: module_rank,
+        }
+    }
+
+    /// Return the same instance with secret distribution `secret_dist`.
+    pub fn with_secret_dist(self, secret_dist: SecretDist) -> Self {
+        MLWE {
+            secret_dist,
+            ..self
+        }
+    }
+
+    /// Return the same instance with `num_samples` (ring) samples.
+    pub fn with_num_samples(self, num_samples: usize) -> Self {
+        MLWE {
+            num_samples,
+            ..self
+        }
+    }
+
+    /// Flatten to the LWE instance `LWE\[rank*d, q, error_std_dev, num_samples*d\]` obtained by replacing each ring element of $A$ by its $d \times d$ (negacyclic) multiplication matrix.
+    pub fn to_lwe(&self) -> LWE {
+        LWE::new(
+            self.rank * self.d,
+            self.q.clone(),
+            self.error_std_dev,
+            self.num_samples * self.d,
+        )
+        .with_secret_dist(self.secret_dist)
+    }
+
+    /// Return the cheapest attack on the flattened LWE instance (see [`MLWE::to_lwe`] and [`LWE::estimate_all`]) under the cost model `est`, with unbounded memory.
+    /// Returns [`EstimatorError::InvalidParameters`] for invalid instances, and [`EstimatorError::NonConvergent`] if no attack succeeds.
+    pub fn best_attack_internal(&self, est: Estimates) -> Result<AttackEstimate, EstimatorError> {
+        let lwe = self.to_lwe();
+        match AttackEstimate::best(&lwe.estimate_all(&[est], &CostConstraint::default())) {
+            Some(attack) => Ok(attack.clone()),
+            // The primal attack failed, report why
+            None => {
+                lwe.security_level_internal(est, &CostConstraint::default(), BkzSchedule::Fixed)
+            }
+        }
+    }
+
+    /// Return $\lambda$ such that `MLWE\[rank, d, q, error_std_dev\]` is $2^\lambda$-hard, by reducing to the flattened LWE instance and calling the LWE security estimator.
+    /// Returns the errors of [`LWE::security_level`].
+    pub fn security_level(&self) -> Result<f64, EstimatorError> {
+        self.to_lwe().security_level()
+    }
+}
+
+/// Return the cheapest attack under the cost model `est` on the MLWE instance of module rank `rank` over $\mathbb{Z}\_Q\[X\]/(X^N+1)$ with error standard deviation `sigma` and secret distribution `secret`, see [`MLWE::best_attack_internal`].
+pub fn module_lwe_security<const Q: u64, const N: usize>(
+    rank: usize,
+    sigma: f64,
+    secret: SecretDist,
+    est: Estimates,
+) -> Result<AttackEstimate, EstimatorError> {
+    MLWE::new(N, rank, Q.into(), sigma)
+        .with_secret_dist(secret)
+        .best_attack_internal(est)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::presets::kyber512_lwe;
+
+    use super::*;
+
+    /// NTT-friendly primes for $N = 128$, i.e., $Q \equiv 1 \bmod 2N$.
+    const Q_SMALL: u64 = 7681;
+    const Q_LARGE: u64 = 18446744069414584321;
+    const N: usize = 128;
+
+    #[test]
+    fn test_module_lwe_toy_parameters() {
+        for est in [Estimates::Kyber, Estimates::Matzov] {
+            let small =
+                module_lwe_security::<Q_SMALL, N>(1, 3.2, SecretDist::NormalForm, est).unwrap();
+            let large =
+                module_lwe_security::<Q_LARGE, N>(1, 3.2, SecretDist::NormalForm, est).unwrap();
+            println!("{small}{large}");
+            assert!(small.log2_cost_time < 64.);
+            assert!(large.log2_cost_time < small.log2_cost_time);
+        }
+        // A larger rank increases the security
+        let rank_1 =
+            module_lwe_security::<Q_SMALL, N>(1, 3.2, SecretDist::NormalForm, Estimates::Matzov);
+        let rank_4 =
+            module_lwe_security::<Q_SMALL, N>(4, 3.2, SecretDist::NormalForm, Estimates::Matzov);
+        assert!(rank_1.unwrap().log2_cost_time < rank_4.unwrap().log2_cost_time);
+    }
+
+    #[test]
+    fn test_module_lwe_flattening() {
+        let kyber512 = MLWE::new(256, 2, 3329u64.into(), 1.5f64.sqrt());
+        assert_eq!(kyber512.to_lwe().to_string(), kyber512_lwe().to_string());
+        assert_eq!(kyber512.security_level(), kyber512_lwe().security_level());
+        assert!(matches!(
+            MLWE::new(256, 2, 3329u64.into(), 0.).best_attack_internal(Estimates::Matzov),
+            Err(EstimatorError::InvalidParameters(_))
+        ));
+    }
+}
@@ -1,7 +1,9 @@
 use crate::errors::LatticeEstimatorError;
 use crate::msis::MSIS;
+use crate::param_search::is_secure;
 
 /// Return the smallest `h` such that `MSIS\[h, w, d, q, length_bound\]` is $2^\lambda$-hard (for a given norm).
+/// Instances that no attack solves (see [`EstimatorError::NonConvergent`](crate::errors::EstimatorError::NonConvergent)) count as secure.
 pub fn find_optimal_h(msis: &MSIS, lambda: usize) -> Result<usize, LatticeEstimatorError> {
     let mut lo_sis: usize = 1;
     let mut hi_sis: usize = msis.to_sis().upper_bound_h();
@@ -11,16 +13,15 @@ pub fn find_optimal_h(msis: &MSIS, lambda: usize) -> Result<usize, LatticeEstima
 
     let lambda_hi = msis.security_level();
     debug_assert!(
-        lambda_hi >= lambda as f64,
-        "{msis} has sec. param. {lambda_hi}  < target lambda = {lambda}"
+        is_secure(lambda_hi.clone(), lambda as f64) == Ok(true),
+        "{msis} has sec. param. {lambda_hi:?}  < target lambda = {lambda}"
     );
     // Loop invariant: SIS_{hi_sis * d, q, m*d, length_bound} is 2^lambda_hi-hard with lambda_hi >= lambda
     while hi_sis > lo_sis {
         let mid_sis = lo_sis + (hi_sis - lo_sis) / 2;
         // Use closest multiple of d for the MSIS instance
         let msis = msis.with_h((mid_sis as f64 / msis.d as f64).round() as usize);
-        let mid_lambda = msis.security_level();
-        if mid_lambda >= lambda as f64 {
+        if is_secure(msis.security_level(), lambda as f64)? {
             // Search for smaller n in [lo, mid]
             hi_sis = mid_sis;
         } else {
@@ -33,6 +34,7 @@ pub fn find_optimal_h(msis: &MSIS, lambda: usize) -> Result<usize, LatticeEstima
 }
 
 /// Return the smallest h such that `MSIS\[h, w, d, q, length_bound(h)\]` is $2^\lambda$-hard (for a given norm), where `length_bound` is a function of `h`.
+/// Instances that no attack solves (see [`EstimatorError::NonConvergent`](crate::errors::EstimatorError::NonConvergent)) count as secure.
 pub fn find_optimal_h_dynamic<F>(
     msis: &MSIS,
     length_bound: F,
@@ -45,14 +47,15 @@ where
     // Instead, exhaustively search powers of 2 until we find a suitable n.
     // TODO: use a better search algorithm / return a more fine-grained result
     let hi = msis.d * msis.to_sis().upper_bound_h();
-    let candidates = (1..hi).map(|i| 2usize.pow(i as u32));
-    candidates
-        .map(|h| msis.with_h(h).with_length_bound(length_bound(h)))
-        .find(|sis| sis.security_level() >= lambda as f64)
-        .map(|sis| sis.h)
-        .ok_or(LatticeEstimatorError::from(
-            "no suitable h found".to_string(),
-        ))
+    for h in (1..hi).map(|i| 2usize.pow(i as u32)) {
+        let msis = msis.with_h(h).with_length_bound(length_bound(h));
+        if is_secure(msis.security_level(), lambda as f64)? {
+            return Ok(h);
+        }
+    }
+    Err(LatticeEstimatorError::from(
+        "no suitable h found".to_string(),
+    ))
 }
 
 #[cfg(test)]
@@ -74,7 +77,7 @@ mod test {
             w: 512,
             norm: Norm::L2,
         };
-        let lambda = test_l2.security_level();
+        let lambda = test_l2.security_level().unwrap();
         println!("{test_l2} -> lambda: {lambda}");
     }
 
@@ -88,7 +91,7 @@ mod test {
             w: 512,
             norm: Norm::Linf,
         };
-        let lambda = test_linf.security_level();
+        let lambda = test_linf.security_level().unwrap();
         println!("{test_linf} -> lambda: {lambda}");
     }
 
@@ -105,8 +108,8 @@ mod test {
         let h_opt =
             crate::msis::security_estimates::find_optimal_h(&test_l2.with_h(0), 128).unwrap();
         let msis = test_l2.with_h(h_opt);
-        println!("{test_l2} -> lambda: {}", test_l2.security_level());
-        println!("{msis} -> lambda: {}", msis.security_level());
+        println!("{test_l2} -> lambda: {}", test_l2.security_level().unwrap());
+        println!("{msis} -> lambda: {}", msis.security_level().unwrap());
     }
 
     #[test]
@@ -122,7 +125,10 @@ mod test {
         let h_opt =
             crate::msis::security_estimates::find_optimal_h(&test_linf.with_h(0), 128).unwrap();
         let msis = test_linf.with_h(h_opt);
-        println!("{test_linf} -> lambda: {}", test_linf.security_level());
-        println!("{msis} -> lambda: {}", msis.security_level());
+        println!(
+            "{test_linf} -> lambda: {}",
+            test_linf.security_level().unwrap()
+        );
+        println!("{msis} -> lambda: {}", msis.security_level().unwrap());
     }
 }
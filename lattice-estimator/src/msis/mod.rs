@@ -4,6 +4,7 @@ use std::fmt::{Debug, Display};
 use num_bigint::BigUint;
 use num_traits::ToPrimitive;
 
+use crate::errors::EstimatorError;
 use crate::norms::Norm;
 use crate::sis::SIS;
 
@@ -40,7 +41,57 @@ impl Debug for MSIS {
     }
 }
 
+/// Module-SIS instance over $R\_q^{\texttt{module\\_rank}\times\texttt{num\\_cols}}$, see [`MSIS`].
+pub type ModuleSIS = MSIS;
+
+/// Ring-SIS instance, i.e., a [`ModuleSIS`] instance of module rank 1, see [`MSIS::ring_sis`].
+pub type RingSIS = MSIS;
+
 impl MSIS {
+    /// Construct an MSIS instance $A \in R\_q^{\texttt{module\\_rank}\times\texttt{num\\_cols}}$ over $R\_q = \mathbb{Z}\_\texttt{q}\[X\]/(X^\texttt{ring\\_degree}+1)$.
+    /// `norm_bound` is the bound on the coefficient embedding of the full solution vector $s \in R\_q^\texttt{num\\_cols}$, i.e., on the flattened vector $s \in \mathbb{Z}\_q^{\texttt{ring\\_degree} \cdot \texttt{num\\_cols}}$.
+    /// For power-of-two cyclotomics, the coefficient embedding is exactly what the flattening in [`MSIS::to_sis`] preserves, so the bound carries over to the SIS instance unchanged (for both norms).
+    pub fn new(
+        ring_degree: usize,
+        module_rank: usize,
+        num_cols: usize,
+        q: BigUint,
+        norm_bound: f64,
+        norm: Norm,
+    ) -> Self {
+        assert!(
+            ring_degree.is_power_of_two(),
+            "ring degree must be a power of two, got {ring_degree}"
+        );
+        MSIS {
+            h: module_rank,
+            d: ring_degree,
+            q,
+            length_bound: norm_bound,
+            w: num_cols,
+            norm,
+        }
+    }
+
+    /// Construct a Ring-SIS instance $a \in R\_q^{1\times\texttt{num\\_cols}}$, i.e., an MSIS instance of module rank 1.
+    pub fn ring_sis(
+        ring_degree: usize,
+        num_cols: usize,
+        q: BigUint,
+        norm_bound: f64,
+        norm: Norm,
+    ) -> Self {
+        Self::new(ring_degree, 1, num_cols, q, norm_bound, norm)
+    }
+
+    pub fn module_rank(&self) -> usize {
+        self.h
+    }
+
+    pub fn ring_degree(&self) -> usize {
+        self.d
+    }
+
     pub fn with_h(&self, h: usize) -> Self {
         MSIS {
             h,
@@ -63,6 +114,7 @@ impl MSIS {
         }
     }
 
+    /// Flatten to the SIS instance `SIS\[h*d, w*d, q, length_bound\]` obtained by replacing each ring element of $A$ by its $d \times d$ (negacyclic) multiplication matrix.
     pub fn to_sis(&self) -> SIS {
         SIS::new(
             self.h * self.d,
@@ -75,7 +127,8 @@ impl MSIS {
 
     /// Return $\lambda$ such that `MSIS\[h, w, d, q, length_bound\]` is $2^\lambda$-hard (for a given norm).
     /// We estimate the security by reducing to `SIS\[h\*d, w\*d, q, length_bound\]` and calling the SIS security estimator.
-    pub fn security_level(&self) -> f64 {
+    /// Returns the errors of [`SIS::security_level`].
+    pub fn security_level(&self) -> Result<f64, EstimatorError> {
         self.to_sis().security_level()
     }
 
@@ -110,3 +163,44 @@ pub fn msis_h_128_linf(msis: &MSIS) -> Option<usize> {
 
     msis_h_128_l2(&msis_l2)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const Q: u64 = 2147483649;
+
+    #[test]
+    fn test_module_sis_flattening() {
+        let msis = ModuleSIS::new(64, 3, 10, Q.into(), 1024., Norm::L2);
+        let sis = msis.to_sis();
+        assert_eq!(sis.h, 64 * 3);
+        assert_eq!(sis.w, 64 * 10);
+        assert_eq!(sis.q, Q.into());
+        assert_eq!(sis.length_bound, 1024.);
+
+        let rsis = RingSIS::ring_sis(256, 8, Q.into(), 16., Norm::Linf);
+        assert_eq!(rsis.module_rank(), 1);
+        let sis = rsis.to_sis();
+        assert_eq!(sis.h, 256);
+        assert_eq!(sis.w, 256 * 8);
+        assert_eq!(sis.length_bound, 16.);
+    }
+
+    #[test]
+    fn test_module_sis_security_level_monotonic_in_rank() {
+        let lambdas = (1..=4)
+            .map(|rank| {
+                ModuleSIS::new(64, rank, 32, Q.into(), 1024., Norm::L2)
+                    .security_level()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        for w in lambdas.windows(2) {
+            assert!(
+                w[0] <= w[1],
+                "security level not monotonic in module rank: {lambdas:?}"
+            );
+        }
+    }
+}
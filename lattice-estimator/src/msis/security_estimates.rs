@@ -2,8 +2,10 @@ use log::debug;
 
 use crate::errors::LatticeEstimatorError;
 use crate::msis::MSIS;
+use crate::param_search::is_secure;
 
 /// Return the smallest `h` such that `MSIS\[h, w, d, q, length_bound\]` is $2^\lambda$-hard (for a given norm).
+/// Instances that no attack solves (see [`EstimatorError::NonConvergent`](crate::errors::EstimatorError::NonConvergent)) count as secure.
 pub fn find_optimal_h(msis: &MSIS, lambda: usize) -> Result<usize, LatticeEstimatorError> {
     let lo: usize = 1;
     let hi: usize = msis.upper_bound_h();
@@ -13,8 +15,8 @@ pub fn find_optimal_h(msis: &MSIS, lambda: usize) -> Result<usize, LatticeEstima
     for h in lo..=hi {
         let curr = msis.with_h(h);
         let lambda_curr = curr.security_level();
-        debug!("\t{curr} -> {lambda_curr} bits of security");
-        if lambda_curr >= lambda as f64 {
+        debug!("\t{curr} -> {lambda_curr:?} bits of security");
+        if is_secure(lambda_curr, lambda as f64)? {
             return Ok(h);
         }
     }
@@ -24,6 +26,7 @@ pub fn find_optimal_h(msis: &MSIS, lambda: usize) -> Result<usize, LatticeEstima
 }
 
 /// Return the smallest h such that `MSIS\[h, w, d, q, length_bound(h)\]` is $2^\lambda$-hard (for a given norm), where `length_bound` is a function of `h`.
+/// Instances that no attack solves (see [`EstimatorError::NonConvergent`](crate::errors::EstimatorError::NonConvergent)) count as secure.
 pub fn find_optimal_h_dynamic<F>(
     msis: &MSIS,
     length_bound: F,
@@ -40,8 +43,8 @@ where
     for h in lo..=hi {
         let curr = msis.with_h(h).with_length_bound(length_bound(h));
         let lambda_curr = curr.security_level();
-        debug!("\t{curr} -> {lambda_curr} bits of security");
-        if lambda_curr >= lambda as f64 {
+        debug!("\t{curr} -> {lambda_curr:?} bits of security");
+        if is_secure(lambda_curr, lambda as f64)? {
             return Ok(h);
         }
     }
@@ -69,7 +72,7 @@ mod test {
             w: 512,
             norm: Norm::L2,
         };
-        let lambda = test_l2.security_level();
+        let lambda = test_l2.security_level().unwrap();
         println!("{test_l2} -> lambda: {lambda}");
     }
 
@@ -83,7 +86,7 @@ mod test {
             w: 512,
             norm: Norm::Linf,
         };
-        let lambda = test_linf.security_level();
+        let lambda = test_linf.security_level().unwrap();
         println!("{test_linf} -> lambda: {lambda}");
     }
 
@@ -99,8 +102,8 @@ mod test {
         };
         let h_opt = find_optimal_h(&test_l2.with_h(0), 128).unwrap();
         let msis = test_l2.with_h(h_opt);
-        println!("{test_l2} -> lambda: {}", test_l2.security_level());
-        println!("{msis} -> lambda: {}", msis.security_level());
+        println!("{test_l2} -> lambda: {}", test_l2.security_level().unwrap());
+        println!("{msis} -> lambda: {}", msis.security_level().unwrap());
     }
 
     #[test]
@@ -115,7 +118,10 @@ mod test {
         };
         let h_opt = find_optimal_h(&test_linf.with_h(0), 128).unwrap();
         let msis = test_linf.with_h(h_opt);
-        println!("{test_linf} -> lambda: {}", test_linf.security_level());
-        println!("{msis} -> lambda: {}", msis.security_level());
+        println!(
+            "{test_linf} -> lambda: {}",
+            test_linf.security_level().unwrap()
+        );
+        println!("{msis} -> lambda: {}", msis.security_level().unwrap());
     }
 }
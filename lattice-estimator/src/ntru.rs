@@ -0,0 +1,238 @@
+use std::fmt;
+use std::fmt::{Debug, Display};
+
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+use crate::attack::{AttackEstimate, AttackKind, CostConstraint};
+use crate::errors::EstimatorError;
+use crate::lwe::LWE;
+use crate::reduction::{bkz_cost, bkz_delta, bkz_memory_cost, BkzSchedule, Estimates};
+
+/// NTRU parameters for instances $h = g/f \in R\_q = \mathbb{Z}\_\texttt{q}\[X\]/(X^\texttt{n}+1)$, where $f$ and $g$ have $\ell\_2$ norms `f_norm` and `g_norm`.
+pub struct NTRU {
+    pub(crate) n: usize,
+    pub(crate) q: BigUint,
+    pub(crate) f_norm: f64,
+    pub(crate) g_norm: f64,
+}
+
+impl Display for NTRU {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "NTRU[n={}, q={}, f_norm={}, g_norm={}]",
+            self.n, self.q, self.f_norm, self.g_norm
+        )
+    }
+}
+
+impl Debug for NTRU {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "NTRU[n={}, q={}, f_norm={}, g_norm={}]",
+            self.n, self.q, self.f_norm, self.g_norm
+        )
+    }
+}
+
+/// Which attack on the NTRU lattice applies first.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum NTRURegime {
+    /// Lattice reduction recovers the secret key as the unique shortest vector (SKR) before it detects the dense sublattice.
+    Standard,
+    /// The modulus is large enough (beyond the "fatigue point" [DvW21]) for lattice reduction to detect the dense sublattice spanned by the rotations of $(f, g)$ (DSD) at a smaller block size than SKR.
+    Overstretched,
+}
+
+/// The estimates of both attacks on an NTRU instance, see [`NTRU::security_level_internal`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NTRUEstimate {
+    pub regime: NTRURegime,
+    /// Secret key recovery with the primal uSVP attack.
+    pub skr: Option<AttackEstimate>,
+    /// Dense sublattice discovery, if lattice reduction with block size at most $2n$ triggers it.
+    pub dsd: Option<AttackEstimate>,
+}
+
+impl NTRUEstimate {
+    /// Return the cheaper of the two attacks, if any.
+    pub fn best(&self) -> Option<&AttackEstimate> {
+        self.skr
+            .iter()
+            .chain(self.dsd.iter())
+            .min_by(|a, b| a.log2_cost_time.total_cmp(&b.log2_cost_time))
+    }
+}
+
+impl NTRU {
+    pub const fn new(n: usize, q: BigUint, f_norm: f64, g_norm: f64) -> Self {
+        NTRU {
+            n,
+            q,
+            f_norm,
+            g_norm,
+        }
+    }
+
+    fn log_q(&self) -> f64 {
+        self.q.to_f64().unwrap().log2()
+    }
+
+    /// $\log\_2$ of the norm of the secret key $(f, g)$, i.e., of the length of each of the $n$ basis vectors of the dense sublattice.
+    fn log2_secret_norm(&self) -> f64 {
+        self.f_norm.hypot(self.g_norm).log2()
+    }
+
+    /// Return an error if the instance is invalid, i.e., unless $n \geq 1$, $q \geq 2$ and both norms are positive.
+    fn check_params(&self) -> Result<(), EstimatorError> {
+        if self.n == 0 {
+            return Err(EstimatorError::InvalidParameters(format!(
+                "{self}: the degree must be positive"
+            )));
+        }
+        if self.q < BigUint::from(2u64) {
+            return Err(EstimatorError::InvalidParameters(format!(
+                "{self}: the modulus must be at least 2"
+            )));
+        }
+        if [self.f_norm, self.g_norm]
+            .iter()
+            .any(|norm| norm.is_nan() || *norm <= 0.)
+        {
+            return Err(EstimatorError::InvalidParameters(format!(
+                "{self}: the norms of f and g must be positive"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Return the LWE instance (with $n$ samples) corresponding to the secret key recovery attack, where the coefficients of $(f, g)$ have the same standard deviation.
+    pub fn to_lwe(&self) -> LWE {
+        let std_dev = self.f_norm.hypot(self.g_norm) / ((2 * self.n) as f64).sqrt();
+        LWE::new(self.n, self.q.clone(), std_dev, self.n)
+    }
+
+    /// Return true iff BKZ with block size `block_size` triggers the dense sublattice discovery on the $2n$-dimensional NTRU lattice $L$ of volume $q^n$.
+    /// Following [KF17, DvW21], a BKZ-reduced basis following the GSA cannot exist if the projection of $L$ orthogonally to the first $n$ basis vectors has larger volume than the dense sublattice $L'$ of rank $n$: the projection of $L'$ is then a sublattice of larger volume than $L'$ itself, so some vector of $L'$ must be in the span of the first $n$ basis vectors.
+    /// Under the GSA, the first $n$ Gram-Schmidt vectors have volume $q^{n/2} \delta^{n^2}$, so the condition is $\delta^n < \sqrt{q} / \lVert (f, g) \rVert$.
+    fn dsd_succeeds(&self, block_size: usize) -> bool {
+        self.n as f64 * bkz_delta(block_size).log2() < 0.5 * self.log_q() - self.log2_secret_norm()
+    }
+
+    /// Return the cheapest dense sublattice discovery attack under the cost model `est`, or `None` if no block size up to the lattice dimension triggers it.
+    fn dsd(&self, est: Estimates) -> Option<AttackEstimate> {
+        let d = 2 * self.n;
+        // The attack succeeds for all block sizes above the smallest successful one, and its cost is increasing in the block size
+        let block_size = (2..=d).find(|&block_size| self.dsd_succeeds(block_size))?;
+        Some(AttackEstimate {
+            attack: AttackKind::DenseSublatticeDiscovery,
+            estimate: est,
+            block_size,
+            schedule: BkzSchedule::Fixed,
+            dim: d,
+            log2_cost_time: bkz_cost(
+                est,
+                block_size,
+                d,
+                self.log_q(),
+                self.n as f64 * self.log_q(),
+            ),
+            log2_cost_memory: Some(bkz_memory_cost(est, block_size)),
+        })
+    }
+
+    /// Return native estimates of secret key recovery (via the primal uSVP attack on [`NTRU::to_lwe`]) and dense sublattice discovery under the cost model `est`, together with the regime the instance is in.
+    pub fn security_level_internal(&self, est: Estimates) -> NTRUEstimate {
+        let skr = self
+            .to_lwe()
+            .security_level_internal(est, &CostConstraint::default(), BkzSchedule::Fixed)
+            .ok();
+        let dsd = self.dsd(est);
+        let regime = match (&skr, &dsd) {
+            (Some(skr), Some(dsd)) if dsd.block_size < skr.block_size => NTRURegime::Overstretched,
+            (None, Some(_)) => NTRURegime::Overstretched,
+            _ => NTRURegime::Standard,
+        };
+        NTRUEstimate { regime, skr, dsd }
+    }
+
+    /// Return the estimate under the default cost model [`Estimates::DEFAULT`] containing the cheapest attack.
+    pub fn estimate(&self) -> NTRUEstimate {
+        Estimates::DEFAULT
+            .iter()
+            .map(|est| self.security_level_internal(*est))
+            .min_by(|a, b| {
+                let cost = |e: &NTRUEstimate| e.best().map_or(f64::INFINITY, |a| a.log2_cost_time);
+                cost(a).total_cmp(&cost(b))
+            })
+            .unwrap()
+    }
+
+    /// Return lambda such that NTRU_{n, q, f_norm, g_norm} is 2^lambda-hard, i.e., the time cost of the cheapest attack in [`NTRU::estimate`].
+    /// Returns [`EstimatorError::InvalidParameters`] for invalid instances, and [`EstimatorError::NonConvergent`] if no attack succeeds.
+    pub fn security_level(&self) -> Result<f64, EstimatorError> {
+        self.check_params()?;
+        self.estimate()
+            .best()
+            .map(|attack| attack.log2_cost_time)
+            .ok_or_else(|| EstimatorError::NonConvergent(format!("no attack solves {self}")))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Ternary $f, g$ with $2n/3$ non-zero coefficients.
+    fn ternary_ntru(n: usize, q: u64) -> NTRU {
+        let norm = (2. * n as f64 / 3.).sqrt();
+        NTRU::new(n, q.into(), norm, norm)
+    }
+
+    #[test]
+    fn test_ntru_overstretched() {
+        let ntru = ternary_ntru(512, 512u64.pow(3));
+        let est = ntru.estimate();
+        println!("{ntru}: {est:?}");
+        assert_eq!(est.regime, NTRURegime::Overstretched);
+        let skr = est.skr.as_ref().unwrap();
+        let dsd = est.dsd.as_ref().unwrap();
+        assert_eq!(dsd.attack, AttackKind::DenseSublatticeDiscovery);
+        assert!(dsd.log2_cost_time < skr.log2_cost_time - 20.);
+        assert_eq!(ntru.security_level(), Ok(dsd.log2_cost_time));
+    }
+
+    #[test]
+    fn test_ntru_hps() {
+        // ntruhps2048509: ternary f and g with q/8 - 2 non-zero coefficients
+        let ntru = NTRU::new(
+            509,
+            2048u64.into(),
+            (2. * 509. / 3f64).sqrt(),
+            254f64.sqrt(),
+        );
+        let est = ntru.estimate();
+        println!("{ntru}: {est:?}");
+        assert_eq!(est.regime, NTRURegime::Standard);
+        assert!(est.dsd.is_none());
+        assert!(ntru.security_level().unwrap() >= 100.);
+    }
+
+    #[test]
+    fn test_ntru_invalid_params() {
+        for ntru in [
+            NTRU::new(0, 2048u64.into(), 1., 1.),
+            NTRU::new(509, 1u64.into(), 1., 1.),
+            NTRU::new(509, 2048u64.into(), 0., 1.),
+            NTRU::new(509, 2048u64.into(), 1., f64::NAN),
+        ] {
+            assert!(matches!(
+                ntru.security_level(),
+                Err(EstimatorError::InvalidParameters(_))
+            ));
+        }
+    }
+}
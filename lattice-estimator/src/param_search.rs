@@ -0,0 +1,311 @@
+//! Inverting the SIS estimator, i.e., searching for the smallest parameters achieving a target security level.
+use crate::attack::AttackEstimate;
+use crate::errors::{EstimatorError, LatticeEstimatorError};
+use crate::norms::Norm;
+use crate::reduction::{CostCache, Estimates};
+use crate::sis::SIS;
+
+/// Return true iff `security_level` reaches `target_lambda` bits of security.
+/// [`EstimatorError::NonConvergent`] (i.e., no attack succeeds) counts as secure; all other errors are returned.
+pub(crate) fn is_secure(
+    security_level: Result<f64, EstimatorError>,
+    target_lambda: f64,
+) -> Result<bool, EstimatorError> {
+    match security_level {
+        Ok(lambda) => Ok(lambda >= target_lambda),
+        Err(EstimatorError::NonConvergent(_)) => Ok(true),
+        Err(error) => Err(error),
+    }
+}
+
+/// Return the smallest `x` in `[lo, hi]` such that `estimate(x)` reaches `target_lambda` bits of security, together with the estimate at `x`, or `None` if no parameter in `[lo, hi]` reaches the target.
+/// Parameters for which `estimate` returns [`EstimatorError::NonConvergent`] (i.e., for which no attack succeeds) are considered secure; all other errors are returned as soon as they occur.
+/// This assumes that the security level is non-decreasing in `x`, and uses an exponential search from `lo` followed by a binary search, i.e., $O(\log x)$ calls to `estimate`.
+/// If no attack succeeds for the smallest secure parameter, there is no estimate to report, and the [`EstimatorError::NonConvergent`] error is returned.
+pub(crate) fn find_min_param<F>(
+    lo: u64,
+    hi: u64,
+    target_lambda: f64,
+    estimate: F,
+) -> Result<Option<(u64, AttackEstimate)>, EstimatorError>
+where
+    F: Fn(u64) -> Result<AttackEstimate, EstimatorError>,
+{
+    assert!(lo <= hi, "empty parameter range [{lo}, {hi}]");
+    let reaches_target = |x: u64| {
+        is_secure(
+            estimate(x).map(|attack| attack.log2_cost_time),
+            target_lambda,
+        )
+    };
+
+    // Exponential search for an upper bound
+    let (mut insecure, mut secure) = (None, lo);
+    while !reaches_target(secure)? {
+        if secure == hi {
+            return Ok(None);
+        }
+        insecure = Some(secure);
+        secure = secure.saturating_mul(2).clamp(secure + 1, hi);
+    }
+
+    // Loop invariant: `secure` is secure, and `insecure` (if any) is not
+    if let Some(mut insecure) = insecure {
+        while secure - insecure > 1 {
+            let mid = insecure + (secure - insecure) / 2;
+            if reaches_target(mid)? {
+                secure = mid;
+            } else {
+                insecure = mid;
+            }
+        }
+    }
+    estimate(secure).map(|attack| Some((secure, attack)))
+}
+
+/// A parameter set found by a [`ParamSearch`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParamSearchResult {
+    pub n: usize,
+    pub m: usize,
+    pub q: u64,
+    pub norm_bound: f64,
+    pub norm: Norm,
+    /// The best attack on `SIS\[n, m, q, norm_bound\]`, which reaches the target security level.
+    pub estimate: AttackEstimate,
+}
+
+impl ParamSearchResult {
+    pub fn to_sis(&self) -> SIS {
+        SIS::new(self.n, self.q.into(), self.norm_bound, self.m, self.norm)
+    }
+}
+
+/// Builder for searching the smallest modulus or dimension of SIS instances achieving a target security level, possibly for several norm bounds at once.
+/// ```
+/// # use lattice_estimator::norms::Norm;
+/// # use lattice_estimator::param_search::ParamSearch;
+/// # use lattice_estimator::reduction::Estimates;
+/// let results = ParamSearch::new(128., Estimates::BDGL16)
+///     .dimension(512)
+///     .num_cols(1024)
+///     .norm(Norm::L2)
+///     .sweep_norm_bounds([2048., 4096., 8192.])
+///     .find_min_modulus()
+///     .unwrap();
+/// assert_eq!(results.len(), 3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ParamSearch {
+    target_lambda: f64,
+    est: Estimates,
+    n: Option<usize>,
+    m: Option<usize>,
+    q: Option<u64>,
+    norm: Norm,
+    norm_bounds: Vec<f64>,
+}
+
+impl ParamSearch {
+    /// Start a search for parameters with at least `target_lambda` bits of security under the cost model `est`.
+    pub fn new(target_lambda: f64, est: Estimates) -> Self {
+        ParamSearch {
+            target_lambda,
+            est,
+            n: None,
+            m: None,
+            q: None,
+            norm: Norm::L2,
+            norm_bounds: vec![],
+        }
+    }
+
+    /// Set the number of rows `n`, which is required by [`ParamSearch::find_min_modulus`].
+    pub fn dimension(mut self, n: usize) -> Self {
+        self.n = Some(n);
+        self
+    }
+
+    /// Set the number of columns `m`, which is always required.
+    pub fn num_cols(mut self, m: usize) -> Self {
+        self.m = Some(m);
+        self
+    }
+
+    /// Set the modulus `q`, which is required by [`ParamSearch::find_min_dimension`].
+    pub fn modulus(mut self, q: u64) -> Self {
+        self.q = Some(q);
+        self
+    }
+
+    /// Set the norm of the solution (defaults to [`Norm::L2`]).
+    pub fn norm(mut self, norm: Norm) -> Self {
+        self.norm = norm;
+        self
+    }
+
+    /// Search parameters for the norm bound `norm_bound` (in addition to any previously set norm bounds).
+    pub fn norm_bound(mut self, norm_bound: f64) -> Self {
+        self.norm_bounds.push(norm_bound);
+        self
+    }
+
+    /// Search parameters for each of the norm bounds in `norm_bounds` (in addition to any previously set norm bounds).
+    pub fn sweep_norm_bounds<I: IntoIterator<Item = f64>>(mut self, norm_bounds: I) -> Self {
+        self.norm_bounds.extend(norm_bounds);
+        self
+    }
+
+    fn require<T: Copy>(param: Option<T>, name: &str) -> Result<T, LatticeEstimatorError> {
+        param.ok_or(LatticeEstimatorError::from(format!(
+            "parameter search requires {name} to be set"
+        )))
+    }
+
+    /// For each norm bound, return the parameters with the smallest modulus reaching the target security level, see [`SIS::find_min_modulus`].
+    /// Norm bounds for which no such modulus exists are skipped, and the first estimator error is returned.
+    pub fn find_min_modulus(&self) -> Result<Vec<ParamSearchResult>, LatticeEstimatorError> {
+        let n = Self::require(self.n, "the dimension")?;
+        let m = Self::require(self.m, "the number of columns")?;
+        // A single cache for the whole sweep
+        let cache = CostCache::new();
+        self.norm_bounds
+            .iter()
+            .filter_map(|&norm_bound| {
+                SIS::find_min_modulus_cached(
+                    n,
+                    m,
+                    norm_bound,
+                    self.norm,
+                    self.target_lambda,
+                    self.est,
+                    &cache,
+                )
+                .transpose()
+                .map(|result| {
+                    result.map(|(q, estimate)| ParamSearchResult {
+                        n,
+                        m,
+                        q,
+                        norm_bound,
+                        norm: self.norm,
+                        estimate,
+                    })
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map_err(LatticeEstimatorError::from)
+    }
+
+    /// For each norm bound, return the parameters with the smallest dimension reaching the target security level, see [`SIS::find_min_dimension`].
+    /// Norm bounds for which no such dimension exists are skipped, and the first estimator error is returned.
+    pub fn find_min_dimension(&self) -> Result<Vec<ParamSearchResult>, LatticeEstimatorError> {
+        let m = Self::require(self.m, "the number of columns")?;
+        let q = Self::require(self.q, "the modulus")?;
+        // All candidate instances share the modulus and number of columns, and hence the BKZ costs
+        let cache = CostCache::new();
+        self.norm_bounds
+            .iter()
+            .filter_map(|&norm_bound| {
+                SIS::find_min_dimension_cached(
+                    m,
+                    q,
+                    norm_bound,
+                    self.norm,
+                    self.target_lambda,
+                    self.est,
+                    &cache,
+                )
+                .transpose()
+                .map(|result| {
+                    result.map(|(n, estimate)| ParamSearchResult {
+                        n,
+                        m,
+                        q,
+                        norm_bound,
+                        norm: self.norm,
+                        estimate,
+                    })
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map_err(LatticeEstimatorError::from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_find_min_param() {
+        let estimate = |x: u64| {
+            Ok(AttackEstimate {
+                log2_cost_time: x as f64,
+                ..AttackEstimate::trivial(Estimates::BDGL16)
+            })
+        };
+        let find = |lo, hi| find_min_param(lo, hi, 128., estimate).unwrap();
+        assert_eq!(find(1, 1000).unwrap().0, 128);
+        assert_eq!(find(200, 1000).unwrap().0, 200);
+        assert_eq!(find(1, 128).unwrap().0, 128);
+        assert!(find(1, 127).is_none());
+    }
+
+    #[test]
+    fn test_find_min_param_errors() {
+        let non_convergent = || EstimatorError::NonConvergent("no attack".to_string());
+        // Parameters for which no attack succeeds are secure
+        let estimate = |x: u64| {
+            if x >= 100 {
+                return Err(non_convergent());
+            }
+            Ok(AttackEstimate {
+                log2_cost_time: x as f64,
+                ..AttackEstimate::trivial(Estimates::BDGL16)
+            })
+        };
+        assert_eq!(
+            find_min_param(1, 1000, 64., estimate).unwrap().unwrap().0,
+            64
+        );
+        // ... but there is no estimate to report if the smallest secure parameter is one of them
+        assert_eq!(
+            find_min_param(1, 1000, 128., estimate),
+            Err(non_convergent())
+        );
+        // Other errors are not considered secure
+        let invalid = EstimatorError::InvalidParameters("invalid".to_string());
+        assert_eq!(
+            find_min_param(1, 1000, 128., |x| if x > 4 {
+                Err(invalid.clone())
+            } else {
+                estimate(x)
+            }),
+            Err(invalid)
+        );
+    }
+
+    #[test]
+    fn test_param_search_sweep() {
+        let search = ParamSearch::new(128., Estimates::BDGL16)
+            .dimension(512)
+            .num_cols(1024)
+            .sweep_norm_bounds([2048., 4096., 8192.]);
+        let results = search.find_min_modulus().unwrap();
+        assert_eq!(results.len(), 3);
+        for r in results.iter() {
+            assert!(r.estimate.log2_cost_time >= 128.);
+        }
+        // Larger norm bounds require larger moduli
+        for w in results.windows(2) {
+            assert!(w[0].q <= w[1].q);
+        }
+
+        assert!(search.find_min_dimension().is_err());
+        let results = search.modulus(12289).find_min_dimension().unwrap();
+        for w in results.windows(2) {
+            assert!(w[0].n <= w[1].n);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Instances underlying well-known lattice-based schemes, with their published parameters.
+use crate::lwe::LWE;
+use crate::norms::Norm;
+use crate::sis::SIS;
+
+/// Key recovery for Kyber-512: $n = 2 \cdot 256$, $q = 3329$ and secret and error from the centered binomial distribution with $\eta\_1 = 3$ (standard deviation $\sqrt{3/2}$), with $n$ samples.
+/// ```
+/// # use lattice_estimator::presets::kyber512_lwe;
+/// let lambda = kyber512_lwe().security_level().unwrap();
+/// assert!((135. ..150.).contains(&lambda));
+/// ```
+pub fn kyber512_lwe() -> LWE {
+    LWE::new(512, 3329u64.into(), 1.5f64.sqrt(), 512)
+}
+
+/// Key recovery for Kyber-768: $n = 3 \cdot 256$, $q = 3329$ and secret and error from the centered binomial distribution with $\eta\_1 = 2$ (standard deviation $1$), with $n$ samples.
+/// ```
+/// # use lattice_estimator::presets::kyber768_lwe;
+/// let lambda = kyber768_lwe().security_level().unwrap();
+/// assert!((195. ..215.).contains(&lambda));
+/// ```
+pub fn kyber768_lwe() -> LWE {
+    LWE::new(768, 3329u64.into(), 1., 768)
+}
+
+/// The MSIS instance underlying the (weak) unforgeability of Dilithium2, flattened to SIS: $h = 4 \cdot 256$, $w = (4 + 4 + 1) \cdot 256$ and $q = 8380417$, with an $\ell\_\infty$ bound.
+/// ```
+/// # use lattice_estimator::presets::dilithium2_sis;
+/// let lambda = dilithium2_sis().security_level().unwrap();
+/// assert!((130. ..160.).contains(&lambda));
+/// ```
+pub fn dilithium2_sis() -> SIS {
+    SIS::new(1024, 8380417u64.into(), 350209., 2304, Norm::Linf)
+}
+
+/// The SIS instance underlying the unforgeability of Falcon-512: $h = 512$, $w = 2 \cdot 512$, $q = 12289$ and the $\ell\_2$ bound $\beta = \sqrt{34034726}$ on signatures.
+/// ```
+/// # use lattice_estimator::presets::falcon512_sis;
+/// let lambda = falcon512_sis().security_level().unwrap();
+/// assert!((130. ..150.).contains(&lambda));
+/// ```
+pub fn falcon512_sis() -> SIS {
+    SIS::new(512, 12289u64.into(), 34034726f64.sqrt(), 1024, Norm::L2)
+}
+
+/// The SIS instance underlying the unforgeability of Falcon-1024: $h = 1024$, $w = 2 \cdot 1024$, $q = 12289$ and the $\ell\_2$ bound $\beta = \sqrt{70265242}$ on signatures.
+/// ```
+/// # use lattice_estimator::presets::falcon1024_sis;
+/// let lambda = falcon1024_sis().security_level().unwrap();
+/// assert!((280. ..310.).contains(&lambda));
+/// ```
+pub fn falcon1024_sis() -> SIS {
+    SIS::new(1024, 12289u64.into(), 70265242f64.sqrt(), 2048, Norm::L2)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Current outputs of the estimator, to flag changes to the cost models.
+    const PINNED_LWE: [(fn() -> LWE, f64); 2] = [(kyber512_lwe, 140.75), (kyber768_lwe, 202.76)];
+    const PINNED_SIS: [(fn() -> SIS, f64); 3] = [
+        (dilithium2_sis, 132.70),
+        (falcon512_sis, 144.31),
+        (falcon1024_sis, 295.89),
+    ];
+
+    #[test]
+    fn test_pinned_security_levels() {
+        for (preset, expected) in PINNED_LWE {
+            let lwe = preset();
+            let lambda = lwe.security_level().unwrap();
+            assert!(
+                (lambda - expected).abs() <= 2.,
+                "{lwe}: lambda = {lambda}, expected {expected}"
+            );
+        }
+        for (preset, expected) in PINNED_SIS {
+            let sis = preset();
+            let lambda = sis.security_level().unwrap();
+            assert!(
+                (lambda - expected).abs() <= 2.,
+                "{sis}: lambda = {lambda}, expected {expected}"
+            );
+        }
+    }
+}
@@ -0,0 +1,878 @@
+//! Native cost models for lattice reduction, following `estimator/reduction.py` of the [lattice-estimator](https://github.com/malb/lattice-estimator).
+//! All costs are returned as $\log\_2$ of the number of operations.
+use std::collections::HashMap;
+use std::f64::consts::{E, PI};
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{LazyLock, Mutex};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::errors::LatticeEstimatorError;
+use crate::simulator::gsa_log2_sq_norm;
+
+/// Cost models for solving SVP in dimension $\beta$ (and thus for running BKZ with block size $\beta$).
+/// Cost models are (de)serialized as their string representation, see [`Estimates::from_str`].
+/// Equality compares the costs a model computes, i.e., it ignores the label of [`Estimates::Custom`], which is not part of the string representation.
+#[derive(Clone, Copy, Debug)]
+pub enum Estimates {
+    /// Core-SVP with classical sieving, $2^{0.292 \beta}$ [ADPS16].
+    ADPS16,
+    /// Classical sieving [BDGL16], $2^{0.292 \beta + 16.4}$ per SVP call (and $2^{0.387 \beta - 16.4}$ for $\beta < 90$).
+    BDGL16,
+    /// Quantum sieving [LaaMosPol14], $2^{0.265 \beta + 16.4}$ per SVP call.
+    LaaMosPol14,
+    /// Quantum sieving [ChaLoy21], $2^{0.257 \beta + 16.4}$ per SVP call.
+    ChaLoy21,
+    /// Enumeration with extreme pruning [CheNgu12].
+    CheNgu12,
+    /// Gate count of list-decoding sieving with dimensions for free, as in the Kyber round 3 specification.
+    Kyber,
+    /// Gate count of list-decoding sieving with dimensions for free, as in [MATZOV22].
+    Matzov,
+    /// Sieving with user-provided constants, $2^{a \beta + b}$ per SVP call. `label` is used to identify the model in attack reports.
+    Custom { a: f64, b: f64, label: &'static str },
+}
+
+impl Estimates {
+    pub const ALL: [Estimates; 7] = [
+        Estimates::ADPS16,
+        Estimates::BDGL16,
+        Estimates::LaaMosPol14,
+        Estimates::ChaLoy21,
+        Estimates::CheNgu12,
+        Estimates::Kyber,
+        Estimates::Matzov,
+    ];
+
+    /// Classical cost models that count the full cost of BKZ, used by e.g. [`crate::sis::SIS::best_attack`].
+    /// This excludes the core-SVP ([`Estimates::ADPS16`]) and quantum models, and matches the default of the lattice-estimator (MATZOV) in most regimes.
+    pub const DEFAULT: [Estimates; 4] = [
+        Estimates::BDGL16,
+        Estimates::CheNgu12,
+        Estimates::Kyber,
+        Estimates::Matzov,
+    ];
+}
+
+impl PartialEq for Estimates {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Estimates::Custom { a, b, .. }, Estimates::Custom { a: a_, b: b_, .. }) => {
+                a == a_ && b == b_
+            }
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl Estimates {
+    /// Return a human-readable name of the cost model, i.e., the label for [`Estimates::Custom`] and the string representation otherwise.
+    pub fn name(&self) -> String {
+        match self {
+            Estimates::Custom { label, .. } => label.to_string(),
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl Display for Estimates {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Estimates::ADPS16 => write!(f, "adps16"),
+            Estimates::BDGL16 => write!(f, "bdgl16"),
+            Estimates::LaaMosPol14 => write!(f, "laamospol14"),
+            Estimates::ChaLoy21 => write!(f, "chaloy21"),
+            Estimates::CheNgu12 => write!(f, "chengu12"),
+            Estimates::Kyber => write!(f, "kyber-classical"),
+            Estimates::Matzov => write!(f, "matzov-classical"),
+            Estimates::Custom { a, b, .. } => write!(f, "custom:{a}:{b}"),
+        }
+    }
+}
+
+impl FromStr for Estimates {
+    type Err = LatticeEstimatorError;
+
+    /// Parse a cost model from its string representation as produced by `Display`, e.g., `"bdgl16"`, `"matzov-classical"` or `"custom:0.292:16.4"`.
+    /// The label is not part of the string representation of [`Estimates::Custom`], parsed custom models are labeled `"custom"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(constants) = s.strip_prefix("custom:") {
+            let constants = constants
+                .split(':')
+                .map(f64::from_str)
+                .collect::<Result<Vec<_>, _>>();
+            return match constants.as_deref() {
+                Ok(&[a, b]) => Ok(Estimates::Custom {
+                    a,
+                    b,
+                    label: "custom",
+                }),
+                _ => Err(LatticeEstimatorError::from(format!(
+                    "invalid custom cost model \"{s}\", expected \"custom:<a>:<b>\""
+                ))),
+            };
+        }
+        Estimates::ALL
+            .into_iter()
+            .find(|est| est.to_string() == s)
+            .ok_or(LatticeEstimatorError::from(format!(
+                "unknown cost model \"{s}\""
+            )))
+    }
+}
+
+impl Serialize for Estimates {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Estimates {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Estimates::from_str(&s).map_err(de::Error::custom)
+    }
+}
+
+/// Experimental root Hermite factors for small block sizes, as used by the lattice-estimator.
+const SMALL_DELTA: [(usize, f64); 8] = [
+    (2, 1.02190),
+    (5, 1.01862),
+    (10, 1.01616),
+    (15, 1.01485),
+    (20, 1.01420),
+    (25, 1.01342),
+    (28, 1.01331),
+    (40, 1.01295),
+];
+
+/// Block sizes below which [`bkz_delta`] is looked up in a precomputed table.
+const BKZ_DELTA_TABLE_SIZE: usize = 4096;
+
+/// Root Hermite factors for all block sizes below [`BKZ_DELTA_TABLE_SIZE`], computed on first use.
+static BKZ_DELTA_TABLE: LazyLock<Vec<f64>> =
+    LazyLock::new(|| (0..BKZ_DELTA_TABLE_SIZE).map(compute_bkz_delta).collect());
+
+/// Block size from which [`bkz_delta`] follows the asymptotic formula; between the largest tabulated block size and this one, it blends the two.
+const BKZ_DELTA_BLEND_END: usize = 60;
+
+/// Return the root Hermite factor $\delta$ achieved by BKZ with block size `block_size`.
+/// Up to the largest tabulated block size (40), this interpolates linearly between the experimental values; from [`BKZ_DELTA_BLEND_END`] on, it uses the asymptotic formula $\delta = \left(\frac{\beta}{2\pi e} (\pi\beta)^{1/\beta}\right)^{1/(2(\beta-1))}$, and in between a smoothstep blend of the two.
+/// The result is strictly decreasing in $\beta \geq 2$ and continuous in $\beta$, so cost curves have no plateaus or jumps.
+/// Since this is evaluated for every candidate block size and dimension, values for block sizes below [`BKZ_DELTA_TABLE_SIZE`] are precomputed.
+pub fn bkz_delta(block_size: usize) -> f64 {
+    BKZ_DELTA_TABLE
+        .get(block_size)
+        .copied()
+        .unwrap_or_else(|| compute_bkz_delta(block_size))
+}
+
+fn asymptotic_bkz_delta(block_size: usize) -> f64 {
+    let beta = block_size as f64;
+    (beta / (2. * PI * E) * (PI * beta).powf(1. / beta)).powf(1. / (2. * (beta - 1.)))
+}
+
+fn compute_bkz_delta(block_size: usize) -> f64 {
+    let (first, last) = (SMALL_DELTA[0], SMALL_DELTA[SMALL_DELTA.len() - 1]);
+    if block_size <= first.0 {
+        first.1
+    } else if block_size <= last.0 {
+        // Interpolate linearly between the surrounding tabulated block sizes
+        let i = SMALL_DELTA.partition_point(|(b, _)| *b < block_size);
+        let ((b0, d0), (b1, d1)) = (SMALL_DELTA[i - 1], SMALL_DELTA[i]);
+        d0 + (d1 - d0) * (block_size - b0) as f64 / (b1 - b0) as f64
+    } else if block_size < BKZ_DELTA_BLEND_END {
+        // The asymptotic formula is below the last tabulated value (and decreasing) here, so the blend is decreasing
+        let u = (block_size - last.0) as f64 / (BKZ_DELTA_BLEND_END - last.0) as f64;
+        let t = u * u * (3. - 2. * u);
+        (1. - t) * last.1 + t * asymptotic_bkz_delta(block_size)
+    } else {
+        asymptotic_bkz_delta(block_size)
+    }
+}
+
+/// Return the $\log\_2$ of the number of SVP calls made by BKZ with block size `block_size` in dimension `d`, i.e., $8d$ (8 tours) or 1 if `block_size >= d`.
+pub fn svp_repeat(block_size: usize, d: usize) -> f64 {
+    svp_repeat_tours(block_size, d, 8)
+}
+
+/// Return the $\log\_2$ of the number of SVP calls made by `tours` tours of BKZ with block size `block_size` in dimension `d`, i.e., $\texttt{tours} \cdot d$ or 1 if `block_size >= d`.
+pub fn svp_repeat_tours(block_size: usize, d: usize, tours: usize) -> f64 {
+    if block_size < d {
+        ((tours * d) as f64).log2()
+    } else {
+        0.
+    }
+}
+
+/// Return the $\log\_2$ of the cost of running LLL in dimension `d` on a basis with entries of `log_q` bits, i.e., $d^3 \log\_2(q)^2$.
+pub fn lll_cost(d: usize, log_q: f64) -> f64 {
+    3. * (d as f64).log2() + 2. * log_q.max(1.).log2()
+}
+
+/// $\log\_2(2^a + 2^b)$, computed without leaving the log domain.
+pub(crate) fn log2_add(a: f64, b: f64) -> f64 {
+    let (hi, lo) = if a >= b { (a, b) } else { (b, a) };
+    hi + (1. + (lo - hi).exp2()).log2()
+}
+
+/// Number of "dimensions for free" [Duc18] when sieving in dimension `block_size`.
+pub fn dims_for_free(block_size: usize) -> usize {
+    let beta = block_size as f64;
+    if block_size < 40 {
+        return 0;
+    }
+    (beta * (4. / 3f64).ln() / (beta / (2. * PI * E)).ln())
+        .floor()
+        .max(0.) as usize
+}
+
+/// Classical sieving cost [BDGL16] for BKZ with block size `block_size` in dimension `d`.
+pub fn bdgl_sieve(block_size: usize, d: usize) -> f64 {
+    let beta = block_size as f64;
+    let svp = if block_size < 90 {
+        0.387 * beta - 16.4
+    } else {
+        0.292 * beta + 16.4
+    };
+    svp + svp_repeat(block_size, d)
+}
+
+/// Enumeration cost [CheNgu12] for BKZ with block size `block_size` in dimension `d`.
+pub fn enumeration_cost(block_size: usize, d: usize) -> f64 {
+    let beta = block_size as f64;
+    let svp = 0.270188776350190 * beta * beta.ln() - 1.0192050451318417 * beta
+        + 16.10253135200765
+        + 100f64.log2();
+    svp + svp_repeat(block_size, d)
+}
+
+/// Return the number of Gram-Schmidt vectors lattice reduction with block size `block_size` has to process on a `d`-dimensional q-ary lattice with modulus $2^\texttt{log\\_q}$ and volume $2^\texttt{log2\\_volume}$ (e.g., $q^h$ for the SIS lattice of an $h \times d$ matrix).
+/// Under the Z-shape GSA (see [`crate::simulator::zgsa_simulator`]), the leading Gram-Schmidt vectors for which the GSA profile exceeds $q$ are q-vectors, which BKZ leaves untouched.
+pub fn qary_useful_dim(block_size: usize, d: usize, log_q: f64, log2_volume: f64) -> usize {
+    // The GSA profile is decreasing, find the first index below q by binary search
+    let (mut lo, mut hi) = (0, d);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if gsa_log2_sq_norm(d, log2_volume, block_size, mid) > 2. * log_q {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    d - lo
+}
+
+/// Gate count of sieving with list decoding and dimensions for free, parameterized by the fitted constants `a`, `b`.
+/// The number of SVP calls is the number of blocks in the useful part of the basis, see [`qary_useful_dim`].
+fn list_decoding_cost(
+    block_size: usize,
+    d: usize,
+    log_q: f64,
+    log2_volume: f64,
+    a: f64,
+    b: f64,
+) -> f64 {
+    const C: f64 = 5.46;
+    if block_size < 20 {
+        return enumeration_cost(block_size, d);
+    }
+    let beta_ = (block_size - dims_for_free(block_size)) as f64;
+    let d_useful = qary_useful_dim(block_size, d, log_q, log2_volume);
+    let svp_calls = (d_useful.saturating_sub(block_size).max(1) as f64).log2();
+    C.log2() + a * beta_ + b + svp_calls
+}
+
+/// Kyber round 3 cost model for BKZ with block size `block_size` in dimension `d` on a q-ary lattice with modulus $2^\texttt{log\\_q}$ and volume $2^\texttt{log2\\_volume}$.
+pub fn kyber_cost(block_size: usize, d: usize, log_q: f64, log2_volume: f64) -> f64 {
+    list_decoding_cost(
+        block_size,
+        d,
+        log_q,
+        log2_volume,
+        0.2988026130564745,
+        26.011121212891872,
+    )
+}
+
+/// MATZOV cost model for BKZ with block size `block_size` in dimension `d` on a q-ary lattice with modulus $2^\texttt{log\\_q}$ and volume $2^\texttt{log2\\_volume}$.
+pub fn matzov_cost(block_size: usize, d: usize, log_q: f64, log2_volume: f64) -> f64 {
+    list_decoding_cost(
+        block_size,
+        d,
+        log_q,
+        log2_volume,
+        0.29613500308205365,
+        20.387885985467914,
+    )
+}
+
+/// Return the $\log\_2$ of the cost of a single SVP oracle call in dimension `block_size` (inside BKZ in dimension `d`), excluding repetitions.
+/// A single call does not depend on the q-ary structure of the lattice, so the volume passed to [`kyber_cost`] and [`matzov_cost`] does not matter.
+pub fn svp_cost(est: Estimates, block_size: usize, d: usize, log_q: f64) -> f64 {
+    let beta = block_size as f64;
+    match est {
+        Estimates::ADPS16 => 0.292 * beta,
+        Estimates::BDGL16 => bdgl_sieve(block_size, d) - svp_repeat(block_size, d),
+        Estimates::LaaMosPol14 => 0.265 * beta + 16.4,
+        Estimates::ChaLoy21 => 0.257 * beta + 16.4,
+        Estimates::CheNgu12 => enumeration_cost(block_size, d) - svp_repeat(block_size, d),
+        Estimates::Kyber => kyber_cost(block_size, block_size, log_q, 0.),
+        Estimates::Matzov => matzov_cost(block_size, block_size, log_q, 0.),
+        Estimates::Custom { a, b, .. } => a * beta + b,
+    }
+}
+
+/// Return the $\log\_2$ of the cost of running BKZ with block size `block_size` on a `d`-dimensional basis with entries of `log_q` bits and volume $2^\texttt{log2\\_volume}$ under the cost model `est`.
+/// Core-SVP ([`Estimates::ADPS16`]) only counts a single SVP call; all other models add the cost of LLL preprocessing and of $8d$ SVP calls (8 tours).
+pub fn bkz_cost(est: Estimates, block_size: usize, d: usize, log_q: f64, log2_volume: f64) -> f64 {
+    let svp = match est {
+        Estimates::ADPS16 => return svp_cost(est, block_size, d, log_q),
+        Estimates::BDGL16 => bdgl_sieve(block_size, d),
+        Estimates::CheNgu12 => enumeration_cost(block_size, d),
+        Estimates::Kyber => kyber_cost(block_size, d, log_q, log2_volume),
+        Estimates::Matzov => matzov_cost(block_size, d, log_q, log2_volume),
+        Estimates::LaaMosPol14 | Estimates::ChaLoy21 | Estimates::Custom { .. } => {
+            svp_cost(est, block_size, d, log_q) + svp_repeat(block_size, d)
+        }
+    };
+    log2_add(lll_cost(d, log_q), svp)
+}
+
+/// Block size at which progressive BKZ schedules start by default.
+pub const PROGRESSIVE_BKZ_START: usize = 60;
+
+/// Return the $\log\_2$ of the cost of running progressive BKZ with block sizes $60, 61, \ldots, \texttt{target\\_block\\_size}$ and a single tour per block size, see [`progressive_bkz_cost_with_schedule`].
+pub fn progressive_bkz_cost(
+    est: Estimates,
+    target_block_size: usize,
+    d: usize,
+    log_q: f64,
+    log2_volume: f64,
+) -> f64 {
+    progressive_bkz_cost_with_schedule(
+        est,
+        target_block_size,
+        d,
+        log_q,
+        log2_volume,
+        PROGRESSIVE_BKZ_START,
+        1,
+    )
+}
+
+/// Return the $\log\_2$ of the cost of running progressive BKZ on a `d`-dimensional basis with entries of `log_q` bits and volume $2^\texttt{log2\\_volume}$, i.e., the sum of the costs of a single tour for each block size `start, start + step, ...`, up to and including `target_block_size`.
+/// For `target_block_size < start`, as well as for the cost models that only count a single tour in the first place (core-SVP, [`Estimates::Kyber`] and [`Estimates::Matzov`]), this is just [`bkz_cost`].
+pub fn progressive_bkz_cost_with_schedule(
+    est: Estimates,
+    target_block_size: usize,
+    d: usize,
+    log_q: f64,
+    log2_volume: f64,
+    start: usize,
+    step: usize,
+) -> f64 {
+    assert!(step > 0, "progressive BKZ step must be positive");
+    if target_block_size < start
+        || matches!(
+            est,
+            Estimates::ADPS16 | Estimates::Kyber | Estimates::Matzov
+        )
+    {
+        return bkz_cost(est, target_block_size, d, log_q, log2_volume);
+    }
+    let tour_cost =
+        |block_size| svp_cost(est, block_size, d, log_q) + svp_repeat_tours(block_size, d, 1);
+    let svp = (start..target_block_size)
+        .step_by(step)
+        .chain([target_block_size])
+        .map(tour_cost)
+        .reduce(log2_add)
+        .unwrap();
+    log2_add(lll_cost(d, log_q), svp)
+}
+
+/// Block size schedule of the BKZ runs in an attack.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BkzSchedule {
+    /// A single BKZ run with the final block size and 8 tours, see [`bkz_cost`].
+    #[default]
+    Fixed,
+    /// Progressive BKZ with block sizes `start, start + step, ...` up to the final block size and a single tour each, see [`progressive_bkz_cost_with_schedule`].
+    Progressive { start: usize, step: usize },
+}
+
+impl BkzSchedule {
+    /// Progressive BKZ starting at block size [`PROGRESSIVE_BKZ_START`] and increasing the block size by one after each tour.
+    pub const PROGRESSIVE: BkzSchedule = BkzSchedule::Progressive {
+        start: PROGRESSIVE_BKZ_START,
+        step: 1,
+    };
+
+    /// Return the $\log\_2$ of the cost of reaching block size `block_size` on a `d`-dimensional basis with entries of `log_q` bits and volume $2^\texttt{log2\\_volume}$ under the cost model `est`.
+    pub fn cost(
+        &self,
+        est: Estimates,
+        block_size: usize,
+        d: usize,
+        log_q: f64,
+        log2_volume: f64,
+    ) -> f64 {
+        match self {
+            BkzSchedule::Fixed => bkz_cost(est, block_size, d, log_q, log2_volume),
+            BkzSchedule::Progressive { start, step } => progressive_bkz_cost_with_schedule(
+                est,
+                block_size,
+                d,
+                log_q,
+                log2_volume,
+                *start,
+                *step,
+            ),
+        }
+    }
+}
+
+impl Display for BkzSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BkzSchedule::Fixed => write!(f, "fixed"),
+            BkzSchedule::Progressive { start, step } => write!(f, "progressive({start}:{step})"),
+        }
+    }
+}
+
+/// Key identifying the arguments of [`BkzSchedule::cost`] except for the dimension in a [`CostCache`]; cost models, moduli and volumes are compared bitwise.
+type CostKey = (BkzSchedule, (u8, u64, u64), usize, u64, u64);
+
+/// Memoizes [`BkzSchedule::cost`] (and thus [`bkz_cost`]) across the many evaluations with identical arguments made by parameter searches, e.g., for the same modulus and number of columns but different numbers of rows.
+/// Costs are stored per schedule, cost model, block size, modulus and volume in a vector indexed by the dimension, since searches evaluate them for ranges of dimensions.
+/// Only the [`Estimates::Kyber`] and [`Estimates::Matzov`] costs depend on the volume, so the other cost models share their entries across volumes.
+/// Cached costs are bit-identical to uncached ones.
+/// The cache is thread-safe, so that a single cache can serve a whole sweep evaluated in parallel, see [`estimate_grid`](crate::table::estimate_grid).
+#[derive(Debug, Default)]
+pub struct CostCache {
+    costs: Mutex<HashMap<CostKey, Vec<Option<f64>>>>,
+    hits: AtomicUsize,
+}
+
+impl CostCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn key(
+        schedule: BkzSchedule,
+        est: Estimates,
+        block_size: usize,
+        log_q: f64,
+        log2_volume: f64,
+    ) -> CostKey {
+        let log2_volume = match est {
+            Estimates::Kyber | Estimates::Matzov => log2_volume,
+            _ => 0.,
+        };
+        let est = match est {
+            Estimates::ADPS16 => (0, 0, 0),
+            Estimates::BDGL16 => (1, 0, 0),
+            Estimates::LaaMosPol14 => (2, 0, 0),
+            Estimates::ChaLoy21 => (3, 0, 0),
+            Estimates::CheNgu12 => (4, 0, 0),
+            Estimates::Kyber => (5, 0, 0),
+            Estimates::Matzov => (6, 0, 0),
+            Estimates::Custom { a, b, .. } => (7, a.to_bits(), b.to_bits()),
+        };
+        (
+            schedule,
+            est,
+            block_size,
+            log_q.to_bits(),
+            log2_volume.to_bits(),
+        )
+    }
+
+    /// Return [`bkz_cost`]`(est, block_size, d, log_q, log2_volume)`, computing it only if it is not cached yet.
+    pub fn bkz_cost(
+        &self,
+        est: Estimates,
+        block_size: usize,
+        d: usize,
+        log_q: f64,
+        log2_volume: f64,
+    ) -> f64 {
+        self.cost(BkzSchedule::Fixed, est, block_size, d, log_q, log2_volume)
+    }
+
+    /// Return `schedule.cost(est, block_size, d, log_q, log2_volume)`, computing it only if it is not cached yet.
+    pub fn cost(
+        &self,
+        schedule: BkzSchedule,
+        est: Estimates,
+        block_size: usize,
+        d: usize,
+        log_q: f64,
+        log2_volume: f64,
+    ) -> f64 {
+        let key = Self::key(schedule, est, block_size, log_q, log2_volume);
+        let cached = self
+            .costs
+            .lock()
+            .unwrap()
+            .get(&key)
+            .and_then(|costs| costs.get(d).copied().flatten());
+        if let Some(cost) = cached {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return cost;
+        }
+        // Compute outside the lock, so that parallel sweeps only contend on the lookups
+        let cost = schedule.cost(est, block_size, d, log_q, log2_volume);
+        let mut costs = self.costs.lock().unwrap();
+        let costs = costs.entry(key).or_default();
+        if costs.len() <= d {
+            costs.resize(d + 1, None);
+        }
+        costs[d] = Some(cost);
+        cost
+    }
+
+    /// Return the number of lookups answered from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Return the number of cached costs.
+    pub fn len(&self) -> usize {
+        self.costs
+            .lock()
+            .unwrap()
+            .values()
+            .map(|costs| costs.iter().flatten().count())
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Return the $\log\_2$ of the memory (in vectors) required by a single SVP oracle call in dimension `block_size` under the cost model `est`.
+/// Sieves store $2^{0.2075 \beta}$ vectors (in dimension $\beta$ minus the dimensions for free for [`Estimates::Kyber`] and [`Estimates::Matzov`]), while enumeration only requires memory polynomial in $\beta$, which we take to be $\beta^2$.
+pub fn bkz_memory_cost(est: Estimates, block_size: usize) -> f64 {
+    let beta = block_size as f64;
+    match est {
+        Estimates::ADPS16
+        | Estimates::BDGL16
+        | Estimates::LaaMosPol14
+        | Estimates::ChaLoy21
+        | Estimates::Custom { .. } => 0.2075 * beta,
+        Estimates::CheNgu12 => 2. * beta.log2(),
+        Estimates::Kyber | Estimates::Matzov => {
+            0.2075 * (block_size - dims_for_free(block_size)) as f64
+        }
+    }
+}
+
+/// Return the block size in `[lo, hi]` minimizing `cost_fn`, together with its cost.
+/// This assumes that `cost_fn` is unimodal in the block size, where infeasible block sizes (e.g., those for which an attack does not succeed) have cost `f64::INFINITY`.
+/// The minimum is found using ternary search, followed by a verification pass over the ±3 neighbors of the result to guard against small non-monotonicities (e.g., from rounding dimensions for free).
+pub fn find_optimal_block_size<F>(cost_fn: F, lo: usize, hi: usize) -> (usize, f64)
+where
+    F: Fn(usize) -> f64,
+{
+    assert!(lo <= hi, "empty block size range [{lo}, {hi}]");
+    let (mut lo, mut hi) = (lo, hi);
+    let (lo_0, hi_0) = (lo, hi);
+    // Loop invariant: a minimizer of cost_fn is in [lo, hi]
+    while hi - lo > 2 {
+        let m1 = lo + (hi - lo) / 3;
+        let m2 = hi - (hi - lo) / 3;
+        if cost_fn(m1) < cost_fn(m2) {
+            hi = m2 - 1;
+        } else {
+            // For equal costs (including two infeasible block sizes), the minimum is to the right of m1
+            lo = m1;
+        }
+    }
+    (lo.saturating_sub(3).max(lo_0)..=(hi + 3).min(hi_0))
+        .map(|b| (b, cost_fn(b)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bkz_delta_table() {
+        for block_size in (0..BKZ_DELTA_TABLE_SIZE + 10).step_by(7) {
+            assert_eq!(
+                bkz_delta(block_size).to_bits(),
+                compute_bkz_delta(block_size).to_bits()
+            );
+        }
+    }
+
+    #[test]
+    fn test_bkz_delta() {
+        assert_eq!(bkz_delta(2), 1.02190);
+        assert_eq!(bkz_delta(40), 1.01295);
+        // Reference values from the lattice-estimator
+        assert!((bkz_delta(100) - 1.009259).abs() < 1e-6);
+        assert!((bkz_delta(400) - 1.003982).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_bkz_delta_monotone() {
+        for (block_size, delta) in SMALL_DELTA {
+            assert_eq!(bkz_delta(block_size), delta);
+        }
+        for block_size in 2..200 {
+            assert!(
+                bkz_delta(block_size + 1) < bkz_delta(block_size),
+                "bkz_delta({}) >= bkz_delta({block_size})",
+                block_size + 1
+            );
+        }
+        // Interpolated between the tabulated values
+        assert!(bkz_delta(12) < 1.01616 && bkz_delta(12) > 1.01485);
+        // Continuous at the end of the blend
+        assert_eq!(
+            bkz_delta(BKZ_DELTA_BLEND_END),
+            asymptotic_bkz_delta(BKZ_DELTA_BLEND_END)
+        );
+    }
+
+    #[test]
+    fn test_bkz_cost_increasing() {
+        for est in Estimates::ALL {
+            let costs = (60..500)
+                .map(|b| bkz_cost(est, b, 1024, 14., 7168.))
+                .collect::<Vec<_>>();
+            // Dimensions for free are rounded down, which causes dips of a few thousandths of a bit in the Kyber and MATZOV models
+            for w in costs.windows(2) {
+                assert!(
+                    w[0] <= w[1] + 0.01,
+                    "{est}: cost not increasing in block size"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_list_decoding_cost_modulus() {
+        // The cost model ignoring the modulus, i.e., with one SVP call per block of the full basis
+        let matzov_cost_ignoring_q = |b: usize, d: usize| {
+            let beta_ = (b - dims_for_free(b)) as f64;
+            5.46f64.log2()
+                + 0.29613500308205365 * beta_
+                + 20.387885985467914
+                + ((d - b) as f64).log2()
+        };
+        // Large modulus: the whole basis is reduced
+        for b in [100, 300, 500] {
+            assert_eq!(qary_useful_dim(b, 1024, 30., 512. * 30.), 1024);
+            assert_eq!(
+                matzov_cost(b, 1024, 30., 512. * 30.),
+                matzov_cost_ignoring_q(b, 1024)
+            );
+        }
+        // Small modulus and weak reduction: many leading q-vectors
+        let d_useful = qary_useful_dim(100, 2048, 5., 1024. * 5.);
+        assert!(d_useful < 1200);
+        assert!(matzov_cost(100, 2048, 5., 1024. * 5.) < matzov_cost_ignoring_q(100, 2048) - 0.5);
+        assert!(
+            kyber_cost(100, 2048, 5., 1024. * 5.) < kyber_cost(100, 2048, 30., 1024. * 30.) - 0.5
+        );
+    }
+
+    #[test]
+    fn test_qary_useful_dim_volume() {
+        // The larger the volume, the more leading Gram-Schmidt vectors hit q
+        let useful = [256., 1024., 1792.].map(|h| qary_useful_dim(100, 2048, 5., h * 5.));
+        assert!(useful[0] > useful[1] && useful[1] > useful[2]);
+        // A SIS lattice with volume q^256 has fewer q-vectors than assumed by a volume of q^{d/2}
+        assert!(matzov_cost(100, 2048, 5., 256. * 5.) > matzov_cost(100, 2048, 5., 1024. * 5.));
+        // The other cost models ignore the volume
+        assert_eq!(
+            bkz_cost(Estimates::BDGL16, 100, 2048, 5., 256. * 5.),
+            bkz_cost(Estimates::BDGL16, 100, 2048, 5., 1024. * 5.)
+        );
+    }
+
+    #[test]
+    fn test_core_svp() {
+        assert_eq!(
+            bkz_cost(Estimates::ADPS16, 400, 1024, 14., 7168.),
+            0.292 * 400.
+        );
+    }
+
+    #[test]
+    fn test_progressive_bkz_cost() {
+        for est in [Estimates::BDGL16, Estimates::CheNgu12, Estimates::ChaLoy21] {
+            for b in [300, 400, 500] {
+                assert!(
+                    progressive_bkz_cost(est, b, 1024, 14., 7168.)
+                        < bkz_cost(est, b, 1024, 14., 7168.),
+                    "{est}: progressive cost not below fixed cost for block size {b}"
+                );
+            }
+            for b in [2, 20, PROGRESSIVE_BKZ_START - 1] {
+                assert_eq!(
+                    progressive_bkz_cost(est, b, 1024, 14., 7168.),
+                    bkz_cost(est, b, 1024, 14., 7168.)
+                );
+            }
+        }
+        // A larger step means fewer tours
+        assert!(
+            progressive_bkz_cost_with_schedule(Estimates::BDGL16, 400, 1024, 14., 7168., 60, 10)
+                < progressive_bkz_cost(Estimates::BDGL16, 400, 1024, 14., 7168.)
+        );
+    }
+
+    #[test]
+    fn test_custom() {
+        let custom = Estimates::Custom {
+            a: 0.292,
+            b: 16.4,
+            label: "my-bdgl16",
+        };
+        for b in 90..1000 {
+            assert_eq!(
+                svp_cost(custom, b, 1024, 14.) + svp_repeat(b, 1024),
+                bdgl_sieve(b, 1024)
+            );
+            assert_eq!(
+                bkz_cost(custom, b, 1024, 14., 7168.),
+                bkz_cost(Estimates::BDGL16, b, 1024, 14., 7168.)
+            );
+        }
+        assert_eq!(custom.name(), "my-bdgl16");
+    }
+
+    #[test]
+    fn test_estimates_from_str() {
+        let custom = Estimates::Custom {
+            a: 0.292,
+            b: 16.4,
+            label: "custom",
+        };
+        for est in Estimates::ALL.into_iter().chain([custom]) {
+            assert_eq!(Estimates::from_str(&est.to_string()).unwrap(), est);
+        }
+        assert_eq!(custom.to_string(), "custom:0.292:16.4");
+        assert_eq!(
+            Estimates::from_str("matzov-classical").unwrap(),
+            Estimates::Matzov
+        );
+        assert!(Estimates::from_str("matzov").is_err());
+        assert!(Estimates::from_str("custom:0.292").is_err());
+        assert!(Estimates::from_str("custom:0.292:16.4:1").is_err());
+        assert!(Estimates::from_str("custom:a:b").is_err());
+
+        // The label is not part of the string representation, and not compared
+        let labeled = Estimates::Custom {
+            a: 0.292,
+            b: 16.4,
+            label: "my-bdgl16",
+        };
+        assert_eq!(Estimates::from_str(&labeled.to_string()).unwrap(), labeled);
+        assert_ne!(
+            labeled,
+            Estimates::Custom {
+                a: 0.292,
+                b: 16.5,
+                label: "my-bdgl16"
+            }
+        );
+        assert_ne!(Estimates::Kyber, Estimates::Matzov);
+    }
+
+    #[test]
+    fn test_bkz_memory_cost() {
+        assert_eq!(bkz_memory_cost(Estimates::BDGL16, 400), 0.2075 * 400.);
+        for est in Estimates::ALL {
+            // Dimensions for free only kick in at block size 40
+            let costs = (40..1000)
+                .map(|b| bkz_memory_cost(est, b))
+                .collect::<Vec<_>>();
+            for w in costs.windows(2) {
+                assert!(w[0] <= w[1], "{est}: memory not increasing in block size");
+            }
+        }
+        // Enumeration requires far less memory than sieving
+        assert!(bkz_memory_cost(Estimates::CheNgu12, 400) < 20.);
+    }
+
+    #[test]
+    fn test_find_optimal_block_size() {
+        // Infeasible below 137, increasing afterwards
+        let cost = |b: usize| {
+            if b < 137 {
+                f64::INFINITY
+            } else {
+                0.292 * b as f64
+            }
+        };
+        assert_eq!(find_optimal_block_size(cost, 2, 1000), (137, 0.292 * 137.));
+        assert_eq!(find_optimal_block_size(cost, 137, 137), (137, 0.292 * 137.));
+
+        // Convex
+        let cost = |b: usize| (b as f64 - 421.).powi(2);
+        assert_eq!(find_optimal_block_size(cost, 2, 1000), (421, 0.));
+        assert_eq!(find_optimal_block_size(cost, 2, 100), (100, 321. * 321.));
+
+        // Infeasible everywhere
+        let (_, c) = find_optimal_block_size(|_| f64::INFINITY, 2, 1000);
+        assert!(c.is_infinite());
+    }
+
+    #[test]
+    fn test_cost_cache() {
+        let cache = CostCache::new();
+        let custom = Estimates::Custom {
+            a: 0.3,
+            b: 10.,
+            label: "custom",
+        };
+        for _ in 0..2 {
+            for est in Estimates::ALL.into_iter().chain([custom]) {
+                for schedule in [BkzSchedule::Fixed, BkzSchedule::PROGRESSIVE] {
+                    for (block_size, d, log_q, log2_volume) in [
+                        (100, 500, 12., 3000.),
+                        (400, 1000, 23.5, 11750.),
+                        (40, 80, 60., 600.),
+                    ] {
+                        assert_eq!(
+                            cache
+                                .cost(schedule, est, block_size, d, log_q, log2_volume)
+                                .to_bits(),
+                            schedule
+                                .cost(est, block_size, d, log_q, log2_volume)
+                                .to_bits()
+                        );
+                    }
+                }
+            }
+        }
+        // The second round only hits the cache
+        assert_eq!(cache.len(), 8 * 2 * 3);
+        assert_eq!(cache.hits(), 8 * 2 * 3);
+        assert_eq!(
+            cache.bkz_cost(Estimates::BDGL16, 100, 500, 12., 3000.),
+            bkz_cost(Estimates::BDGL16, 100, 500, 12., 3000.)
+        );
+        assert_eq!(cache.len(), 8 * 2 * 3);
+        // Only the Kyber and MATZOV costs are cached per volume
+        cache.bkz_cost(Estimates::BDGL16, 100, 500, 12., 1000.);
+        assert_eq!(cache.len(), 8 * 2 * 3);
+        cache.bkz_cost(Estimates::Matzov, 100, 500, 12., 1000.);
+        assert_eq!(cache.len(), 8 * 2 * 3 + 1);
+        assert_eq!(cache.hits(), 8 * 2 * 3 + 2);
+    }
+}
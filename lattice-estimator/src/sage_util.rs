@@ -55,6 +55,7 @@ where
                 sys.path.insert(0, '{}');\
                 from sis import *;\
                 from msis import *;\
+                from fixtures import *;\
                 print({})",
             root.join("lattice-estimator")
                 .to_str()
@@ -0,0 +1,15 @@
+//! (De)serialization of moduli as decimal strings, which keeps the serialized instances readable and independent of the size of the modulus.
+//! Use with `#[serde(with = "crate::serde_biguint")]`.
+use std::str::FromStr;
+
+use num_bigint::BigUint;
+use serde::{de, Deserialize, Deserializer, Serializer};
+
+pub(crate) fn serialize<S: Serializer>(q: &BigUint, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&q.to_string())
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BigUint, D::Error> {
+    let q = String::deserialize(deserializer)?;
+    BigUint::from_str(&q).map_err(de::Error::custom)
+}
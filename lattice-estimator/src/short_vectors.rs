@@ -0,0 +1,285 @@
+//! The cost of producing many short vectors with lattice sieving, as used by dual attacks, following `short_vectors` of the `Kyber` and `MATZOV` cost models in the [lattice-estimator](https://github.com/malb/lattice-estimator).
+use std::fmt;
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::EstimatorError;
+use crate::reduction::{dims_for_free, kyber_cost, log2_add, matzov_cost, svp_cost, Estimates};
+
+/// Ratio between the length of the vectors output by a sieve and the Gaussian heuristic of the sieving dimension, $\sqrt{4/3}$.
+const SIEVE_RHO: f64 = 1.1547005383792515;
+
+/// The cost of producing short vectors with BKZ preprocessing followed by (repeated) sieving.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ShortVectorsEstimate {
+    /// Ratio between the length of the vectors produced and the length of the shortest vector predicted by BKZ with the block size.
+    pub scaling_factor_rho: f64,
+    /// $\log\_2$ of the cost of producing the vectors.
+    pub log2_cost: f64,
+    /// $\log\_2$ of the number of vectors produced, which is at least the number of vectors requested.
+    /// Unlike the `num_vectors` entry of the deprecated tuples (see [`kyber_short_vectors_tuple`]), this is kept in the log domain, since a single sieve database in cryptographic dimensions already has more than $2^{64}$ vectors.
+    pub log2_num_vectors: f64,
+    /// Dimension of the final sieve (after dimensions for free).
+    pub sieve_dim: usize,
+}
+
+impl Display for ShortVectorsEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "2^{:.2} vectors of length rho = {:.4} from a sieve in dimension {}, log2(cost) = {:.2}",
+            self.log2_num_vectors, self.scaling_factor_rho, self.sieve_dim, self.log2_cost
+        )
+    }
+}
+
+/// Return the sieving dimension (after dimensions for free), together with $\log\_2$ of the number of sieve calls needed to produce $2^\texttt{log2\\_num\\_vectors}$ vectors (by default, a single sieve database, i.e., $2^{0.2075 \beta'}$ vectors) and of the number of vectors produced.
+/// Returns [`EstimatorError::InvalidParameters`] if the number of vectors requested is not a finite number at least 1, or exceeds the $q^d$ points of the lattice modulo $q$.
+fn sieve_repetitions(
+    block_size: usize,
+    d: usize,
+    log_q: f64,
+    log2_num_vectors: Option<f64>,
+    sieve_dim: Option<usize>,
+) -> Result<(usize, f64, f64), EstimatorError> {
+    if block_size < 2 || block_size > d {
+        return Err(EstimatorError::UnsupportedBlockSize(block_size));
+    }
+    let sieve_dim = sieve_dim.unwrap_or(block_size);
+    if sieve_dim < 2 || sieve_dim > d {
+        return Err(EstimatorError::UnsupportedBlockSize(sieve_dim));
+    }
+    let sieve_dim = sieve_dim - dims_for_free(sieve_dim);
+    let log2_database_size = 0.2075 * sieve_dim as f64;
+    let log2_num_vectors = log2_num_vectors.unwrap_or(log2_database_size);
+    if !log2_num_vectors.is_finite() || log2_num_vectors < 0. || log2_num_vectors > d as f64 * log_q
+    {
+        return Err(EstimatorError::InvalidParameters(format!(
+            "cannot produce 2^{log2_num_vectors} vectors in a {d}-dimensional lattice with modulus 2^{log_q}"
+        )));
+    }
+    let log2_repetitions = (log2_num_vectors - log2_database_size).max(0.);
+    Ok((
+        sieve_dim,
+        log2_repetitions,
+        log2_repetitions + log2_database_size,
+    ))
+}
+
+/// A single vector ($\texttt{log2\\_num\\_vectors} = 0$) is the first vector of the BKZ-reduced basis.
+fn single_vector(
+    est: Estimates,
+    block_size: usize,
+    d: usize,
+    log_q: f64,
+    log2_volume: f64,
+) -> Result<ShortVectorsEstimate, EstimatorError> {
+    if block_size < 2 || block_size > d {
+        return Err(EstimatorError::UnsupportedBlockSize(block_size));
+    }
+    let log2_cost = match est {
+        Estimates::Kyber => kyber_cost(block_size, d, log_q, log2_volume),
+        _ => matzov_cost(block_size, d, log_q, log2_volume),
+    };
+    Ok(ShortVectorsEstimate {
+        scaling_factor_rho: 1.,
+        log2_cost,
+        log2_num_vectors: 0.,
+        sieve_dim: block_size,
+    })
+}
+
+/// Return the cost of producing $2^\texttt{log2\\_num\\_vectors}$ short vectors (by default, one sieve database) in a `d`-dimensional q-ary lattice with modulus $2^\texttt{log\\_q}$ and volume $2^\texttt{log2\\_volume}$ under the Kyber round 3 cost model.
+/// A single vector is the output of BKZ with block size `block_size`; otherwise, each batch of $2^{0.2075 \beta'}$ vectors, with $\beta'$ the sieving dimension `sieve_dim` (by default, `block_size`) after dimensions for free, requires a run of BKZ followed by a sieve, and the vectors are $\sqrt{4/3}$ times longer.
+pub fn kyber_short_vectors(
+    block_size: usize,
+    d: usize,
+    log_q: f64,
+    log2_volume: f64,
+    log2_num_vectors: Option<f64>,
+    sieve_dim: Option<usize>,
+) -> Result<ShortVectorsEstimate, EstimatorError> {
+    if log2_num_vectors == Some(0.) && sieve_dim.is_none() {
+        return single_vector(Estimates::Kyber, block_size, d, log_q, log2_volume);
+    }
+    let (sieve_dim, log2_repetitions, log2_num_vectors) =
+        sieve_repetitions(block_size, d, log_q, log2_num_vectors, sieve_dim)?;
+    Ok(ShortVectorsEstimate {
+        scaling_factor_rho: SIEVE_RHO,
+        log2_cost: log2_repetitions + kyber_cost(block_size, d, log_q, log2_volume),
+        log2_num_vectors,
+        sieve_dim,
+    })
+}
+
+/// Return the cost of producing $2^\texttt{log2\\_num\\_vectors}$ short vectors (by default, one sieve database) in a `d`-dimensional q-ary lattice with modulus $2^\texttt{log\\_q}$ and volume $2^\texttt{log2\\_volume}$ under the MATZOV cost model.
+/// Unlike [`kyber_short_vectors`], the BKZ preprocessing with block size `block_size` is only run once, followed by as many sieves in dimension `sieve_dim` (by default, `block_size`) as needed to produce the vectors.
+pub fn matzov_short_vectors(
+    block_size: usize,
+    d: usize,
+    log_q: f64,
+    log2_volume: f64,
+    log2_num_vectors: Option<f64>,
+    sieve_dim: Option<usize>,
+) -> Result<ShortVectorsEstimate, EstimatorError> {
+    if log2_num_vectors == Some(0.) && sieve_dim.is_none() {
+        return single_vector(Estimates::Matzov, block_size, d, log_q, log2_volume);
+    }
+    let full_sieve_dim = sieve_dim.unwrap_or(block_size);
+    let (sieve_dim, log2_repetitions, log2_num_vectors) =
+        sieve_repetitions(block_size, d, log_q, log2_num_vectors, sieve_dim)?;
+    let sieve = log2_repetitions + svp_cost(Estimates::Matzov, full_sieve_dim, d, log_q);
+    Ok(ShortVectorsEstimate {
+        scaling_factor_rho: SIEVE_RHO,
+        log2_cost: log2_add(matzov_cost(block_size, d, log_q, log2_volume), sieve),
+        log2_num_vectors,
+        sieve_dim,
+    })
+}
+
+/// Convert a short-vector estimate to the tuple `(scaling_factor_rho, log2_cost, num_vectors, sieve_dim)` returned by the deprecated wrappers, saturating the number of vectors at `usize::MAX`.
+/// Errors are flagged by an infinite cost and `num_vectors = sieve_dim = usize::MAX`.
+fn to_tuple(estimate: Result<ShortVectorsEstimate, EstimatorError>) -> (f64, f64, usize, usize) {
+    estimate.map_or((SIEVE_RHO, f64::INFINITY, usize::MAX, usize::MAX), |e| {
+        (
+            e.scaling_factor_rho,
+            e.log2_cost,
+            e.log2_num_vectors.exp2() as usize,
+            e.sieve_dim,
+        )
+    })
+}
+
+/// Same as [`kyber_short_vectors`], returning a tuple `(scaling_factor_rho, log2_cost, num_vectors, sieve_dim)`; kept for one release.
+#[deprecated(note = "use `kyber_short_vectors`, which returns a `ShortVectorsEstimate`")]
+pub fn kyber_short_vectors_tuple(
+    block_size: usize,
+    d: usize,
+    log_q: f64,
+    log2_volume: f64,
+    log2_num_vectors: Option<f64>,
+    sieve_dim: Option<usize>,
+) -> (f64, f64, usize, usize) {
+    to_tuple(kyber_short_vectors(
+        block_size,
+        d,
+        log_q,
+        log2_volume,
+        log2_num_vectors,
+        sieve_dim,
+    ))
+}
+
+/// Same as [`matzov_short_vectors`], returning a tuple `(scaling_factor_rho, log2_cost, num_vectors, sieve_dim)`; kept for one release.
+#[deprecated(note = "use `matzov_short_vectors`, which returns a `ShortVectorsEstimate`")]
+pub fn matzov_short_vectors_tuple(
+    block_size: usize,
+    d: usize,
+    log_q: f64,
+    log2_volume: f64,
+    log2_num_vectors: Option<f64>,
+    sieve_dim: Option<usize>,
+) -> (f64, f64, usize, usize) {
+    to_tuple(matzov_short_vectors(
+        block_size,
+        d,
+        log_q,
+        log2_volume,
+        log2_num_vectors,
+        sieve_dim,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_short_vectors_single() {
+        for short_vectors in [kyber_short_vectors, matzov_short_vectors] {
+            let estimate = short_vectors(400, 1000, 12., 6000., Some(0.), None).unwrap();
+            assert_eq!(estimate.log2_num_vectors, 0.);
+            assert_eq!(estimate.scaling_factor_rho, 1.);
+            assert_eq!(estimate.sieve_dim, 400);
+        }
+    }
+
+    #[test]
+    fn test_short_vectors_default() {
+        for short_vectors in [kyber_short_vectors, matzov_short_vectors] {
+            let estimate = short_vectors(400, 1000, 12., 6000., None, None).unwrap();
+            let sieve_dim = 400 - dims_for_free(400);
+            assert_eq!(estimate.sieve_dim, sieve_dim);
+            assert_eq!(estimate.log2_num_vectors, 0.2075 * sieve_dim as f64);
+            assert_eq!(estimate.scaling_factor_rho, SIEVE_RHO);
+            println!("{estimate}");
+        }
+    }
+
+    #[test]
+    fn test_short_vectors_scaling() {
+        // Twice as many vectors as a sieve database requires two sieves
+        let one = kyber_short_vectors(400, 1000, 12., 6000., None, None).unwrap();
+        let two = kyber_short_vectors(400, 1000, 12., 6000., Some(one.log2_num_vectors + 1.), None)
+            .unwrap();
+        assert_eq!(two.log2_num_vectors, one.log2_num_vectors + 1.);
+        assert!((two.log2_cost - one.log2_cost - 1.).abs() < 1e-12);
+
+        // MATZOV only preprocesses once, so more vectors are cheaper than for Kyber
+        let kyber = kyber_short_vectors(400, 1000, 12., 6000., Some(100.), None).unwrap();
+        let matzov = matzov_short_vectors(400, 1000, 12., 6000., Some(100.), None).unwrap();
+        assert!(matzov.log2_cost < kyber.log2_cost);
+    }
+
+    #[test]
+    fn test_short_vectors_sieve_dim() {
+        let small = matzov_short_vectors(400, 1000, 12., 6000., Some(80.), Some(300)).unwrap();
+        let large = matzov_short_vectors(400, 1000, 12., 6000., Some(80.), Some(450)).unwrap();
+        assert!(small.sieve_dim < large.sieve_dim);
+        assert_eq!(small.log2_num_vectors, 80.);
+        assert!(small.log2_cost < large.log2_cost);
+    }
+
+    #[test]
+    fn test_short_vectors_errors() {
+        // More vectors than there are lattice points modulo q
+        assert!(matches!(
+            kyber_short_vectors(400, 1000, 12., 6000., Some(20000.), None),
+            Err(EstimatorError::InvalidParameters(_))
+        ));
+        for log2_num_vectors in [-1., f64::INFINITY, f64::NAN] {
+            assert!(matches!(
+                matzov_short_vectors(400, 1000, 12., 6000., Some(log2_num_vectors), None),
+                Err(EstimatorError::InvalidParameters(_))
+            ));
+        }
+        assert_eq!(
+            matzov_short_vectors(1, 1000, 12., 6000., None, None),
+            Err(EstimatorError::UnsupportedBlockSize(1))
+        );
+        assert_eq!(
+            kyber_short_vectors(400, 1000, 12., 6000., None, Some(2000)),
+            Err(EstimatorError::UnsupportedBlockSize(2000))
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_short_vectors_tuple() {
+        let estimate = matzov_short_vectors(400, 1000, 12., 6000., Some(20.), None).unwrap();
+        assert_eq!(
+            matzov_short_vectors_tuple(400, 1000, 12., 6000., Some(20.), None),
+            (
+                estimate.scaling_factor_rho,
+                estimate.log2_cost,
+                estimate.log2_num_vectors.exp2() as usize,
+                estimate.sieve_dim
+            )
+        );
+        let (_, log2_cost, num_vectors, sieve_dim) =
+            kyber_short_vectors_tuple(400, 1000, 12., 6000., Some(20000.), None);
+        assert_eq!(log2_cost, f64::INFINITY);
+        assert_eq!((num_vectors, sieve_dim), (usize::MAX, usize::MAX));
+    }
+}
@@ -0,0 +1,468 @@
+//! Simulators for the Gram-Schmidt profile of BKZ-reduced bases.
+//! Profiles are returned in the log domain, as the $\log\_2$ of the squared Gram-Schmidt norms $\lVert b\_i^\* \rVert^2$, since the norms themselves overflow `f64` for large moduli and dimensions.
+use std::f64::consts::PI;
+
+use rand::Rng;
+
+use crate::errors::EstimatorError;
+use crate::reduction::bkz_delta;
+
+/// Average $\log\_2 \lVert b\_i^\* \rVert$ of HKZ-reduced bases of 45-dimensional random lattices with unit volume [CN11], used by [`cn11_simulator`] for block sizes up to 45 and for the last block.
+const CN11_RK: [f64; 45] = [
+    0.789527997160000,
+    0.780003183804613,
+    0.750872218594458,
+    0.706520454592593,
+    0.696345241018901,
+    0.660533841808400,
+    0.626274718790505,
+    0.581480717333169,
+    0.553171463433503,
+    0.520811087419712,
+    0.487994338534253,
+    0.459541470573431,
+    0.414638319529319,
+    0.392811729940846,
+    0.339090376264829,
+    0.306561491936042,
+    0.276041187709516,
+    0.236698863270441,
+    0.196186341673080,
+    0.161214212092249,
+    0.110895134828114,
+    0.0678261623920553,
+    0.0272807162335610,
+    -0.0234609979600137,
+    -0.0320527224746912,
+    -0.0940331032784437,
+    -0.129109087817554,
+    -0.176965384290173,
+    -0.209405754915959,
+    -0.265867993276493,
+    -0.299031324494802,
+    -0.349338597048432,
+    -0.380428160303508,
+    -0.427399405474537,
+    -0.474944677694975,
+    -0.530140672818150,
+    -0.561625221138784,
+    -0.612008793872032,
+    -0.669011014635905,
+    -0.713766731570930,
+    -0.754041787011810,
+    -0.808609696192079,
+    -0.859933249032210,
+    -0.884479963601658,
+    -0.886666930030433,
+];
+
+/// Return $\log\_2 \lVert b\_i^\* \rVert^2$ for the `i`-th Gram-Schmidt vector of a BKZ-`block_size`-reduced basis of a `d`-dimensional lattice with volume $2^\texttt{log2\\_volume}$ under the geometric series assumption, i.e., $\lVert b\_i^\* \rVert = \delta^{d - 1 - 2i} \mathrm{vol}^{1/d}$.
+pub fn gsa_log2_sq_norm(d: usize, log2_volume: f64, block_size: usize, i: usize) -> f64 {
+    2. * ((d as f64 - 1. - 2. * i as f64) * bkz_delta(block_size).log2() + log2_volume / d as f64)
+}
+
+/// Return an error if the simulators do not support the arguments, i.e., unless $2 \leq \beta$, $1 \leq n \leq d$ and $\log\_2 q > 0$.
+fn check_simulator_args(
+    d: usize,
+    n: usize,
+    log_q: f64,
+    block_size: usize,
+) -> Result<(), EstimatorError> {
+    if block_size < 2 {
+        return Err(EstimatorError::UnsupportedBlockSize(block_size));
+    }
+    if n == 0 || n > d {
+        return Err(EstimatorError::InvalidParameters(format!(
+            "the rank of the q-ary part n = {n} must be in [1, d = {d}]"
+        )));
+    }
+    if !(log_q.is_finite() && log_q > 0.) {
+        return Err(EstimatorError::InvalidParameters(format!(
+            "log2(q) = {log_q} must be positive"
+        )));
+    }
+    Ok(())
+}
+
+/// Simulate the profile of a BKZ-`block_size`-reduced basis of a `d`-dimensional lattice with volume $q^n$ under the geometric series assumption.
+pub fn gsa_simulator(
+    d: usize,
+    n: usize,
+    log_q: f64,
+    block_size: usize,
+) -> Result<Vec<f64>, EstimatorError> {
+    check_simulator_args(d, n, log_q, block_size)?;
+    let log2_volume = n as f64 * log_q;
+    Ok((0..d)
+        .map(|i| gsa_log2_sq_norm(d, log2_volume, block_size, i))
+        .collect())
+}
+
+/// Simulate the profile of a BKZ-`block_size`-reduced basis of a `d`-dimensional q-ary lattice with volume $q^n$ under the Z-shape geometric series assumption: the Gram-Schmidt norms follow the GSA, but are capped at $q$ (for the leading q-vectors, which lattice reduction does not improve on) and at $1$ (for the trailing unit vectors).
+/// Returns [`EstimatorError::NonConvergent`] if no shift of the capped profile preserves the volume.
+pub fn zgsa_simulator(
+    d: usize,
+    n: usize,
+    log_q: f64,
+    block_size: usize,
+) -> Result<Vec<f64>, EstimatorError> {
+    let profile = gsa_simulator(d, n, log_q, block_size)?;
+    let (max, min) = (2. * log_q, 0.);
+    if profile.first().is_none_or(|r| *r <= max) && profile.last().is_none_or(|r| *r >= min) {
+        return Ok(profile);
+    }
+
+    // Shift the capped GSA slope such that the volume is preserved; the volume is non-decreasing in the shift
+    let slope = 4. * bkz_delta(block_size).log2();
+    let capped = |shift: f64| {
+        (0..d)
+            .map(move |i| (shift - slope * i as f64).clamp(min, max))
+            .collect::<Vec<_>>()
+    };
+    let log2_sq_volume = 2. * n as f64 * log_q;
+    let (mut lo, mut hi) = (min, max + slope * d as f64);
+    for _ in 0..100 {
+        let mid = (lo + hi) / 2.;
+        if capped(mid).iter().sum::<f64>() < log2_sq_volume {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    let profile = capped((lo + hi) / 2.);
+    let error = profile.iter().sum::<f64>() - log2_sq_volume;
+    if error.abs() > 1e-6 * log2_sq_volume.max(1.) {
+        return Err(EstimatorError::NonConvergent(format!(
+            "the Z-shape profile has volume 2^{error} times the lattice volume"
+        )));
+    }
+    Ok(profile)
+}
+
+/// Return an error unless `profile` is non-empty with finite entries and $\beta \geq 2$.
+fn check_profile(profile: &[f64], block_size: usize) -> Result<(), EstimatorError> {
+    if block_size < 2 {
+        return Err(EstimatorError::UnsupportedBlockSize(block_size));
+    }
+    if profile.is_empty() || !profile.iter().all(|r| r.is_finite()) {
+        return Err(EstimatorError::InvalidParameters(
+            "the profile must be non-empty with finite entries".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Return $\log\_2$ of the expected norm of the first vector of an HKZ-reduced basis of a `block_size`-dimensional lattice with unit volume: from [`CN11_RK`] up to dimension 45, and from the Gaussian heuristic above.
+fn cn11_first_norm(block_size: usize) -> f64 {
+    if block_size <= CN11_RK.len() {
+        let rk = &CN11_RK[CN11_RK.len() - block_size..];
+        rk[0] - rk.iter().sum::<f64>() / block_size as f64
+    } else {
+        let beta = block_size as f64;
+        ((beta / 2. + 1.).ln_gamma().0 / beta - PI.sqrt().ln()) / 2f64.ln()
+    }
+}
+
+/// Simulate at most `tours` tours of BKZ-`block_size` on `profile` following [CN11]: the first vector of each block is replaced by the expected norm of the first vector of an HKZ-reduced basis of the block (if shorter), offset by `noise(beta)` (in $\log\_2$ of the norm) for a block of dimension $\beta$, and the last block is HKZ-reduced.
+/// The simulation stops early once a tour leaves the profile unchanged.
+fn simulate_tours(
+    profile: &[f64],
+    block_size: usize,
+    tours: usize,
+    mut noise: impl FnMut(usize) -> f64,
+) -> Vec<f64> {
+    let d = profile.len();
+    // The simulation works with the log2 of the Gram-Schmidt norms, i.e., half the profile
+    let mut r1: Vec<f64> = profile.iter().map(|r| r / 2.).collect();
+    let mut r2 = r1.clone();
+    let last = block_size.min(CN11_RK.len()).min(d);
+    let mut changed = vec![true; d];
+    for _ in 0..tours {
+        let mut changed_next = vec![false; d];
+        let mut prefix = vec![0.; d + 1];
+        for i in 0..d {
+            prefix[i + 1] = prefix[i] + r1[i];
+        }
+        let mut log2_head_volume = 0.;
+        for k in 0..d - last {
+            let beta = block_size.min(d - k);
+            if changed[k..k + beta].iter().any(|&c| c) {
+                let log2_volume = prefix[k + beta] - log2_head_volume;
+                let x = log2_volume / beta as f64 + cn11_first_norm(beta) + noise(beta);
+                if x < r1[k] {
+                    r2[k] = x;
+                    changed_next[k] = true;
+                }
+            }
+            log2_head_volume += r2[k];
+        }
+
+        // HKZ-reduce the last block, preserving the volume
+        let log2_volume = prefix[d] - log2_head_volume;
+        let rk = &CN11_RK[CN11_RK.len() - last..];
+        let rk_mean = rk.iter().sum::<f64>() / last as f64;
+        for (k, r) in (d - last..d).zip(rk) {
+            r2[k] = log2_volume / last as f64 + r - rk_mean;
+            changed_next[k] = true;
+        }
+
+        if r1 == r2 {
+            break;
+        }
+        r1.clone_from(&r2);
+        changed = changed_next;
+    }
+    r1.iter().map(|r| 2. * r).collect()
+}
+
+/// Simulate the profile obtained by running (at most) `tours` tours of BKZ-`block_size` on a basis with profile `profile` using the [CN11] simulator.
+/// As for [CN11], the input basis should be (at least) LLL-reduced, e.g., follow [`gsa_simulator`] with block size 2: the simulator only ever shortens the first vector of each block, so it does not redistribute the volume of unreduced (e.g., q-ary) bases.
+pub fn cn11_simulator(
+    profile: &[f64],
+    block_size: usize,
+    tours: usize,
+) -> Result<Vec<f64>, EstimatorError> {
+    check_profile(profile, block_size)?;
+    Ok(simulate_tours(profile, block_size, tours, |_| 0.))
+}
+
+/// Sample a profile obtained by running `tours` tours of BKZ-`block_size` on a basis with profile `profile`, using the probabilistic variant of the [CN11] simulator of [BSW18].
+/// In each tour, $\log\_2$ of the norm of the first vector found in each block of dimension $\beta$ deviates from its expectation by Gaussian noise with standard deviation $\pi / (\sqrt{6} \beta \ln 2)$, i.e., that of $\log\_2 \lambda\_1$ for a random lattice (the volume of the ball of radius $\lambda\_1$ being exponentially distributed).
+pub fn probabilistic_simulator<R: Rng + ?Sized>(
+    profile: &[f64],
+    block_size: usize,
+    tours: usize,
+    rng: &mut R,
+) -> Result<Vec<f64>, EstimatorError> {
+    check_profile(profile, block_size)?;
+    Ok(simulate_tours(profile, block_size, tours, |beta| {
+        // Box-Muller transform
+        let (u1, u2): (f64, f64) = (1. - rng.random::<f64>(), rng.random());
+        let z = (-2. * u1.ln()).sqrt() * (2. * PI * u2).cos();
+        z * PI / (6f64.sqrt() * beta as f64 * 2f64.ln())
+    }))
+}
+
+/// Run [`probabilistic_simulator`] `n_samples` times and return, for each of `quantiles` (in $[0, 1]$), the profile of that quantile at each index (interpolating linearly between samples).
+pub fn simulate_quantiles<R: Rng + ?Sized>(
+    profile: &[f64],
+    block_size: usize,
+    tours: usize,
+    rng: &mut R,
+    n_samples: usize,
+    quantiles: &[f64],
+) -> Result<Vec<Vec<f64>>, EstimatorError> {
+    if n_samples == 0 {
+        return Err(EstimatorError::InvalidParameters(
+            "the number of samples must be positive".to_string(),
+        ));
+    }
+    if let Some(quantile) = quantiles.iter().find(|p| !(0. ..=1.).contains(*p)) {
+        return Err(EstimatorError::InvalidParameters(format!(
+            "quantile {quantile} is not in [0, 1]"
+        )));
+    }
+    let samples = (0..n_samples)
+        .map(|_| probabilistic_simulator(profile, block_size, tours, rng))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut columns: Vec<Vec<f64>> = (0..profile.len())
+        .map(|i| samples.iter().map(|sample| sample[i]).collect())
+        .collect();
+    for column in &mut columns {
+        column.sort_by(f64::total_cmp);
+    }
+    Ok(quantiles
+        .iter()
+        .map(|p| {
+            columns
+                .iter()
+                .map(|column| {
+                    let pos = p * (n_samples - 1) as f64;
+                    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+                    column[lo] + (pos - lo as f64) * (column[hi] - column[lo])
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Convert a profile from the log domain to squared Gram-Schmidt norms, or return an error if they do not fit in an `f64`.
+pub fn to_linear(profile: &[f64]) -> Result<Vec<f64>, EstimatorError> {
+    profile
+        .iter()
+        .enumerate()
+        .map(|(i, r)| {
+            let r_linear = r.exp2();
+            if r_linear.is_finite() {
+                Ok(r_linear)
+            } else {
+                Err(EstimatorError::InvalidParameters(format!(
+                    "squared Gram-Schmidt norm 2^{r} at index {i} overflows"
+                )))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    fn assert_volume(profile: &[f64], n: usize, log_q: f64) {
+        let log2_sq_volume = profile.iter().sum::<f64>();
+        assert!(
+            (log2_sq_volume - 2. * n as f64 * log_q).abs() < 1e-6,
+            "profile has volume 2^{log2_sq_volume}, expected 2^{}",
+            2. * n as f64 * log_q
+        );
+    }
+
+    #[test]
+    fn test_gsa_simulator() {
+        let profile = gsa_simulator(100, 50, 10., 20).unwrap();
+        assert_eq!(profile.len(), 100);
+        assert_volume(&profile, 50, 10.);
+        for w in profile.windows(2) {
+            assert!(w[0] > w[1]);
+        }
+        let linear = to_linear(&profile).unwrap();
+        assert!((linear.iter().map(|r| r.log2()).sum::<f64>() - 1000.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_zgsa_simulator() {
+        // Weak reduction on a large q-ary lattice: the profile hits both q and 1
+        let profile = zgsa_simulator(1000, 500, 10., 10).unwrap();
+        assert_volume(&profile, 500, 10.);
+        assert_eq!(profile[0], 20.);
+        assert_eq!(profile[999], 0.);
+        for w in profile.windows(2) {
+            assert!(w[0] >= w[1]);
+        }
+
+        // Strong reduction: the profile is the GSA
+        assert_eq!(
+            zgsa_simulator(200, 100, 20., 150).unwrap(),
+            gsa_simulator(200, 100, 20., 150).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_large_modulus() {
+        let (d, n, log_q) = (4096, 2048, 60.);
+        for block_size in [2, 50, 500] {
+            for profile in [
+                gsa_simulator(d, n, log_q, block_size).unwrap(),
+                zgsa_simulator(d, n, log_q, block_size).unwrap(),
+            ] {
+                assert!(profile.iter().all(|r| r.is_finite()));
+                assert_volume(&profile, n, log_q);
+                assert!(to_linear(&profile).is_ok());
+            }
+        }
+        assert!(to_linear(&[10., 2000.]).is_err());
+    }
+
+    /// Profile of an LLL-reduced basis of a `d`-dimensional lattice with volume $q^n$.
+    fn lll_profile(d: usize, n: usize, log_q: f64) -> Vec<f64> {
+        gsa_simulator(d, n, log_q, 2).unwrap()
+    }
+
+    #[test]
+    fn test_cn11_simulator() {
+        let input = lll_profile(120, 60, 10.);
+        let profile = cn11_simulator(&input, 40, 20).unwrap();
+        assert_volume(&profile, 60, 10.);
+        assert!(profile[0] < input[0]);
+        // After enough tours, the head of the profile is close to the GSA (the tail is HKZ-shaped)
+        let gsa = gsa_simulator(120, 60, 10., 40).unwrap();
+        for i in 0..60 {
+            assert!((profile[i] - gsa[i]).abs() < 1., "index {i}");
+        }
+        // More tours only improve the first vector
+        assert!(cn11_simulator(&input, 40, 40).unwrap()[0] <= profile[0]);
+    }
+
+    #[test]
+    fn test_probabilistic_simulator() {
+        let input = lll_profile(120, 60, 10.);
+        let cn11 = cn11_simulator(&input, 40, 20).unwrap();
+        let n_samples = 200;
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut mean = vec![0.; input.len()];
+        for _ in 0..n_samples {
+            let sample = probabilistic_simulator(&input, 40, 20, &mut rng).unwrap();
+            for (m, r) in mean.iter_mut().zip(sample) {
+                *m += r / n_samples as f64;
+            }
+        }
+        let max_deviation = mean
+            .iter()
+            .zip(&cn11)
+            .map(|(m, r)| (m - r).abs())
+            .fold(0., f64::max);
+        assert!(max_deviation < 0.5, "max deviation {max_deviation}");
+    }
+
+    #[test]
+    fn test_probabilistic_simulator_seeded() {
+        let input = lll_profile(100, 50, 10.);
+        let sample =
+            |seed| probabilistic_simulator(&input, 30, 10, &mut StdRng::seed_from_u64(seed));
+        assert_eq!(sample(1).unwrap(), sample(1).unwrap());
+        assert_ne!(sample(1).unwrap(), sample(2).unwrap());
+
+        let quantiles = |seed| {
+            simulate_quantiles(
+                &input,
+                30,
+                10,
+                &mut StdRng::seed_from_u64(seed),
+                50,
+                &[0.1, 0.5, 0.9],
+            )
+            .unwrap()
+        };
+        let q = quantiles(3);
+        assert_eq!(q, quantiles(3));
+        assert_eq!(q.len(), 3);
+        for i in 0..input.len() {
+            assert!(q[0][i] <= q[1][i] && q[1][i] <= q[2][i]);
+        }
+        assert!(matches!(
+            simulate_quantiles(&input, 30, 10, &mut StdRng::seed_from_u64(0), 10, &[1.5]),
+            Err(EstimatorError::InvalidParameters(_))
+        ));
+        assert_eq!(
+            cn11_simulator(&input, 1, 10),
+            Err(EstimatorError::UnsupportedBlockSize(1))
+        );
+    }
+
+    #[test]
+    fn test_simulator_errors() {
+        // Small block sizes use the tabulated root Hermite factors
+        assert!(zgsa_simulator(100, 50, 10., 3).is_ok());
+        for block_size in [0, 1] {
+            assert_eq!(
+                zgsa_simulator(100, 50, 10., block_size),
+                Err(EstimatorError::UnsupportedBlockSize(block_size))
+            );
+        }
+        assert!(matches!(
+            gsa_simulator(100, 101, 10., 20),
+            Err(EstimatorError::InvalidParameters(_))
+        ));
+        assert!(matches!(
+            zgsa_simulator(100, 50, -1., 20),
+            Err(EstimatorError::InvalidParameters(_))
+        ));
+        assert!(matches!(
+            to_linear(&[2000.]),
+            Err(EstimatorError::InvalidParameters(_))
+        ));
+    }
+}
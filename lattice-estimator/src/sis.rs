@@ -5,17 +5,32 @@ use std::str::FromStr;
 
 use num_bigint::BigUint;
 use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
 
-use crate::errors::LatticeEstimatorError;
+use crate::attack::{AttackEstimate, AttackKind, CostConstraint};
+use crate::combinatorial::{combinatorial_sis_attack, COMBINATORIAL_MAX_LENGTH_BOUND};
+use crate::errors::{EstimatorError, LatticeEstimatorError};
+use crate::geometry::expected_bkz_first_length;
 use crate::norms::Norm;
+use crate::param_search::{find_min_param, is_secure};
+use crate::reduction::{
+    bkz_memory_cost, find_optimal_block_size, BkzSchedule, CostCache, Estimates,
+};
 use crate::sage_util::sagemath_eval;
 
+/// SIS parameters for instances $A \in \mathbb{Z}\_\texttt{q}^{\texttt{h}\times\texttt{w}}$, asking for a non-zero solution of $A x = 0 \bmod q$ with $\|x\| \leq$ `length_bound` in the norm `norm`.
+/// The serialized form has the fields `h`, `w`, `q` (a decimal string), `length_bound`, `norm` and an optional `label`.
+#[derive(Serialize, Deserialize)]
 pub struct SIS {
-    h: usize,
-    w: usize,
-    q: BigUint,
-    length_bound: f64,
-    norm: Norm,
+    pub(crate) h: usize,
+    pub(crate) w: usize,
+    #[serde(with = "crate::serde_biguint")]
+    pub(crate) q: BigUint,
+    pub(crate) length_bound: f64,
+    pub(crate) norm: Norm,
+    /// Optional user label identifying the instance in reports, see [`SIS::with_label`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) label: Option<String>,
 }
 
 impl Display for SIS {
@@ -46,6 +61,7 @@ impl SIS {
             q,
             length_bound,
             norm,
+            label: None,
         }
     }
 
@@ -56,6 +72,7 @@ impl SIS {
             length_bound: self.length_bound,
             w: self.w,
             norm: self.norm,
+            label: self.label.clone(),
         }
     }
 
@@ -66,17 +83,40 @@ impl SIS {
             length_bound,
             w: self.w,
             norm: self.norm,
+            label: self.label.clone(),
         }
     }
 
+    /// Return the same instance with label `label` (e.g., the name of a parameter set), which identifies it in an [`EstimateTable`](crate::table::EstimateTable).
+    pub fn with_label(self, label: impl Into<String>) -> Self {
+        SIS {
+            label: Some(label.into()),
+            ..self
+        }
+    }
+
+    /// Return the label of the instance, if any.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
     pub fn parse_f64(s: String) -> Result<f64, ParseFloatError> {
         // Both lattice-estimator and security-estimator logs some additional info, we only care about the last line of stdout
         f64::from_str(s.lines().last().unwrap())
     }
 
+    /// Return lambda such that SIS_{n, q, length_bound, m} is 2^lambda-hard (for a given norm), i.e., the time cost of [`SIS::best_attack`].
+    /// Returns [`EstimatorError::InvalidParameters`] for invalid instances, and [`EstimatorError::NonConvergent`] if no attack succeeds.
+    pub fn security_level(&self) -> Result<f64, EstimatorError> {
+        self.check_params()?;
+        self.best_attack()
+            .map(|attack| attack.log2_cost_time)
+            .ok_or_else(|| EstimatorError::NonConvergent(format!("no attack solves {self}")))
+    }
+
     /// Return lambda such that SIS_{n, q, length_bound, m} is 2^lambda-hard (for a given norm).
     /// Internally, this calls out to the lattice-estimator via a wrapper Python script.
-    pub fn security_level(&self) -> f64 {
+    pub fn security_level_sage(&self) -> f64 {
         let func = match self.norm {
             Norm::L2 => "sis_security_level_l2",
             Norm::Linf => "sis_security_level_linf",
@@ -91,6 +131,310 @@ impl SIS {
         .unwrap()
     }
 
+    fn log_q(&self) -> f64 {
+        self.q.to_f64().unwrap().log2()
+    }
+
+    /// Return true iff a solution can trivially be found, i.e., if $\beta\_2 \geq q$ (a q-vector is a solution) or $\beta\_\infty \geq q/2$ (any solution reduced to centered representatives is a solution).
+    pub fn is_trivial(&self) -> bool {
+        let q = self.q.to_f64().unwrap();
+        match self.norm {
+            Norm::L2 => self.length_bound >= q,
+            Norm::Linf => self.length_bound >= q / 2.,
+        }
+    }
+
+    /// $\log\_2$ of the length of the shortest vector found by BKZ with block size `block_size` on the SIS lattice restricted to `d` columns, i.e., $\delta^d q^{h/d}$ (see [`expected_bkz_first_length`]).
+    fn log2_reduced_length(&self, block_size: usize, d: usize) -> f64 {
+        if d <= self.h {
+            // The lattice only contains q-vectors
+            return self.log_q();
+        }
+        expected_bkz_first_length(d, block_size, self.h as f64 * self.log_q())
+    }
+
+    /// $\log\_2$ of the largest $\ell\_2$ length a solution restricted to `d` columns may have.
+    /// For the $\ell\_\infty$ norm, this uses the $\sqrt{d}$ scaling, which gets tighter the more columns are dropped.
+    fn log2_target_length(&self, d: usize) -> f64 {
+        match self.norm {
+            Norm::L2 => self.length_bound.log2(),
+            Norm::Linf => self.length_bound.log2() + 0.5 * (d as f64).log2(),
+        }
+    }
+
+    /// Return an error if the instance is invalid, i.e., unless $h, w \geq 1$, $q \geq 2$ and the length bound is positive.
+    fn check_params(&self) -> Result<(), EstimatorError> {
+        if self.h == 0 || self.w == 0 {
+            return Err(EstimatorError::InvalidParameters(format!(
+                "{self}: the numbers of rows and columns must be positive"
+            )));
+        }
+        if self.q < BigUint::from(2u64) {
+            return Err(EstimatorError::InvalidParameters(format!(
+                "{self}: the modulus must be at least 2"
+            )));
+        }
+        if self.length_bound.is_nan() || self.length_bound <= 0. {
+            return Err(EstimatorError::InvalidParameters(format!(
+                "{self}: the length bound must be positive"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Return a native estimate of the hardness of `SIS\[h, w, q, length_bound\]` (for a given norm) under the cost model `est`.
+    /// Returns [`EstimatorError::InvalidParameters`] for invalid instances, and [`EstimatorError::NonConvergent`] if lattice reduction does not find a solution for any block size satisfying `constraint`.
+    /// For each block size, the attack runs on the best sublattice obtained by dropping columns, and succeeds if the expected length of the shortest vector found is below the (norm-converted) length bound.
+    /// The block size is chosen using [`find_optimal_block_size`], among the block sizes whose memory cost satisfies `constraint`.
+    /// The cost of reaching that block size is computed according to `schedule`, i.e., for a single BKZ run or for progressive BKZ.
+    pub fn security_level_internal(
+        &self,
+        est: Estimates,
+        constraint: &CostConstraint,
+        schedule: BkzSchedule,
+    ) -> Result<AttackEstimate, EstimatorError> {
+        self.security_level_internal_cached(est, constraint, schedule, &CostCache::new())
+    }
+
+    /// Same as [`SIS::security_level_internal`], but looks up and stores the BKZ costs in `cache`, which may be shared across instances with the same modulus and number of columns.
+    pub fn security_level_internal_cached(
+        &self,
+        est: Estimates,
+        constraint: &CostConstraint,
+        schedule: BkzSchedule,
+        cache: &CostCache,
+    ) -> Result<AttackEstimate, EstimatorError> {
+        self.check_params()?;
+        if self.is_trivial() {
+            return Ok(AttackEstimate::trivial(est));
+        }
+        let non_convergent = || {
+            EstimatorError::NonConvergent(format!(
+                "lattice reduction does not solve {self} under {est} with {constraint:?}"
+            ))
+        };
+        // The memory cost is (essentially) non-decreasing in the block size, so this keeps the search range contiguous
+        let max_block_size = constraint
+            .max_block_size(est, self.w)
+            .ok_or_else(non_convergent)?;
+        let (block_size, lambda) = find_optimal_block_size(
+            |block_size| {
+                if !constraint.is_satisfied(est, block_size) {
+                    return f64::INFINITY;
+                }
+                self.best_dimension(est, schedule, block_size, cache)
+                    .map_or(f64::INFINITY, |(_, cost)| cost)
+            },
+            2,
+            max_block_size,
+        );
+        if lambda.is_infinite() {
+            return Err(non_convergent());
+        }
+        let (dim, _) = self
+            .best_dimension(est, schedule, block_size, cache)
+            .ok_or_else(non_convergent)?;
+        let attack = if dim < self.w {
+            AttackKind::DimensionReduction {
+                dropped_columns: self.w - dim,
+            }
+        } else {
+            AttackKind::LatticeReduction
+        };
+        Ok(AttackEstimate {
+            attack,
+            estimate: est,
+            block_size,
+            schedule,
+            dim,
+            log2_cost_time: lambda,
+            log2_cost_memory: Some(bkz_memory_cost(est, block_size)),
+        })
+    }
+
+    /// Return the generalized birthday attack on the instance (see [`combinatorial_sis_attack`]), reported under the cost model `est`.
+    /// Returns [`EstimatorError::UnsupportedNorm`] if the norm is not $\ell\_\infty$, [`EstimatorError::InvalidParameters`] if the length bound is larger than [`COMBINATORIAL_MAX_LENGTH_BOUND`], and [`EstimatorError::NonConvergent`] if there are not enough columns for the attack.
+    /// The attack stores all its lists, so its memory cost is its time cost.
+    pub fn combinatorial_attack(&self, est: Estimates) -> Result<AttackEstimate, EstimatorError> {
+        self.check_params()?;
+        if self.norm != Norm::Linf {
+            return Err(EstimatorError::UnsupportedNorm(self.norm));
+        }
+        if self.length_bound > COMBINATORIAL_MAX_LENGTH_BOUND {
+            return Err(EstimatorError::InvalidParameters(format!(
+                "{self}: the generalized birthday attack only applies to length bounds up to {COMBINATORIAL_MAX_LENGTH_BOUND}"
+            )));
+        }
+        let (levels, cost) = combinatorial_sis_attack(self.h, self.w, &self.q, self.length_bound)
+            .ok_or_else(|| {
+            EstimatorError::NonConvergent(format!(
+                "{self}: not enough columns for the generalized birthday attack"
+            ))
+        })?;
+        Ok(AttackEstimate {
+            attack: AttackKind::Combinatorial { levels },
+            estimate: est,
+            block_size: 0,
+            schedule: BkzSchedule::Fixed,
+            dim: self.w,
+            log2_cost_time: cost,
+            log2_cost_memory: Some(cost),
+        })
+    }
+
+    /// Return the estimates of all attacks (using a single BKZ run) satisfying `constraint` under each of the cost models in `estimators`, skipping cost models under which no such attack succeeds.
+    /// For small $\ell\_\infty$ bounds, this includes the generalized birthday attack, see [`SIS::combinatorial_attack`].
+    pub fn estimate_all(
+        &self,
+        estimators: &[Estimates],
+        constraint: &CostConstraint,
+    ) -> Vec<AttackEstimate> {
+        self.estimate_all_cached(estimators, constraint, &CostCache::new())
+    }
+
+    /// Same as [`SIS::estimate_all`], but looks up and stores the BKZ costs in `cache`, see [`SIS::security_level_internal_cached`].
+    pub fn estimate_all_cached(
+        &self,
+        estimators: &[Estimates],
+        constraint: &CostConstraint,
+        cache: &CostCache,
+    ) -> Vec<AttackEstimate> {
+        estimators
+            .iter()
+            .flat_map(|est| {
+                let lattice = self
+                    .security_level_internal_cached(*est, constraint, BkzSchedule::Fixed, cache)
+                    .ok();
+                let combinatorial = self.combinatorial_attack(*est).ok().filter(|attack| {
+                    constraint
+                        .max_log2_memory
+                        .is_none_or(|max_log2_memory| attack.log2_cost_time <= max_log2_memory)
+                });
+                lattice.into_iter().chain(combinatorial)
+            })
+            .collect()
+    }
+
+    /// Return the cheapest attack under the default cost models [`Estimates::DEFAULT`] with unbounded memory, or `None` if no attack succeeds.
+    pub fn best_attack(&self) -> Option<AttackEstimate> {
+        self.best_attack_constrained(&CostConstraint::default())
+    }
+
+    /// Return the cheapest attack satisfying `constraint` under the default cost models [`Estimates::DEFAULT`], or `None` if no such attack succeeds.
+    pub fn best_attack_constrained(&self, constraint: &CostConstraint) -> Option<AttackEstimate> {
+        AttackEstimate::best(&self.estimate_all(&Estimates::DEFAULT, constraint)).cloned()
+    }
+
+    /// Return the number of columns `d` for which BKZ with block size `block_size` (reached according to `schedule`) finds a solution at the lowest cost, together with that cost, or `None` if it does not find a solution for any `d`.
+    /// The SIS lattice restricted to `d > h` columns has volume $q^h$.
+    fn best_dimension(
+        &self,
+        est: Estimates,
+        schedule: BkzSchedule,
+        block_size: usize,
+        cache: &CostCache,
+    ) -> Option<(usize, f64)> {
+        let log_q = self.log_q();
+        let log2_volume = self.h as f64 * log_q;
+        (block_size.max(self.h + 1)..=self.w)
+            .filter(|&d| self.log2_reduced_length(block_size, d) <= self.log2_target_length(d))
+            .map(|d| {
+                let cost = cache.cost(schedule, est, block_size, d, log_q, log2_volume);
+                (d, cost)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// Return the smallest modulus `q` such that `SIS\[n, m, q, norm_bound\]` is $2^\lambda$-hard under the cost model `est` for $\lambda \geq$ `target_lambda`, together with the best attack on that instance.
+    /// The security level is non-decreasing in `q`: increasing `q` only increases the length of the vectors found by lattice reduction (and the cost of LLL), so an attack succeeding for `q` also succeeds for smaller moduli, at lower cost.
+    /// Returns `Ok(None)` if no modulus below $2^{62}$ reaches the target, and the estimator errors otherwise, see [`find_min_param`] for the treatment of [`EstimatorError::NonConvergent`].
+    pub fn find_min_modulus(
+        n: usize,
+        m: usize,
+        norm_bound: f64,
+        norm: Norm,
+        target_lambda: f64,
+        est: Estimates,
+    ) -> Result<Option<(u64, AttackEstimate)>, EstimatorError> {
+        Self::find_min_modulus_cached(
+            n,
+            m,
+            norm_bound,
+            norm,
+            target_lambda,
+            est,
+            &CostCache::new(),
+        )
+    }
+
+    /// Same as [`SIS::find_min_modulus`], but looks up and stores the BKZ costs in `cache`; in particular, the estimate reported for the smallest secure modulus reuses the costs computed while searching for it.
+    pub(crate) fn find_min_modulus_cached(
+        n: usize,
+        m: usize,
+        norm_bound: f64,
+        norm: Norm,
+        target_lambda: f64,
+        est: Estimates,
+        cache: &CostCache,
+    ) -> Result<Option<(u64, AttackEstimate)>, EstimatorError> {
+        find_min_param(2, 1 << 62, target_lambda, |q| {
+            SIS::new(n, q.into(), norm_bound, m, norm).security_level_internal_cached(
+                est,
+                &CostConstraint::default(),
+                BkzSchedule::Fixed,
+                cache,
+            )
+        })
+    }
+
+    /// Return the smallest number of rows `n < m` such that `SIS\[n, m, q, norm_bound\]` is $2^\lambda$-hard under the cost model `est` for $\lambda \geq$ `target_lambda`, together with the best attack on that instance.
+    /// The security level is non-decreasing in `n`: increasing `n` only increases the length of the vectors found by lattice reduction, so an attack succeeding for `n` also succeeds for smaller `n`, at lower cost.
+    /// Returns `Ok(None)` if no `n < m` reaches the target, and the estimator errors otherwise, see [`find_min_param`] for the treatment of [`EstimatorError::NonConvergent`].
+    pub fn find_min_dimension(
+        m: usize,
+        q: u64,
+        norm_bound: f64,
+        norm: Norm,
+        target_lambda: f64,
+        est: Estimates,
+    ) -> Result<Option<(usize, AttackEstimate)>, EstimatorError> {
+        Self::find_min_dimension_cached(
+            m,
+            q,
+            norm_bound,
+            norm,
+            target_lambda,
+            est,
+            &CostCache::new(),
+        )
+    }
+
+    /// Same as [`SIS::find_min_dimension`], sharing the BKZ costs in `cache` across all candidate dimensions (which all have the same modulus and number of columns).
+    pub(crate) fn find_min_dimension_cached(
+        m: usize,
+        q: u64,
+        norm_bound: f64,
+        norm: Norm,
+        target_lambda: f64,
+        est: Estimates,
+        cache: &CostCache,
+    ) -> Result<Option<(usize, AttackEstimate)>, EstimatorError> {
+        if m < 2 {
+            return Err(EstimatorError::InvalidParameters(format!(
+                "searching for n < m requires m >= 2 columns, got m = {m}"
+            )));
+        }
+        let found = find_min_param(1, m as u64 - 1, target_lambda, |n| {
+            SIS::new(n as usize, q.into(), norm_bound, m, norm).security_level_internal_cached(
+                est,
+                &CostConstraint::default(),
+                BkzSchedule::Fixed,
+                cache,
+            )
+        })?;
+        Ok(found.map(|(n, attack)| (n as usize, attack)))
+    }
+
     pub fn upper_bound_h(&self) -> usize {
         let log_q = match self.norm {
             Norm::L2 => self.q.to_f64().unwrap().log2(),
@@ -106,22 +450,28 @@ impl SIS {
     }
 
     /// Return the smallest `h` such that `SIS\[h, w, q, length_bound\]` is $2^\lambda$-hard (for a given norm).
+    /// Instances that no attack solves (see [`EstimatorError::NonConvergent`]) count as secure.
     pub fn find_optimal_h(&self, lambda: usize) -> Result<usize, LatticeEstimatorError> {
-        let mut hi: usize = self.upper_bound_h();
+        let mut hi: usize = self.upper_bound_h().max(1);
         let mut lo: usize = 1;
 
-        let sis = self.with_h(hi);
-        let lambda_hi = sis.security_level();
-        debug_assert!(
-            lambda_hi >= lambda as f64,
-            "{sis} has sec. param. {lambda_hi}  < target lambda = {lambda}"
-        );
+        // `upper_bound_h` is only a heuristic starting point: extend the search interval (up to
+        // `w`) until the target security level is actually reached.
+        let reaches_lambda = |sis: &SIS| is_secure(sis.security_level(), lambda as f64);
+        while !reaches_lambda(&self.with_h(hi))? {
+            if hi >= self.w {
+                return Err(LatticeEstimatorError::from(format!(
+                    "no h <= w = {} reaches target lambda = {lambda}",
+                    self.w
+                )));
+            }
+            lo = hi + 1;
+            hi = (2 * hi).min(self.w);
+        }
         // Loop invariant: SIS[hi, w, q, length_bound] is 2^lambda_hi-hard with lambda_hi >= lambda
         while hi > lo {
             let mid = lo + (hi - lo) / 2;
-            let sis = self.with_h(mid);
-            let mid_lambda = sis.security_level();
-            if mid_lambda >= lambda as f64 {
+            if reaches_lambda(&self.with_h(mid))? {
                 // Search for smaller n in [lo, mid]
                 hi = mid;
             } else {
@@ -134,6 +484,7 @@ impl SIS {
     }
 
     /// Return the smallest `h` such that `SIS\[h, w, q, length_bound(h)\]` is $2^\lambda$-hard (for a given norm), where `length_bound` is a function of `h`.
+    /// Instances that no attack solves (see [`EstimatorError::NonConvergent`]) count as secure.
     pub fn find_optimal_h_dynamic<F>(
         &self,
         length_bound: F,
@@ -146,26 +497,32 @@ impl SIS {
         // Instead, exhaustively search powers of 2 until we find a suitable n.
         // TODO: use a better search algorithm / return a more fine-grained result
         let log2_m = self.w.ilog2();
-        let candidates = (1..log2_m).map(|i| 2usize.pow(i));
-        candidates
-            .map(|n| self.with_h(n).with_length_bound(length_bound(n)))
-            .find(|sis| sis.security_level() >= lambda as f64)
-            .map(|sis| sis.h)
-            .ok_or(LatticeEstimatorError::from(
-                "no suitable h found".to_string(),
-            ))
+        for n in (1..log2_m).map(|i| 2usize.pow(i)) {
+            let sis = self.with_h(n).with_length_bound(length_bound(n));
+            if is_secure(sis.security_level(), lambda as f64)? {
+                return Ok(n);
+            }
+        }
+        Err(LatticeEstimatorError::from(
+            "no suitable h found".to_string(),
+        ))
     }
 }
 
 #[cfg(test)]
 mod test {
+    use num_bigint::BigUint;
+
+    use crate::attack::{AttackKind, AttackReport, CostConstraint};
+    use crate::errors::EstimatorError;
     use crate::norms::Norm;
+    use crate::reduction::{BkzSchedule, CostCache, Estimates};
     use crate::sis::SIS;
 
     #[test]
     fn test_sis_security_level_l2() {
         let falcon512_unf: SIS = SIS::new(512, 12289u64.into(), 5833.9072, 1024, Norm::L2);
-        let lambda = falcon512_unf.security_level();
+        let lambda = falcon512_unf.security_level().unwrap();
         assert!(lambda >= 128.);
         println!("{falcon512_unf} -> lambda: {lambda}");
     }
@@ -175,21 +532,42 @@ mod test {
         let dilithium2_msis_wk_unf: SIS =
             SIS::new(1024, 8380417u64.into(), 350209., 2304, Norm::Linf);
 
-        let lambda = dilithium2_msis_wk_unf.security_level();
+        let lambda = dilithium2_msis_wk_unf.security_level().unwrap();
         assert!(lambda >= 128.);
         println!("{dilithium2_msis_wk_unf} -> lambda: {lambda}");
     }
 
+    #[test]
+    fn test_sis_security_level_errors() {
+        for sis in [
+            SIS::new(0, 12289u64.into(), 5833.9072, 1024, Norm::L2),
+            SIS::new(512, 1u64.into(), 5833.9072, 1024, Norm::L2),
+            SIS::new(512, 12289u64.into(), 0., 1024, Norm::L2),
+            SIS::new(512, 12289u64.into(), f64::NAN, 1024, Norm::L2),
+        ] {
+            assert!(matches!(
+                sis.security_level(),
+                Err(EstimatorError::InvalidParameters(_))
+            ));
+        }
+        // No non-zero integer vector is shorter than 1
+        let sis = SIS::new(512, 12289u64.into(), 0.5, 1024, Norm::L2);
+        assert!(matches!(
+            sis.security_level(),
+            Err(EstimatorError::NonConvergent(_))
+        ));
+    }
+
     #[test]
     fn test_find_optimal_h_l2() {
         let falcon512_unf: SIS = SIS::new(512, 12289u64.into(), 5833.9072, 1024, Norm::L2);
         let h_opt = falcon512_unf.find_optimal_h(128).unwrap();
         let sis = falcon512_unf.with_h(h_opt);
-        let lambda = sis.security_level();
+        let lambda = sis.security_level().unwrap();
         assert!(lambda >= 128.0);
         println!(
             "{falcon512_unf} -> lambda: {}",
-            falcon512_unf.security_level()
+            falcon512_unf.security_level().unwrap()
         );
         println!("{sis} -> lambda: {lambda}");
     }
@@ -201,12 +579,430 @@ mod test {
 
         let h_opt = dilithium2_msis_wk_unf.find_optimal_h(128).unwrap();
         let sis = dilithium2_msis_wk_unf.with_h(h_opt);
-        let lambda = sis.security_level();
+        let lambda = sis.security_level().unwrap();
         assert!(lambda >= 128.0);
         println!(
             "{dilithium2_msis_wk_unf} -> lambda: {}",
-            dilithium2_msis_wk_unf.security_level()
+            dilithium2_msis_wk_unf.security_level().unwrap()
         );
         println!("{sis} -> lambda: {lambda}");
     }
+
+    #[test]
+    fn test_sis_security_level_internal_l2() {
+        let falcon512_unf: SIS = SIS::new(512, 12289u64.into(), 5833.9072, 1024, Norm::L2);
+        let est = falcon512_unf
+            .security_level_internal(
+                Estimates::BDGL16,
+                &CostConstraint::default(),
+                BkzSchedule::Fixed,
+            )
+            .unwrap();
+        println!("{falcon512_unf} -> {est:?}");
+        assert!(est.log2_cost_time >= 128.);
+    }
+
+    #[test]
+    fn test_sis_security_level_internal_linf_vs_l2() {
+        let falcon512_unf: SIS = SIS::new(512, 12289u64.into(), 5833.9072, 1024, Norm::L2);
+        let falcon512_unf_linf: SIS =
+            SIS::new(512, 12289u64.into(), 5833.9072 / 32., 1024, Norm::Linf);
+        for est in Estimates::ALL {
+            let l2 = falcon512_unf
+                .security_level_internal(est, &CostConstraint::default(), BkzSchedule::Fixed)
+                .unwrap();
+            let linf = falcon512_unf_linf
+                .security_level_internal(est, &CostConstraint::default(), BkzSchedule::Fixed)
+                .unwrap();
+            println!("{est}: l2 -> {l2:?}, linf -> {linf:?}");
+            assert!((l2.log2_cost_time - linf.log2_cost_time).abs() < 2.);
+        }
+    }
+
+    #[test]
+    fn test_sis_security_level_internal_trivial() {
+        let sis = SIS::new(512, 12289u64.into(), 6145., 1024, Norm::Linf);
+        let est = sis
+            .security_level_internal(
+                Estimates::BDGL16,
+                &CostConstraint::default(),
+                BkzSchedule::Fixed,
+            )
+            .unwrap();
+        assert_eq!(est.attack, AttackKind::Trivial);
+        assert!(est.log2_cost_time < 1.);
+
+        let sis = SIS::new(512, 12289u64.into(), 12289., 1024, Norm::L2);
+        let est = sis
+            .security_level_internal(
+                Estimates::BDGL16,
+                &CostConstraint::default(),
+                BkzSchedule::Fixed,
+            )
+            .unwrap();
+        assert_eq!(est.attack, AttackKind::Trivial);
+    }
+
+    #[test]
+    fn test_sis_security_level_internal_dimension_reduction() {
+        // Many more columns than needed: the attack should not use all of them
+        let sis = SIS::new(64, 12289u64.into(), 8., 4096, Norm::Linf);
+        let est = sis
+            .security_level_internal(
+                Estimates::ADPS16,
+                &CostConstraint::default(),
+                BkzSchedule::Fixed,
+            )
+            .unwrap();
+        assert!(matches!(est.attack, AttackKind::DimensionReduction { .. }));
+        assert!(est.dim < 4096);
+    }
+
+    fn exhaustive_security_level_internal(sis: &SIS, est: Estimates) -> (usize, f64) {
+        let cache = CostCache::new();
+        (2..=sis.w)
+            .map(|b| {
+                (
+                    b,
+                    sis.best_dimension(est, BkzSchedule::Fixed, b, &cache)
+                        .map_or(f64::INFINITY, |(_, cost)| cost),
+                )
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_security_level_internal_matches_exhaustive_search() {
+        let instances = [
+            SIS::new(512, 12289u64.into(), 5833.9072, 1024, Norm::L2),
+            SIS::new(256, 12289u64.into(), 1000., 1024, Norm::L2),
+            SIS::new(256, 8380417u64.into(), 4096., 1024, Norm::Linf),
+            SIS::new(128, 2147483649u64.into(), 2., 2048, Norm::Linf),
+        ];
+        for sis in instances {
+            for est in [Estimates::ADPS16, Estimates::BDGL16, Estimates::CheNgu12] {
+                let est_opt = sis
+                    .security_level_internal(est, &CostConstraint::default(), BkzSchedule::Fixed)
+                    .unwrap();
+                let (b, lambda) = exhaustive_security_level_internal(&sis, est);
+                assert_eq!(est_opt.block_size, b, "{sis}, {est}");
+                assert_eq!(est_opt.log2_cost_time, lambda, "{sis}, {est}");
+            }
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_security_level_internal_grid() {
+        let grid = (0..10)
+            .flat_map(|i| (0..10).map(move |j| (128 + 32 * i, 1u64 << (20 + j))))
+            .map(|(h, q)| SIS::new(h, q.into(), 4096., 2048, Norm::L2))
+            .collect::<Vec<_>>();
+
+        let start = std::time::Instant::now();
+        for sis in grid.iter() {
+            let _ = sis.security_level_internal(
+                Estimates::BDGL16,
+                &CostConstraint::default(),
+                BkzSchedule::Fixed,
+            );
+        }
+        let optimized = start.elapsed();
+
+        let start = std::time::Instant::now();
+        for sis in grid.iter() {
+            exhaustive_security_level_internal(sis, Estimates::BDGL16);
+        }
+        let exhaustive = start.elapsed();
+        println!("100-point grid: ternary search {optimized:?}, exhaustive sweep {exhaustive:?}");
+    }
+
+    #[test]
+    fn test_best_attack() {
+        let falcon512_unf: SIS = SIS::new(512, 12289u64.into(), 5833.9072, 1024, Norm::L2);
+        let all = falcon512_unf.estimate_all(&Estimates::ALL, &CostConstraint::default());
+        assert_eq!(all.len(), Estimates::ALL.len());
+        println!("{}", AttackReport(&all));
+
+        let best = falcon512_unf.best_attack().unwrap();
+        println!("{best}");
+        assert_eq!(best.estimate, Estimates::Matzov);
+        assert_eq!(best.block_size, 416);
+        assert_eq!(falcon512_unf.security_level(), Ok(best.log2_cost_time));
+    }
+
+    #[test]
+    fn test_custom_estimate() {
+        let falcon512_unf: SIS = SIS::new(512, 12289u64.into(), 5833.9072, 1024, Norm::L2);
+        let custom = Estimates::Custom {
+            a: 0.292,
+            b: 16.4,
+            label: "my-bdgl16",
+        };
+        let constraint = CostConstraint::default();
+        let est = falcon512_unf
+            .security_level_internal(custom, &constraint, BkzSchedule::Fixed)
+            .unwrap();
+        let est_bdgl = falcon512_unf
+            .security_level_internal(Estimates::BDGL16, &constraint, BkzSchedule::Fixed)
+            .unwrap();
+        assert_eq!(est.log2_cost_time, est_bdgl.log2_cost_time);
+        assert!(est.to_string().contains("my-bdgl16"));
+    }
+
+    #[test]
+    fn test_progressive_schedule() {
+        let falcon512_unf: SIS = SIS::new(512, 12289u64.into(), 5833.9072, 1024, Norm::L2);
+        let constraint = CostConstraint::default();
+        let fixed = falcon512_unf
+            .security_level_internal(Estimates::BDGL16, &constraint, BkzSchedule::Fixed)
+            .unwrap();
+        let progressive = falcon512_unf
+            .security_level_internal(Estimates::BDGL16, &constraint, BkzSchedule::PROGRESSIVE)
+            .unwrap();
+        println!("{}", AttackReport(&[fixed.clone(), progressive.clone()]));
+        assert_eq!(progressive.schedule, BkzSchedule::PROGRESSIVE);
+        assert!(progressive.log2_cost_time < fixed.log2_cost_time);
+        assert!(progressive.to_string().contains("progressive(60:1)"));
+    }
+
+    fn security_level_bdgl16(sis: &SIS) -> f64 {
+        sis.security_level_internal(
+            Estimates::BDGL16,
+            &CostConstraint::default(),
+            BkzSchedule::Fixed,
+        )
+        .map_or(f64::INFINITY, |attack| attack.log2_cost_time)
+    }
+
+    #[test]
+    fn test_find_min_modulus() {
+        // Falcon-512 uses q = 12289, which should be (more than) enough for 128 bits of security
+        let (q, attack) =
+            SIS::find_min_modulus(512, 1024, 5833.9072, Norm::L2, 128., Estimates::BDGL16)
+                .unwrap()
+                .unwrap();
+        println!("q = {q}\n{attack}");
+        assert!(q <= 12289);
+        assert!(attack.log2_cost_time >= 128.);
+        assert_eq!(
+            security_level_bdgl16(&SIS::new(512, q.into(), 5833.9072, 1024, Norm::L2)),
+            attack.log2_cost_time
+        );
+        assert!(
+            security_level_bdgl16(&SIS::new(512, (q - 1).into(), 5833.9072, 1024, Norm::L2)) < 128.
+        );
+    }
+
+    #[test]
+    fn test_find_min_dimension() {
+        let (n, attack) =
+            SIS::find_min_dimension(1024, 12289, 5833.9072, Norm::L2, 128., Estimates::BDGL16)
+                .unwrap()
+                .unwrap();
+        println!("n = {n}\n{attack}");
+        assert!(n <= 512);
+        assert!(attack.log2_cost_time >= 128.);
+        assert!(
+            security_level_bdgl16(&SIS::new(n - 1, 12289u64.into(), 5833.9072, 1024, Norm::L2))
+                < 128.
+        );
+    }
+
+    #[test]
+    fn test_find_min_param_errors() {
+        // Invalid instances are reported instead of being considered secure
+        for norm_bound in [0., f64::NAN] {
+            assert!(matches!(
+                SIS::find_min_modulus(512, 1024, norm_bound, Norm::L2, 128., Estimates::BDGL16),
+                Err(EstimatorError::InvalidParameters(_))
+            ));
+        }
+        assert!(matches!(
+            SIS::find_min_dimension(1, 12289, 5833.9072, Norm::L2, 128., Estimates::BDGL16),
+            Err(EstimatorError::InvalidParameters(_))
+        ));
+    }
+
+    #[test]
+    fn test_memory_constraint() {
+        let falcon1024_unf: SIS = SIS::new(1024, 12289u64.into(), 8382.4, 2048, Norm::L2);
+        let unconstrained = falcon1024_unf.best_attack().unwrap();
+        assert!(unconstrained.log2_cost_memory.unwrap() > 80.);
+
+        // Sieving requires too much memory, so the best attack is enumeration
+        let constraint = CostConstraint::with_max_log2_memory(80.);
+        let constrained = falcon1024_unf.best_attack_constrained(&constraint).unwrap();
+        println!(
+            "{}",
+            AttackReport(&[unconstrained.clone(), constrained.clone()])
+        );
+        assert_eq!(constrained.estimate, Estimates::CheNgu12);
+        assert!(constrained.log2_cost_memory.unwrap() <= 80.);
+        assert!(constrained.log2_cost_time > unconstrained.log2_cost_time);
+
+        for est in falcon1024_unf.estimate_all(&Estimates::ALL, &constraint) {
+            assert!(est.log2_cost_memory.unwrap() <= 80.);
+        }
+    }
+
+    #[test]
+    fn test_attack_estimate_serde() {
+        let falcon512_unf: SIS = SIS::new(512, 12289u64.into(), 5833.9072, 1024, Norm::L2);
+        let best = falcon512_unf.best_attack().unwrap();
+        let json = serde_json::to_string(&best).unwrap();
+        let best_: crate::attack::AttackEstimate = serde_json::from_str(&json).unwrap();
+        assert_eq!(best, best_);
+
+        // Custom cost models are deserialized with the label "custom", which equality ignores
+        let custom = Estimates::Custom {
+            a: 0.292,
+            b: 16.4,
+            label: "my-bdgl16",
+        };
+        for est in falcon512_unf.estimate_all(&[custom], &CostConstraint::default()) {
+            let json = serde_json::to_string(&est).unwrap();
+            let est_: crate::attack::AttackEstimate = serde_json::from_str(&json).unwrap();
+            assert_eq!(est, est_);
+            assert_eq!(est_.estimate.name(), "custom");
+        }
+    }
+
+    #[test]
+    fn test_combinatorial_attack() {
+        // Many columns and a ternary solution: the generalized birthday attack beats lattice reduction
+        let wide = SIS::new(32, 3329u64.into(), 1., 16384, Norm::Linf);
+        let all = wide.estimate_all(&Estimates::DEFAULT, &CostConstraint::default());
+        println!("{wide}\n{}", AttackReport(&all));
+        let best = wide.best_attack().unwrap();
+        assert!(matches!(best.attack, AttackKind::Combinatorial { .. }));
+        assert!(AttackKind::Combinatorial { levels: 9 }
+            .to_string()
+            .contains("512 lists"));
+
+        // Falcon modulus with fewer columns: the lists are too small for many levels, lattice reduction wins
+        let falcon_like = SIS::new(32, 12289u64.into(), 2., 2048, Norm::Linf);
+        assert!(falcon_like.combinatorial_attack(Estimates::BDGL16).is_ok());
+        assert!(!matches!(
+            falcon_like.best_attack().unwrap().attack,
+            AttackKind::Combinatorial { .. }
+        ));
+        let falcon512_linf = SIS::new(512, 12289u64.into(), 2., 1024, Norm::Linf);
+        assert!(falcon512_linf
+            .combinatorial_attack(Estimates::BDGL16)
+            .is_err());
+
+        // The attack only applies to small l_inf bounds
+        let l2 = SIS::new(32, 3329u64.into(), 1., 16384, Norm::L2);
+        assert_eq!(
+            l2.combinatorial_attack(Estimates::BDGL16),
+            Err(EstimatorError::UnsupportedNorm(Norm::L2))
+        );
+    }
+
+    /// A 50x50 grid of instances with 2048 columns, ordered by modulus.
+    fn grid_50x50() -> Vec<SIS> {
+        (0..50)
+            .flat_map(|j| (0..50).map(move |i| (64 + 16 * i, BigUint::from(1u32) << (16 + j))))
+            .map(|(h, q)| SIS::new(h, q, 4096., 2048, Norm::L2))
+            .collect()
+    }
+
+    #[test]
+    fn test_cost_cache_bit_identical() {
+        // A single cache for instances with different moduli and volumes
+        let cache = CostCache::new();
+        for sis in grid_50x50().iter().step_by(7) {
+            for schedule in [BkzSchedule::Fixed, BkzSchedule::PROGRESSIVE] {
+                let uncached = sis.security_level_internal(
+                    Estimates::Matzov,
+                    &CostConstraint::default(),
+                    schedule,
+                );
+                let cached = sis.security_level_internal_cached(
+                    Estimates::Matzov,
+                    &CostConstraint::default(),
+                    schedule,
+                    &cache,
+                );
+                assert_eq!(
+                    uncached.map(|e| (e.block_size, e.dim, e.log2_cost_time.to_bits())),
+                    cached.map(|e| (e.block_size, e.dim, e.log2_cost_time.to_bits())),
+                    "{sis}"
+                );
+            }
+        }
+        assert!(!cache.is_empty());
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_cost_cache_grid() {
+        let grid = grid_50x50();
+
+        let start = std::time::Instant::now();
+        for sis in grid.iter() {
+            let _ = sis.security_level_internal(
+                Estimates::Matzov,
+                &CostConstraint::default(),
+                BkzSchedule::Fixed,
+            );
+        }
+        let uncached = start.elapsed();
+
+        let start = std::time::Instant::now();
+        let cache = CostCache::new();
+        for sis in grid.iter() {
+            let _ = sis.security_level_internal_cached(
+                Estimates::Matzov,
+                &CostConstraint::default(),
+                BkzSchedule::Fixed,
+                &cache,
+            );
+        }
+        let cached = start.elapsed();
+        println!(
+            "2500-point grid: uncached {uncached:?}, cached {cached:?} ({} cached costs)",
+            cache.len()
+        );
+    }
+
+    #[test]
+    fn test_security_level_internal_errors() {
+        let estimate = |sis: SIS| {
+            sis.security_level_internal(
+                Estimates::BDGL16,
+                &CostConstraint::default(),
+                BkzSchedule::Fixed,
+            )
+        };
+        for sis in [
+            SIS::new(0, 12289u64.into(), 100., 1024, Norm::L2),
+            SIS::new(512, 12289u64.into(), 100., 0, Norm::L2),
+            SIS::new(512, 1u64.into(), 100., 1024, Norm::L2),
+            SIS::new(512, 12289u64.into(), 0., 1024, Norm::L2),
+            SIS::new(512, 12289u64.into(), f64::NAN, 1024, Norm::L2),
+        ] {
+            assert!(matches!(
+                estimate(sis),
+                Err(EstimatorError::InvalidParameters(_))
+            ));
+        }
+        // Too few columns for lattice reduction to find a short solution
+        assert!(matches!(
+            estimate(SIS::new(512, 12289u64.into(), 100., 600, Norm::L2)),
+            Err(EstimatorError::NonConvergent(_))
+        ));
+        // No block size satisfies the memory constraint
+        let sis = SIS::new(512, 12289u64.into(), 5833.9072, 1024, Norm::L2);
+        assert!(matches!(
+            sis.security_level_internal(
+                Estimates::BDGL16,
+                &CostConstraint::with_max_log2_memory(0.),
+                BkzSchedule::Fixed
+            ),
+            Err(EstimatorError::NonConvergent(_))
+        ));
+    }
 }
@@ -0,0 +1,254 @@
+//! Batch estimation of grids of SIS instances, e.g., for parameter selection, rendered as CSV or markdown tables.
+use std::io;
+use std::io::Write;
+
+use rayon::prelude::*;
+
+use crate::attack::{AttackEstimate, CostConstraint};
+use crate::reduction::{CostCache, Estimates};
+use crate::sis::SIS;
+
+/// The best attacks on a single instance of an [`EstimateTable`], one per cost model.
+pub struct EstimateRow {
+    pub(crate) instance: SIS,
+    pub(crate) attacks: Vec<Option<AttackEstimate>>,
+}
+
+impl EstimateRow {
+    pub fn instance(&self) -> &SIS {
+        &self.instance
+    }
+
+    /// Return the best attack under each of the cost models of the table (in the same order), or `None` for cost models under which no attack succeeds.
+    pub fn attacks(&self) -> &[Option<AttackEstimate>] {
+        &self.attacks
+    }
+
+    /// Return the cheapest attack across all cost models, if any.
+    pub fn best(&self) -> Option<&AttackEstimate> {
+        self.attacks
+            .iter()
+            .flatten()
+            .min_by(|a, b| a.log2_cost_time.total_cmp(&b.log2_cost_time))
+    }
+
+    /// Return the cells describing the instance, i.e., its label (if any), $h$, $w$, $q$, the length bound and the norm.
+    fn instance_cells(&self) -> Vec<String> {
+        let sis = &self.instance;
+        vec![
+            sis.label().unwrap_or_default().to_string(),
+            sis.h.to_string(),
+            sis.w.to_string(),
+            sis.q.to_string(),
+            sis.length_bound.to_string(),
+            sis.norm.to_string(),
+        ]
+    }
+
+    /// Return the cells containing the $\log\_2$ of the time cost of the best attack under each cost model (`inf` if none succeeds), followed by the cost model and attack of the overall best attack.
+    fn estimate_cells(&self) -> Vec<String> {
+        let mut cells: Vec<String> = self
+            .attacks
+            .iter()
+            .map(|attack| {
+                attack
+                    .as_ref()
+                    .map_or("inf".to_string(), |a| format!("{:.2}", a.log2_cost_time))
+            })
+            .collect();
+        match self.best() {
+            Some(best) => cells.extend([best.estimate.name(), best.attack.to_string()]),
+            None => cells.extend([String::new(), String::new()]),
+        }
+        cells
+    }
+}
+
+/// The best attacks on a list of instances under a list of cost models, see [`estimate_grid`].
+pub struct EstimateTable {
+    pub(crate) estimators: Vec<Estimates>,
+    pub(crate) rows: Vec<EstimateRow>,
+}
+
+impl EstimateTable {
+    pub fn estimators(&self) -> &[Estimates] {
+        &self.estimators
+    }
+
+    /// Return one row per instance, in the order of the instances passed to [`estimate_grid`].
+    pub fn rows(&self) -> &[EstimateRow] {
+        &self.rows
+    }
+
+    fn header(&self) -> Vec<String> {
+        ["label", "h", "w", "q", "length_bound", "norm"]
+            .into_iter()
+            .map(str::to_string)
+            .chain(self.estimators.iter().map(Estimates::name))
+            .chain(["best_estimator", "best_attack"].map(str::to_string))
+            .collect()
+    }
+
+    fn body(&self) -> impl Iterator<Item = Vec<String>> + '_ {
+        self.rows.iter().map(|row| {
+            let mut cells = row.instance_cells();
+            cells.extend(row.estimate_cells());
+            cells
+        })
+    }
+
+    /// Write the table to `writer` as CSV, with a header row followed by one row per instance.
+    /// Each row contains the parameters of the instance, the $\log\_2$ of the time cost of the best attack under each cost model, and the overall best attack.
+    pub fn to_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for row in std::iter::once(self.header()).chain(self.body()) {
+            let cells: Vec<String> = row.iter().map(|cell| csv_escape(cell)).collect();
+            writeln!(writer, "{}", cells.join(","))?;
+        }
+        Ok(())
+    }
+
+    /// Return the table as a markdown table, with the same columns as [`EstimateTable::to_csv`].
+    pub fn to_markdown(&self) -> String {
+        let header = self.header();
+        let mut markdown = format!("| {} |\n", header.join(" | "));
+        markdown.push_str(&format!("|{}\n", "---|".repeat(header.len())));
+        for row in self.body() {
+            let cells: Vec<String> = row.iter().map(|cell| cell.replace('|', "\\|")).collect();
+            markdown.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+        markdown
+    }
+}
+
+/// Quote `cell` if it contains a comma, a double quote or a line break, doubling any double quotes (RFC 4180).
+fn csv_escape(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+/// Estimate the best attack (with unbounded memory) on each of `instances` under each of the cost models in `estimators`, see [`SIS::estimate_all`].
+/// The instances are estimated in parallel, sharing a single [`CostCache`], and the rows of the table follow the order of `instances`.
+pub fn estimate_grid(
+    instances: impl Iterator<Item = SIS>,
+    estimators: &[Estimates],
+) -> EstimateTable {
+    let instances: Vec<SIS> = instances.collect();
+    let cache = CostCache::new();
+    let rows = instances
+        .into_par_iter()
+        .map(|instance| {
+            let attacks = estimators
+                .iter()
+                .map(|est| {
+                    AttackEstimate::best(&instance.estimate_all_cached(
+                        &[*est],
+                        &CostConstraint::default(),
+                        &cache,
+                    ))
+                    .cloned()
+                })
+                .collect();
+            EstimateRow { instance, attacks }
+        })
+        .collect();
+    EstimateTable {
+        estimators: estimators.to_vec(),
+        rows,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use num_bigint::BigUint;
+
+    use crate::norms::Norm;
+    use crate::reduction::BkzSchedule;
+
+    use super::*;
+
+    const ESTIMATORS: [Estimates; 2] = [Estimates::Kyber, Estimates::Matzov];
+
+    fn grid() -> impl Iterator<Item = SIS> {
+        [128, 192, 256].into_iter().flat_map(|n| {
+            [1u64 << 20, 1 << 24, 1 << 28].into_iter().map(move |q| {
+                SIS::new(n, BigUint::from(q), 2f64.powi(16), 4 * n, Norm::L2)
+                    .with_label(format!("n={n}, q=2^{}", q.ilog2()))
+            })
+        })
+    }
+
+    #[test]
+    fn test_estimate_grid() {
+        let table = estimate_grid(grid(), &ESTIMATORS);
+        assert_eq!(table.estimators(), ESTIMATORS);
+        assert_eq!(table.rows().len(), 9);
+        for (row, sis) in table.rows().iter().zip(grid()) {
+            assert_eq!(row.instance().label(), sis.label());
+            assert_eq!(row.attacks().len(), ESTIMATORS.len());
+            for (attack, est) in row.attacks().iter().zip(ESTIMATORS) {
+                let expected = sis
+                    .security_level_internal(est, &CostConstraint::default(), BkzSchedule::Fixed)
+                    .ok();
+                assert_eq!(attack, &expected, "{sis} under {est}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_estimate_grid_shared_cache() {
+        // Instances with the same modulus share the BKZ costs of the cost models independent of the volume
+        let shared = CostCache::new();
+        let mut unshared_hits = 0;
+        for sis in grid() {
+            let fresh = CostCache::new();
+            assert_eq!(
+                sis.estimate_all_cached(&[Estimates::BDGL16], &CostConstraint::default(), &shared),
+                sis.estimate_all_cached(&[Estimates::BDGL16], &CostConstraint::default(), &fresh)
+            );
+            unshared_hits += fresh.hits();
+        }
+        assert!(shared.hits() > unshared_hits);
+    }
+
+    #[test]
+    fn test_estimate_table_csv() {
+        let table = estimate_grid(grid(), &ESTIMATORS);
+        let mut csv = Vec::new();
+        table.to_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 1 + 9);
+        assert_eq!(
+            lines[0],
+            "label,h,w,q,length_bound,norm,kyber-classical,matzov-classical,best_estimator,best_attack"
+        );
+        // Labels contain a comma, so they are quoted
+        assert!(lines[1].starts_with("\"n=128, q=2^20\",128,512,1048576,65536,L2,"));
+        for (line, row) in lines[1..].iter().zip(table.rows()) {
+            let best = row.best().unwrap();
+            let expected_suffix = format!(
+                "{:.2},{:.2},{},{}",
+                row.attacks()[0].as_ref().unwrap().log2_cost_time,
+                row.attacks()[1].as_ref().unwrap().log2_cost_time,
+                best.estimate.name(),
+                csv_escape(&best.attack.to_string())
+            );
+            assert!(line.ends_with(&expected_suffix), "{line}");
+        }
+
+        let markdown = table.to_markdown();
+        println!("{markdown}");
+        assert_eq!(markdown.lines().count(), 2 + 9);
+        assert!(markdown.contains("| n=128, q=2^20 | 128 | 512 |"));
+    }
+
+    #[test]
+    fn test_csv_escape() {
+        assert_eq!(csv_escape("plain"), "plain");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
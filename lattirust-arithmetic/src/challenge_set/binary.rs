@@ -1,4 +1,9 @@
-use crate::ring::Ring;
+use bitter::BitReader;
+
+use crate::challenge_set::ChallengeSet;
+use crate::ring::pow2_cyclotomic_poly_ring::Pow2CyclotomicPolyRing;
+use crate::ring::pow2_cyclotomic_poly_ring_ntt::Pow2CyclotomicPolyRingNTT;
+use crate::ring::{NttRing, Ring};
 use crate::traits::FromRandomBytes;
 
 pub struct BinaryChallengeSet<R> {
@@ -19,3 +24,211 @@ impl<F: Ring> FromRandomBytes<F> for BinaryChallengeSet<F> {
         Some(F::from(v))
     }
 }
+
+impl<F: Ring> ChallengeSet<F> for BinaryChallengeSet<F> {
+    fn set_size_log2() -> f64 {
+        1.
+    }
+
+    fn linf_bound() -> u128 {
+        1
+    }
+
+    fn operator_norm_bound() -> f64 {
+        1.
+    }
+
+    fn difference_invertible() -> bool {
+        true
+    }
+}
+
+/// Challenge set of polynomials in $\mathbb{Z}\_q\[X\]/(X^N+1)$ with independent and uniformly random coefficients in $\\{0, 1\\}$, i.e., of subset sums of $\\{X^i : 0 \leq i < N\\}$, of size $2^N$.
+/// Challenges have $\ell\_1$ norm at most $N$, and hence operator norm at most $N$. Multiplying by a challenge only needs additions, see [`BinaryPolyChallengeSet::mul_by_challenge`].
+pub struct BinaryPolyChallengeSet<R> {
+    _marker: std::marker::PhantomData<R>,
+}
+
+impl<BaseRing: Ring, const N: usize> BinaryPolyChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>> {
+    /// Return `challenge * poly` using only additions and subtractions, by summing the negacyclic rotations $X^i \cdot$ `poly` for all $i$ with $c\_i = 1$.
+    /// Panics if `challenge` has coefficients not in $\\{0, 1\\}$.
+    pub fn mul_by_challenge(
+        challenge: &Pow2CyclotomicPolyRing<BaseRing, N>,
+        poly: &Pow2CyclotomicPolyRing<BaseRing, N>,
+    ) -> Pow2CyclotomicPolyRing<BaseRing, N> {
+        let coeffs = poly.coefficient_array();
+        let mut res = vec![BaseRing::zero(); N];
+        for (i, c) in challenge.coefficient_array().iter().enumerate() {
+            if c.is_zero() {
+                continue;
+            }
+            assert!(c.is_one(), "{challenge:?} is not a binary challenge");
+            // X^i * X^k = X^(i+k) = -X^(i+k-N) for i+k >= N
+            for (k, x) in coeffs.iter().enumerate() {
+                if i + k < N {
+                    res[i + k] += x;
+                } else {
+                    res[i + k - N] -= x;
+                }
+            }
+        }
+        Pow2CyclotomicPolyRing::from(res)
+    }
+}
+
+impl<BaseRing: Ring, const N: usize> FromRandomBytes<Pow2CyclotomicPolyRing<BaseRing, N>>
+    for BinaryPolyChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>>
+{
+    fn has_no_bias() -> bool {
+        true
+    }
+
+    fn needs_bytes() -> usize {
+        N.div_ceil(8)
+    }
+
+    fn try_from_random_bytes_inner(bytes: &[u8]) -> Option<Pow2CyclotomicPolyRing<BaseRing, N>> {
+        let mut bits = bitter::LittleEndianReader::new(bytes);
+        Some(Pow2CyclotomicPolyRing::from_fn(|_| {
+            BaseRing::from(bits.read_bit().unwrap())
+        }))
+    }
+}
+
+impl<BaseRing: Ring, const N: usize> ChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>>
+    for BinaryPolyChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>>
+{
+    fn set_size_log2() -> f64 {
+        N as f64
+    }
+
+    fn linf_bound() -> u128 {
+        1
+    }
+
+    /// The $\ell\_1$ norm of challenges, which is at most $N$, bounds their operator norm.
+    fn operator_norm_bound() -> f64 {
+        N as f64
+    }
+
+    /// Differences have coefficients in $\\{-1, 0, 1\\}$, which are not invertible in general, e.g., $X^{N/2} \pm 1$ is not when $X^N+1$ splits into linear factors.
+    fn difference_invertible() -> bool {
+        false
+    }
+}
+
+impl<BaseRing: NttRing<N>, const N: usize> FromRandomBytes<Pow2CyclotomicPolyRingNTT<BaseRing, N>>
+    for BinaryPolyChallengeSet<Pow2CyclotomicPolyRingNTT<BaseRing, N>>
+{
+    fn has_no_bias() -> bool {
+        true
+    }
+
+    fn needs_bytes() -> usize {
+        BinaryPolyChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>>::byte_size()
+    }
+
+    fn try_from_random_bytes_inner(bytes: &[u8]) -> Option<Pow2CyclotomicPolyRingNTT<BaseRing, N>> {
+        BinaryPolyChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>>::try_from_random_bytes(bytes)
+            .map(|x| x.into())
+    }
+}
+
+impl<BaseRing: NttRing<N>, const N: usize> ChallengeSet<Pow2CyclotomicPolyRingNTT<BaseRing, N>>
+    for BinaryPolyChallengeSet<Pow2CyclotomicPolyRingNTT<BaseRing, N>>
+{
+    fn set_size_log2() -> f64 {
+        BinaryPolyChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>>::set_size_log2()
+    }
+
+    fn linf_bound() -> u128 {
+        1
+    }
+
+    fn operator_norm_bound() -> f64 {
+        BinaryPolyChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>>::operator_norm_bound()
+    }
+
+    fn difference_invertible() -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_std::rand::Rng;
+    use ark_std::{test_rng, UniformRand};
+    use num_traits::{One, Zero};
+
+    use crate::assert_distribution;
+    use crate::ring::ntt::ntt_prime;
+    use crate::ring::{PolyRing, Zq1};
+
+    use super::*;
+
+    const Q: u64 = ntt_prime::<64>(32);
+    const N: usize = 64;
+
+    type R = Zq1<Q>;
+    type PolyR = Pow2CyclotomicPolyRing<R, N>;
+    type CS = BinaryPolyChallengeSet<PolyR>;
+
+    fn sample(rng: &mut impl Rng) -> PolyR {
+        let mut bytes = vec![0u8; CS::byte_size()];
+        rng.fill(bytes.as_mut_slice());
+        CS::try_from_random_bytes(&bytes).unwrap()
+    }
+
+    #[test]
+    fn test_byte_size() {
+        assert_eq!(CS::byte_size(), N / 8);
+        assert_eq!(
+            BinaryPolyChallengeSet::<Pow2CyclotomicPolyRing<R, 4>>::byte_size(),
+            1
+        );
+        // Each bit of the input is one coefficient
+        let mut bytes = vec![0u8; CS::byte_size()];
+        bytes[1] = 0b100;
+        let c = CS::try_from_random_bytes(&bytes).unwrap();
+        assert_eq!(c.coefficients()[10], R::one());
+        assert_eq!(c.coefficients().iter().filter(|x| !x.is_zero()).count(), 1);
+        assert!(CS::try_from_random_bytes(&bytes[1..]).is_none());
+    }
+
+    #[test]
+    fn test_unbiased() {
+        let rng = &mut test_rng();
+        let samples: Vec<PolyR> = (0..1000).map(|_| sample(rng)).collect();
+        let pmf: Vec<((usize, bool), f64)> = (0..N)
+            .flat_map(|i| [((i, false), 0.5 / N as f64), ((i, true), 0.5 / N as f64)])
+            .collect();
+        assert_distribution!(
+            samples.iter().flat_map(|c| {
+                c.coefficients().into_iter().enumerate().map(|(i, x)| {
+                    assert!(x.is_zero() || x.is_one());
+                    (i, x.is_one())
+                })
+            }),
+            pmf = &pmf
+        );
+    }
+
+    #[test]
+    fn test_mul_by_challenge() {
+        let rng = &mut test_rng();
+        for _ in 0..20 {
+            let c = sample(rng);
+            let p = PolyR::rand(rng);
+            assert_eq!(CS::mul_by_challenge(&c, &p), c * p);
+        }
+        let p = PolyR::rand(rng);
+        assert_eq!(CS::mul_by_challenge(&PolyR::zero(), &p), PolyR::zero());
+        assert_eq!(CS::mul_by_challenge(&PolyR::one(), &p), p);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mul_by_non_challenge() {
+        CS::mul_by_challenge(&(PolyR::one() + PolyR::one()), &PolyR::one());
+    }
+}
@@ -0,0 +1,307 @@
+use std::fmt;
+
+use ark_std::rand::Rng;
+use num_traits::{ToPrimitive, Zero};
+
+use crate::challenge_set::ChallengeSet;
+use crate::error::ArithmeticError;
+use crate::ring::ntt::{const_pow_mod, largest_power_of_two_dividing};
+use crate::ring::pow2_cyclotomic_poly_ring::Pow2CyclotomicPolyRing;
+use crate::ring::{Ring, Zq1};
+
+/// Whether differences of distinct challenges are invertible, see [`InvertibilityReport`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Invertibility {
+    /// All differences are invertible, by the norm criterion of [LS18].
+    Guaranteed,
+    /// The norm criterion does not apply, but no sampled difference is non-invertible.
+    NoCounterexample,
+    /// Some sampled difference is not invertible.
+    Counterexample,
+}
+
+/// Result of [`check_challenge_differences`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvertibilityReport {
+    /// Number $k$ of irreducible factors $X^{N/k} - r\_j$ of $X^N + 1$ modulo $Q$, or `None` if $Q \equiv 3 \bmod 4$, for which $X^N + 1$ does not factor in this form.
+    pub num_slots: Option<usize>,
+    /// Number of sampled pairs of distinct challenges.
+    pub samples: usize,
+    /// Number of sampled differences that are zero in some slot, i.e., that are not invertible.
+    pub non_invertible: usize,
+    pub invertibility: Invertibility,
+}
+
+impl InvertibilityReport {
+    /// Return true iff differences of distinct challenges are guaranteed to be invertible, such that the (challenge set, modulus) pair is safe to use in extraction arguments.
+    pub fn is_safe(&self) -> bool {
+        self.invertibility == Invertibility::Guaranteed
+    }
+}
+
+impl fmt::Display for InvertibilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.num_slots {
+            Some(k) => write!(f, "X^N+1 splits into {k} factors, ")?,
+            None => write!(f, "X^N+1 does not split into binomials, ")?,
+        }
+        write!(
+            f,
+            "{}/{} sampled differences are not invertible: ",
+            self.non_invertible, self.samples
+        )?;
+        match self.invertibility {
+            Invertibility::Guaranteed => write!(f, "safe"),
+            Invertibility::NoCounterexample => write!(f, "unsafe (no guarantee)"),
+            Invertibility::Counterexample => write!(f, "unsafe (counterexample found)"),
+        }
+    }
+}
+
+/// Return the number $k$ of irreducible factors of $X^N + 1$ modulo the prime $Q$.
+/// By [LS18, Theorem 2.3], if $Q \equiv 2k'+1 \bmod 4k'$ for a power of two $k' > 1$, then $X^N + 1$ factors into $k = \min(k', N)$ irreducible binomials $X^{N/k} - r\_j$, where the $r\_j$ are the primitive $2k$-th roots of unity modulo $Q$.
+/// Returns `None` if $Q \equiv 3 \bmod 4$.
+pub fn num_slots<const Q: u64, const N: usize>() -> Option<usize> {
+    num_slots_for(Q, N)
+}
+
+/// Return the number of irreducible factors of $X^n + 1$ modulo the prime $q$, see [`num_slots`].
+fn num_slots_for(q: u64, n: usize) -> Option<usize> {
+    let two_adicity = largest_power_of_two_dividing(q - 1);
+    (two_adicity >= 2).then(|| n.min(1 << (two_adicity - 1)))
+}
+
+/// Return true iff every $y$ with $0 < \\|y\\|\_\infty \leq$ `linf_bound` or $0 < \\|y\\|\_2 \leq$ `l2_bound` is invertible, by [LS18, Corollary 1.2] for $k$ slots modulo $q$.
+fn norm_criterion(q: u64, k: usize, linf_bound: f64, l2_bound: f64) -> bool {
+    let bound = (q as f64).powf(1. / k as f64);
+    linf_bound < bound / (k as f64).sqrt() || l2_bound < bound
+}
+
+/// Return true iff every $y \in \mathbb{Z}\_q\[X\]/(X^N+1)$ with $0 < \\|y\\|\_\infty \leq$ `linf_bound` or $0 < \\|y\\|\_2 \leq$ `l2_bound` is invertible by [LS18, Corollary 1.2], where $q$ is the modulus of `BaseRing`.
+/// Returns false if the criterion does not apply, i.e., if $q$ is not a prime that fits into a `u64`, or if $q \equiv 3 \bmod 4$.
+pub fn small_elements_invertible<BaseRing: Ring, const N: usize>(
+    linf_bound: f64,
+    l2_bound: f64,
+) -> bool {
+    let Some(q) = BaseRing::modulus()
+        .to_u64()
+        .filter(|&q| const_primes::is_prime(q))
+    else {
+        return false;
+    };
+    num_slots_for(q, N).is_some_and(|k| norm_criterion(q, k, linf_bound, l2_bound))
+}
+
+/// Return the primitive $2k$-th roots of unity modulo $Q$, i.e., the odd powers of one of them.
+fn slot_roots<const Q: u64>(k: usize) -> Vec<Zq1<Q>> {
+    let k = k as u64;
+    // x^((Q-1)/2k) is a primitive 2k-th root of unity iff its k-th power is -1, which holds for half of all x
+    let w = (2..Q)
+        .map(|x| const_pow_mod::<Q>(x, (Q - 1) / (2 * k)))
+        .find(|&w| const_pow_mod::<Q>(w, k) == Q - 1)
+        .unwrap();
+    (0..k)
+        .map(|j| Zq1::<Q>::try_from(const_pow_mod::<Q>(w, 2 * j + 1)).unwrap())
+        .collect()
+}
+
+/// Return true iff `c` is zero in some slot $\mathbb{Z}\_Q\[X\]/(X^{N/k} - r\_j)$, i.e., iff `c` is not invertible.
+fn has_zero_slot<const Q: u64, const N: usize>(
+    c: &Pow2CyclotomicPolyRing<Zq1<Q>, N>,
+    roots: &[Zq1<Q>],
+) -> bool {
+    let coeffs = c.coefficient_array();
+    let d = N / roots.len();
+    // Reduce modulo X^d - r by substituting r for X^d
+    roots.iter().any(|r| {
+        (0..d).all(|s| {
+            coeffs[s..]
+                .iter()
+                .step_by(d)
+                .rev()
+                .fold(Zq1::<Q>::zero(), |acc, c| acc * r + c)
+                .is_zero()
+        })
+    })
+}
+
+/// Check whether differences of distinct challenges from `C` are invertible in $\mathbb{Z}\_Q\[X\]/(X^N+1)$, which is required by knowledge extractors of special-sound protocols.
+///
+/// Since $\mathbb{Z}\_Q\[X\]/(X^N+1)$ is isomorphic to the product of the slots $\mathbb{Z}\_Q\[X\]/(X^{N/k} - r\_j)$ (see [`num_slots`]), an element is invertible iff it is non-zero in every slot. This function samples `samples` pairs of distinct challenges and counts the differences with a zero slot.
+/// Additionally, by [LS18, Corollary 1.2], every $y$ with $0 < \\|y\\|\_\infty < q^{1/k} / \sqrt{k}$ or $0 < \\|y\\|\_2 < q^{1/k}$ is invertible. Differences of challenges have $\ell\_\infty$ norm at most $2 \cdot$ [`ChallengeSet::linf_bound`] and $\ell\_2$ norm at most $2 \cdot$ [`ChallengeSet::operator_norm_bound`] (since $\\|c\\|\_2 = \\|c \cdot 1\\|\_2$), which yields the guarantee for, e.g., monomial challenges and ternary challenges of small weight, when $X^N+1$ splits into few factors.
+///
+/// Returns [`ArithmeticError::InvalidParameters`] if `C` has a single element, such that there are no distinct challenges to sample.
+pub fn check_challenge_differences<
+    const Q: u64,
+    const N: usize,
+    C: ChallengeSet<Pow2CyclotomicPolyRing<Zq1<Q>, N>>,
+>(
+    samples: usize,
+    rng: &mut impl Rng,
+) -> Result<InvertibilityReport, ArithmeticError> {
+    // A min-entropy of 0 means that a single challenge has probability 1
+    if C::set_size_log2() <= 0. {
+        return Err(ArithmeticError::InvalidParameters(
+            "the challenge set has fewer than 2 elements".to_string(),
+        ));
+    }
+
+    let num_slots = num_slots::<Q, N>();
+
+    let guaranteed = num_slots.is_some_and(|k| {
+        norm_criterion(
+            Q,
+            k,
+            2. * C::linf_bound() as f64,
+            2. * C::operator_norm_bound(),
+        )
+    });
+
+    let roots = num_slots.map_or(vec![], slot_roots::<Q>);
+
+    let mut bytes = vec![0u8; C::byte_size()];
+    let mut sample = |rng: &mut _| loop {
+        Rng::fill(rng, bytes.as_mut_slice());
+        if let Some(c) = C::sample_from_bytes(&bytes) {
+            return c;
+        }
+    };
+    let mut num_samples = 0;
+    let mut non_invertible = 0;
+    if !roots.is_empty() {
+        while num_samples < samples {
+            let (a, b) = (sample(rng), sample(rng));
+            if a == b {
+                continue;
+            }
+            num_samples += 1;
+            non_invertible += has_zero_slot(&(a - b), &roots) as usize;
+        }
+    }
+
+    Ok(InvertibilityReport {
+        num_slots,
+        samples: num_samples,
+        non_invertible,
+        invertibility: if guaranteed {
+            Invertibility::Guaranteed
+        } else if non_invertible > 0 {
+            Invertibility::Counterexample
+        } else {
+            Invertibility::NoCounterexample
+        },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use ark_std::{test_rng, UniformRand};
+
+    use crate::challenge_set::monomial::MonomialChallengeSet;
+    use crate::challenge_set::subset::SubsetChallengeSet;
+    use crate::challenge_set::weighted_ternary::{
+        FixedWeightTernaryChallengeSet, WeightedTernaryPolyChallengeSet,
+    };
+    use crate::ring::ntt::ntt_prime;
+    use crate::ring::pow2_cyclotomic_poly_ring_ntt::Pow2CyclotomicPolyRingNTT;
+
+    use super::*;
+
+    /// Splits $X^{64}+1$ into linear factors
+    const Q_FULL: u64 = ntt_prime::<64>(32);
+    /// Splits $X^{64}+1$ into 2 factors, since $Q \equiv 5 \bmod 8$
+    const Q_TWO: u64 = 4294967197;
+    const N: usize = 64;
+
+    #[test]
+    fn test_num_slots() {
+        assert_eq!(num_slots::<Q_FULL, N>(), Some(N));
+        assert_eq!(num_slots::<Q_TWO, N>(), Some(2));
+        assert_eq!(num_slots::<17, 4>(), Some(4));
+        assert_eq!(num_slots::<17, 16>(), Some(8));
+        assert_eq!(num_slots::<7, 4>(), None);
+    }
+
+    #[test]
+    fn test_zero_slot_matches_ntt() {
+        type R = Zq1<Q_FULL>;
+        let rng = &mut test_rng();
+        let roots = slot_roots::<Q_FULL>(N);
+        let c = Pow2CyclotomicPolyRing::<R, N>::rand(rng);
+        assert!(!has_zero_slot(&c, &roots));
+        // Zero out one slot of the NTT
+        let mut ntt = Pow2CyclotomicPolyRingNTT::<R, N>::from(c).ntt_values();
+        ntt[17] = R::zero();
+        let c = Pow2CyclotomicPolyRing::<R, N>::from(
+            Pow2CyclotomicPolyRingNTT::<R, N>::from_ntt_array(ntt.try_into().unwrap()),
+        );
+        assert!(has_zero_slot(&c, &roots));
+    }
+
+    #[test]
+    fn test_fully_splitting_ternary() {
+        let rng = &mut test_rng();
+        let report = check_challenge_differences::<
+            Q_FULL,
+            N,
+            FixedWeightTernaryChallengeSet<Pow2CyclotomicPolyRing<Zq1<Q_FULL>, N>, 31>,
+        >(100, rng)
+        .unwrap();
+        println!("{report}");
+        assert_eq!(report.num_slots, Some(N));
+        assert!(!report.is_safe());
+
+        // For tiny moduli, non-invertible differences are frequent
+        let report = check_challenge_differences::<
+            17,
+            8,
+            WeightedTernaryPolyChallengeSet<Pow2CyclotomicPolyRing<Zq1<17>, 8>>,
+        >(100, rng)
+        .unwrap();
+        println!("{report}");
+        assert_eq!(report.invertibility, Invertibility::Counterexample);
+        assert!(report.non_invertible > 0);
+    }
+
+    #[test]
+    fn test_two_splitting() {
+        let rng = &mut test_rng();
+        let report = check_challenge_differences::<
+            Q_TWO,
+            N,
+            FixedWeightTernaryChallengeSet<Pow2CyclotomicPolyRing<Zq1<Q_TWO>, N>, 31>,
+        >(100, rng)
+        .unwrap();
+        println!("{report}");
+        assert!(report.is_safe());
+        assert_eq!(report.non_invertible, 0);
+
+        let report = check_challenge_differences::<
+            Q_TWO,
+            N,
+            MonomialChallengeSet<Pow2CyclotomicPolyRing<Zq1<Q_TWO>, N>>,
+        >(100, rng)
+        .unwrap();
+        assert!(report.is_safe());
+        // Monomials are not covered by the guarantee for fully splitting moduli, but differences are still invertible
+        let report = check_challenge_differences::<
+            Q_FULL,
+            N,
+            MonomialChallengeSet<Pow2CyclotomicPolyRing<Zq1<Q_FULL>, N>>,
+        >(100, rng)
+        .unwrap();
+        assert_eq!(report.invertibility, Invertibility::NoCounterexample);
+    }
+
+    #[test]
+    fn test_single_challenge() {
+        let rng = &mut test_rng();
+        // All N positions are set, so the set is {1 + X + ... + X^7}
+        let report = check_challenge_differences::<
+            Q_TWO,
+            8,
+            SubsetChallengeSet<Pow2CyclotomicPolyRing<Zq1<Q_TWO>, 8>, 8>,
+        >(100, rng);
+        assert!(matches!(report, Err(ArithmeticError::InvalidParameters(_))));
+    }
+}
@@ -1,5 +1,7 @@
 use bitter::BitReader;
 
+use crate::challenge_set::invertibility::small_elements_invertible;
+use crate::challenge_set::ChallengeSet;
 use crate::linear_algebra::Matrix;
 use crate::ring::pow2_cyclotomic_poly_ring::Pow2CyclotomicPolyRing;
 use crate::ring::pow2_cyclotomic_poly_ring_ntt::Pow2CyclotomicPolyRingNTT;
@@ -14,7 +16,7 @@ pub struct LabradorChallengeSet<R: PolyRing> {
 /// There are more than 2^128 such elements, and they all have l2-norm 71.
 /// In addition, rejection sampling is used to restrict to challenges with operator norm at most 15.
 /// On average, 6 elements need to be sampled to get some $c$ with $|c|_\textrm{op} < 15$.
-/// Differences of distinct challenges are invertible.
+/// Differences of distinct challenges are invertible if $X^{64}+1$ splits into few factors modulo a prime $q$, see [`LabradorChallengeSet::difference_invertible`].
 impl<R: PolyRing> LabradorChallengeSet<R> {
     pub type Field = R;
     pub type BaseRing = R::BaseRing;
@@ -35,6 +37,17 @@ impl<R: PolyRing> LabradorChallengeSet<R> {
     pub const VARIANCE_SUM_COEFFS: f64 = (Self::NUM_PM_ONES + 2 * Self::NUM_PM_TWOS) as f64; // 51
 }
 
+impl<BaseRing: Ring, const N: usize> LabradorChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>> {
+    /// Return true iff differences of distinct challenges are invertible by the norm criterion of [LS18, Corollary 1.2], for the modulus of `BaseRing` and the number of factors of $X^N+1$ modulo it.
+    /// Differences have $\ell\_\infty$ norm at most 4 and $\ell\_2$ norm at most $2 \sqrt{71}$, so for $k$ factors this holds iff $4 < q^{1/k} / \sqrt{k}$ or $2 \sqrt{71} < q^{1/k}$, e.g., for primes $q \equiv 5 \bmod 8$ above 32 ($k = 2$), but not for fully splitting primes. It never holds for composite moduli such as $2^{64}$.
+    pub fn difference_invertible() -> bool {
+        small_elements_invertible::<BaseRing, N>(
+            2. * Self::LINF_NORM as f64,
+            2. * Self::L2_NORM_SQUARED.sqrt(),
+        )
+    }
+}
+
 impl<BaseRing: Ring, const N: usize> LabradorChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>> {
     const CUTOFF_OPERATOR_NORM_REJECTION_SAMPLES: usize = 64;
     // TODO: find a value with a solid theoretical justification
@@ -169,6 +182,33 @@ impl<BaseRing: Ring, const N: usize> FromRandomBytes<Pow2CyclotomicPolyRing<Base
     }
 }
 
+impl<BaseRing: Ring, const N: usize> ChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>>
+    for LabradorChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>>
+{
+    /// $\log\_2$ of the number of challenges before rejection sampling on the operator norm, i.e., $\binom{64}{23, 31, 10} 2^{41}$, divided by the expected number of samples (6).
+    fn set_size_log2() -> f64 {
+        let log2_factorial = |n: usize| (1..=n).map(|i| (i as f64).log2()).sum::<f64>();
+        log2_factorial(Self::NUM_COEFFS)
+            - log2_factorial(Self::NUM_ZEROS)
+            - log2_factorial(Self::NUM_PM_ONES)
+            - log2_factorial(Self::NUM_PM_TWOS)
+            + (Self::NUM_PM_ONES + Self::NUM_PM_TWOS) as f64
+            - 6f64.log2()
+    }
+
+    fn linf_bound() -> u128 {
+        Self::LINF_NORM
+    }
+
+    fn operator_norm_bound() -> f64 {
+        Self::OPERATOR_NORM_THRESHOLD
+    }
+
+    fn difference_invertible() -> bool {
+        LabradorChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>>::difference_invertible()
+    }
+}
+
 impl<BaseRing: Ring, const N: usize> LabradorChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>> {
     fn challenge_to_matrix(c: &[i8]) -> Matrix<f64> {
         assert_eq!(c.len(), N);
@@ -210,17 +250,39 @@ impl<BaseRing: NttRing<N>, const N: usize> FromRandomBytes<Pow2CyclotomicPolyRin
     }
 }
 
+impl<BaseRing: NttRing<N>, const N: usize> ChallengeSet<Pow2CyclotomicPolyRingNTT<BaseRing, N>>
+    for LabradorChallengeSet<Pow2CyclotomicPolyRingNTT<BaseRing, N>>
+{
+    fn set_size_log2() -> f64 {
+        LabradorChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>>::set_size_log2()
+    }
+
+    fn linf_bound() -> u128 {
+        Self::LINF_NORM
+    }
+
+    fn operator_norm_bound() -> f64 {
+        Self::OPERATOR_NORM_THRESHOLD
+    }
+
+    fn difference_invertible() -> bool {
+        LabradorChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>>::difference_invertible()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use ark_std::rand::{thread_rng, Rng};
     use ark_std::{test_rng, UniformRand};
     use num_traits::ToPrimitive;
 
+    use crate::challenge_set::ChallengeSet;
     use crate::linear_algebra::Matrix;
     use crate::linear_algebra::Vector;
     use crate::ring::ntt::ntt_prime;
     use crate::ring::pow2_cyclotomic_poly_ring::Pow2CyclotomicPolyRing;
-    use crate::ring::{PolyRing, Zq1};
+    use crate::ring::pow2_cyclotomic_poly_ring_ntt::Pow2CyclotomicPolyRingNTT;
+    use crate::ring::{PolyRing, Zq1, Z2_64};
     use crate::traits::{FromRandomBytes, WithL2Norm};
 
     use super::LabradorChallengeSet;
@@ -333,4 +395,19 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_difference_invertible() {
+        // X^64+1 splits into 64 linear factors modulo Q, so the norm criterion does not apply
+        assert!(!LabCS::difference_invertible());
+        assert!(
+            !<LabradorChallengeSet<Pow2CyclotomicPolyRingNTT<R, D>> as ChallengeSet<_>>::difference_invertible()
+        );
+        // X^64+1 splits into 2 factors modulo 4294967197 = 5 mod 8
+        assert!(<LabradorChallengeSet<
+            Pow2CyclotomicPolyRing<Zq1<4294967197>, D>,
+        > as ChallengeSet<_>>::difference_invertible());
+        // 2^64 is not a prime
+        assert!(!LabradorChallengeSet::<Pow2CyclotomicPolyRing<Z2_64, D>>::difference_invertible());
+    }
 }
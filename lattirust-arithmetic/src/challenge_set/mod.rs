@@ -1,5 +1,286 @@
+use crate::ring::Ring;
+use crate::traits::FromRandomBytes;
+
 pub mod binary;
+pub mod invertibility;
 pub mod labrador_challenge_set;
+pub mod monomial;
+pub mod subset;
 pub mod ternary;
 pub mod weighted_ternary;
 pub mod z_q;
+
+/// A distribution of verifier challenges in `R`, together with the metadata needed to compute the soundness of protocols using it.
+/// Sampling is inherited from [`FromRandomBytes`], such that [`FromRandomBytes::byte_size`] is the number of bytes needed to sample a challenge.
+pub trait ChallengeSet<R: Ring>: FromRandomBytes<R> {
+    /// Return a challenge sampled from `bytes`, or `None` if `bytes` is too short or an invalid encoding.
+    fn sample_from_bytes(bytes: &[u8]) -> Option<R> {
+        Self::try_from_random_bytes(bytes)
+    }
+
+    /// Return the min-entropy of the distribution, i.e., $-\log\_2$ of the maximal probability of a challenge.
+    /// For uniform distributions, this is $\log\_2$ of the size of the set.
+    fn set_size_log2() -> f64;
+
+    /// Return a bound on the $\ell\_\infty$ norm of (the coefficients of) challenges.
+    fn linf_bound() -> u128;
+
+    /// Return a bound on the $\ell\_2$ operator norm of challenges, i.e., on $\\|c x\\|\_2 / \\|x\\|\_2$ for all non-zero $x \in R$.
+    fn operator_norm_bound() -> f64;
+
+    /// Return true iff the differences of any two distinct challenges are invertible in `R`, as required by extractors of special-sound protocols.
+    fn difference_invertible() -> bool;
+}
+
+/// Return $\log\_2$ of the knowledge error of `num_repetitions` parallel repetitions of a 2-special-sound protocol with challenges sampled from `C`, i.e., $\log\_2 \left( 2^{-\texttt{set\\_size\\_log2}} \right)^\texttt{num\\_repetitions}$.
+/// If differences of challenges are not invertible (see [`ChallengeSet::difference_invertible`]), the extracted witness is only a relaxed witness, up to multiplication by such a difference.
+pub fn knowledge_error_log2<R: Ring, C: ChallengeSet<R>>(num_repetitions: usize) -> f64 {
+    -(num_repetitions as f64) * C::set_size_log2()
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use ark_std::rand::Rng;
+    use ark_std::test_rng;
+
+    use crate::challenge_set::binary::{BinaryChallengeSet, BinaryPolyChallengeSet};
+    use crate::challenge_set::labrador_challenge_set::LabradorChallengeSet;
+    use crate::challenge_set::monomial::MonomialChallengeSet;
+    use crate::challenge_set::subset::SubsetChallengeSet;
+    use crate::challenge_set::ternary::TernaryChallengeSet;
+    use crate::challenge_set::weighted_ternary::{
+        FixedWeightTernaryChallengeSet, WeightedTernaryChallengeSet,
+        WeightedTernaryPolyChallengeSet,
+    };
+    use crate::ring::ntt::ntt_prime;
+    use crate::ring::pow2_cyclotomic_poly_ring::Pow2CyclotomicPolyRing;
+    use crate::ring::pow2_cyclotomic_poly_ring_ntt::Pow2CyclotomicPolyRingNTT;
+    use crate::ring::{NttRing, PolyRing, Zq1, Z2};
+
+    use super::*;
+
+    const Q: u64 = ntt_prime::<64>(32);
+
+    type R = Zq1<Q>;
+    type PolyR<const N: usize> = Pow2CyclotomicPolyRing<R, N>;
+
+    /// Tolerance for comparing min-entropies.
+    const TOLERANCE: f64 = 0.01;
+
+    /// Return `x` as an integer in $\[-2, 2\]$.
+    fn small(x: &R) -> i8 {
+        (-2i8..=2)
+            .find(|&v| {
+                let abs = R::try_from(v.unsigned_abs()).unwrap();
+                *x == if v < 0 { -abs } else { abs }
+            })
+            .unwrap()
+    }
+
+    fn small_coeffs<const N: usize>(c: &PolyR<N>) -> Vec<i8> {
+        c.coefficients().iter().map(small).collect()
+    }
+
+    /// Return the operator norm of `c`, computed from its multiplication matrix.
+    fn operator_norm<const N: usize>(c: &PolyR<N>) -> f64 {
+        LabradorChallengeSet::<PolyR<N>>::operator_norm(&small_coeffs(c))
+    }
+
+    /// Check the metadata of `C` against the empirical distribution `samples` of challenges, their norms given by `norms`, and the invertibility of their differences given by `invertible`.
+    fn check<T: Ring, C: ChallengeSet<T>>(
+        samples: &[T],
+        norms: impl Fn(&T) -> (u128, f64),
+        invertible: impl Fn(T) -> bool,
+    ) {
+        let mut counts = HashMap::<T, usize>::new();
+        for c in samples {
+            let (linf, op) = norms(c);
+            assert!(linf <= C::linf_bound(), "{c} has linf norm {linf}");
+            assert!(
+                op <= C::operator_norm_bound() + 1e-9,
+                "{c} has operator norm {op}"
+            );
+            *counts.entry(*c).or_default() += 1;
+        }
+        let max_count = *counts.values().max().unwrap();
+        let min_entropy = -(max_count as f64 / samples.len() as f64).log2();
+        assert!(
+            (min_entropy - C::set_size_log2()).abs() < TOLERANCE,
+            "{min_entropy} != {}",
+            C::set_size_log2()
+        );
+        assert!(
+            (knowledge_error_log2::<T, C>(3) + 3. * min_entropy).abs() < 3. * TOLERANCE,
+            "{}",
+            knowledge_error_log2::<T, C>(3)
+        );
+        if C::difference_invertible() {
+            let distinct: Vec<_> = counts.keys().collect();
+            for (i, a) in distinct.iter().enumerate() {
+                for b in &distinct[..i] {
+                    assert!(invertible(**a - **b), "{a} - {b} is not invertible");
+                }
+            }
+        }
+    }
+
+    /// Return the challenges encoded by all values of the last byte, i.e., the exact distribution of challenges sampled from a single (unbiased) byte.
+    fn all_last_bytes<T, C: FromRandomBytes<T>>() -> Vec<T> {
+        (0..=255u8)
+            .map(|b| {
+                let mut bytes = vec![0u8; C::byte_size()];
+                *bytes.last_mut().unwrap() = b;
+                C::try_from_random_bytes(&bytes).unwrap()
+            })
+            .collect()
+    }
+
+    fn random_samples<T, C: FromRandomBytes<T>>(n: usize) -> Vec<T> {
+        let rng = &mut test_rng();
+        let mut bytes = vec![0u8; C::byte_size()];
+        (0..n)
+            .map(|_| {
+                rng.fill(bytes.as_mut_slice());
+                C::try_from_random_bytes(&bytes).unwrap()
+            })
+            .collect()
+    }
+
+    fn scalar_norms(c: &R) -> (u128, f64) {
+        let abs = small(c).unsigned_abs();
+        (abs as u128, abs as f64)
+    }
+
+    fn scalar_invertible(x: R) -> bool {
+        x.inverse().is_some()
+    }
+
+    fn poly_invertible<const N: usize>(x: PolyR<N>) -> bool
+    where
+        R: NttRing<N>,
+    {
+        Pow2CyclotomicPolyRingNTT::<R, N>::from(x)
+            .inverse()
+            .is_some()
+    }
+
+    fn poly_norms<const N: usize>(c: &PolyR<N>) -> (u128, f64) {
+        let linf = small_coeffs(c).iter().map(|v| v.unsigned_abs()).max();
+        (linf.unwrap() as u128, operator_norm(c))
+    }
+
+    #[test]
+    fn test_scalar_challenge_sets() {
+        check::<R, BinaryChallengeSet<R>>(
+            &all_last_bytes::<R, BinaryChallengeSet<R>>(),
+            scalar_norms,
+            scalar_invertible,
+        );
+        check::<R, TernaryChallengeSet<R>>(
+            &all_last_bytes::<R, TernaryChallengeSet<R>>(),
+            scalar_norms,
+            scalar_invertible,
+        );
+        check::<R, WeightedTernaryChallengeSet<R>>(
+            &all_last_bytes::<R, WeightedTernaryChallengeSet<R>>(),
+            scalar_norms,
+            scalar_invertible,
+        );
+        // Differences are not invertible modulo 2
+        assert!(!TernaryChallengeSet::<Z2>::difference_invertible());
+    }
+
+    #[test]
+    fn test_weighted_ternary_poly() {
+        type CS = WeightedTernaryPolyChallengeSet<PolyR<4>>;
+        // Each coefficient is determined by the two lowest bits of its byte
+        let samples: Vec<_> = (0..=255u8)
+            .map(|b| {
+                let bytes: Vec<u8> = (0..4).map(|i| (b >> (2 * i)) & 0b11).collect();
+                CS::try_from_random_bytes(&bytes).unwrap()
+            })
+            .collect();
+        check::<PolyR<4>, CS>(&samples, poly_norms, poly_invertible);
+    }
+
+    #[test]
+    fn test_fixed_weight_ternary() {
+        type CS = FixedWeightTernaryChallengeSet<PolyR<8>, 2>;
+        let samples = random_samples::<PolyR<8>, CS>(100000);
+        let distinct: std::collections::HashSet<_> = samples.iter().collect();
+        assert_eq!(distinct.len(), 8 * 7 / 2 * 4);
+        // The empirical maximal probability overestimates 2^(-set_size_log2) on this number of samples, so only check the norms and the support
+        assert!((CS::set_size_log2() - (distinct.len() as f64).log2()).abs() < 1e-9);
+        for c in &samples {
+            let (linf, op) = poly_norms(c);
+            assert!(linf <= CS::linf_bound());
+            assert!(op <= CS::operator_norm_bound());
+        }
+    }
+
+    #[test]
+    fn test_binary_poly() {
+        type CS = BinaryPolyChallengeSet<PolyR<8>>;
+        // Each byte encodes a distinct challenge
+        let samples: Vec<_> = (0..=255u8)
+            .map(|b| CS::try_from_random_bytes(&[b]).unwrap())
+            .collect();
+        check::<PolyR<8>, CS>(&samples, poly_norms, poly_invertible);
+        type CSNtt = BinaryPolyChallengeSet<Pow2CyclotomicPolyRingNTT<R, 8>>;
+        assert_eq!(CSNtt::set_size_log2(), CS::set_size_log2());
+    }
+
+    #[test]
+    fn test_subset() {
+        type CS = SubsetChallengeSet<PolyR<8>, 3>;
+        let samples = random_samples::<PolyR<8>, CS>(100000);
+        let distinct: std::collections::HashSet<_> = samples.iter().collect();
+        assert_eq!(distinct.len(), 8 * 7 * 6 / 6);
+        // As for fixed-weight ternary challenges, only check the norms and the support
+        assert!((CS::set_size_log2() - (distinct.len() as f64).log2()).abs() < 1e-9);
+        for c in &samples {
+            let (linf, op) = poly_norms(c);
+            assert!(linf <= CS::linf_bound());
+            assert!(op <= CS::operator_norm_bound());
+        }
+    }
+
+    #[test]
+    fn test_monomial() {
+        type CS = MonomialChallengeSet<PolyR<8>>;
+        // Attempts are accepted iff their value (modulo 32) is at most 16, so enumerating the first attempt covers each challenge equally often
+        let samples: Vec<_> = (0..=255u8)
+            .filter(|b| b & 31 <= 16)
+            .map(|b| {
+                let mut bytes = vec![0xffu8; CS::byte_size()];
+                bytes[0] = b;
+                CS::try_from_random_bytes(&bytes).unwrap()
+            })
+            .collect();
+        check::<PolyR<8>, CS>(&samples, poly_norms, poly_invertible);
+        type CSNtt = MonomialChallengeSet<Pow2CyclotomicPolyRingNTT<R, 8>>;
+        assert_eq!(CSNtt::set_size_log2(), CS::set_size_log2());
+    }
+
+    #[test]
+    fn test_labrador() {
+        type CS = LabradorChallengeSet<PolyR<64>>;
+        let samples = random_samples::<PolyR<64>, CS>(20);
+        for c in &samples {
+            let (linf, op) = poly_norms(c);
+            assert!(linf <= CS::linf_bound());
+            assert!(op <= CS::operator_norm_bound());
+        }
+        // Q splits X^64+1 fully, so invertibility is not guaranteed, but random differences are invertible with high probability
+        assert!(!CS::difference_invertible());
+        for (i, a) in samples.iter().enumerate() {
+            for b in &samples[..i] {
+                assert!(poly_invertible(*a - *b));
+            }
+        }
+        assert!(CS::set_size_log2() > 125.);
+        assert!(knowledge_error_log2::<PolyR<64>, CS>(1) < -125.);
+    }
+}
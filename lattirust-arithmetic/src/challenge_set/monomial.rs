@@ -0,0 +1,248 @@
+use num_traits::Zero;
+
+use crate::challenge_set::ChallengeSet;
+use crate::ring::pow2_cyclotomic_poly_ring::Pow2CyclotomicPolyRing;
+use crate::ring::pow2_cyclotomic_poly_ring_ntt::Pow2CyclotomicPolyRingNTT;
+use crate::ring::{NttRing, Ring};
+use crate::traits::FromRandomBytes;
+
+/// Challenge set $\\{0\\} \cup \\{\pm X^i : 0 \leq i < N\\} \subset \mathbb{Z}\_q\[X\]/(X^N+1)$ of size $2N+1$, sampled uniformly.
+/// Multiplying by a challenge is a negacyclic rotation (see [`MonomialChallengeSet::mul_by_challenge`]) and preserves all norms, i.e., challenges have operator norm 1 and differences of challenges have operator norm at most 2.
+/// Moreover, $2/(X^i - X^j)$ has coefficients in $\\{-1, 0, 1\\}$ for $i \neq j$ [BCK+14], so that differences of challenges are invertible up to a factor 2.
+pub struct MonomialChallengeSet<R> {
+    _marker: std::marker::PhantomData<R>,
+}
+
+impl<BaseRing: Ring, const N: usize> MonomialChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>> {
+    /// $\log\_2$ of the probability that [`FromRandomBytes::try_from_random_bytes`] fails on uniformly random bytes.
+    pub const LOG2_FAILURE_PROBABILITY: f64 = -40.;
+
+    pub fn set_size() -> usize {
+        2 * N + 1
+    }
+
+    /// Bound on the operator norm of challenges, i.e., on $\\|c x\\| / \\|x\\|$.
+    pub fn operator_norm_bound() -> f64 {
+        1.
+    }
+
+    /// Number of bytes per attempt at sampling an integer in $\\{0, \ldots, 2N\\}$.
+    fn attempt_byte_size() -> usize {
+        (usize::BITS - (2 * N).leading_zeros()).div_ceil(8) as usize
+    }
+
+    /// Number of attempts such that rejection sampling fails with probability at most $2^\texttt{LOG2\\_FAILURE\\_PROBABILITY}$.
+    fn num_attempts() -> usize {
+        let m = Self::set_size();
+        let rejection_probability = 1. - m as f64 / m.next_power_of_two() as f64;
+        (Self::LOG2_FAILURE_PROBABILITY / rejection_probability.log2()).ceil() as usize
+    }
+
+    /// Return the sign and exponent of a challenge $\pm X^i$, or `None` for 0.
+    /// Panics if `challenge` is not in the challenge set.
+    fn to_monomial(challenge: &Pow2CyclotomicPolyRing<BaseRing, N>) -> Option<(bool, usize)> {
+        let coeffs = challenge.coefficient_array();
+        let mut nonzero = coeffs.iter().enumerate().filter(|(_, c)| !c.is_zero());
+        let (i, c) = nonzero.next()?;
+        assert!(
+            nonzero.next().is_none() && (c.is_one() || (-*c).is_one()),
+            "{challenge:?} is not a monomial challenge"
+        );
+        Some((!c.is_one(), i))
+    }
+
+    /// Return `challenge * poly` in $O(N)$ operations, by rotating the coefficients of `poly` negacyclically.
+    /// Panics if `challenge` is not in the challenge set.
+    pub fn mul_by_challenge(
+        challenge: &Pow2CyclotomicPolyRing<BaseRing, N>,
+        poly: &Pow2CyclotomicPolyRing<BaseRing, N>,
+    ) -> Pow2CyclotomicPolyRing<BaseRing, N> {
+        let Some((negative, i)) = Self::to_monomial(challenge) else {
+            return Pow2CyclotomicPolyRing::zero();
+        };
+        let coeffs = poly.coefficient_array();
+        // X^i * X^k = X^(i+k) = -X^(i+k-N) for i+k >= N
+        Pow2CyclotomicPolyRing::from_fn(|k| {
+            let c = if k >= i {
+                coeffs[k - i]
+            } else {
+                -coeffs[k + N - i]
+            };
+            if negative {
+                -c
+            } else {
+                c
+            }
+        })
+    }
+}
+
+impl<BaseRing: Ring, const N: usize> FromRandomBytes<Pow2CyclotomicPolyRing<BaseRing, N>>
+    for MonomialChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>>
+{
+    fn has_no_bias() -> bool {
+        true
+    }
+
+    fn needs_bytes() -> usize {
+        Self::num_attempts() * Self::attempt_byte_size()
+    }
+
+    fn try_from_random_bytes_inner(bytes: &[u8]) -> Option<Pow2CyclotomicPolyRing<BaseRing, N>> {
+        let m = Self::set_size();
+        let mask = m.next_power_of_two() - 1;
+        let v = bytes[..Self::needs_bytes()]
+            .chunks_exact(Self::attempt_byte_size())
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .rev()
+                    .fold(0usize, |acc, b| (acc << 8) | *b as usize)
+                    & mask
+            })
+            .find(|&v| v < m)?;
+        // 0 encodes 0, 1..=N encode X^0..X^(N-1) and N+1..=2N encode -X^0..-X^(N-1)
+        Some(Pow2CyclotomicPolyRing::from_fn(|k| match v {
+            0 => BaseRing::zero(),
+            _ if v - 1 == k => BaseRing::one(),
+            _ if v - 1 == N + k => -BaseRing::one(),
+            _ => BaseRing::zero(),
+        }))
+    }
+}
+
+impl<BaseRing: Ring, const N: usize> ChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>>
+    for MonomialChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>>
+{
+    fn set_size_log2() -> f64 {
+        (Self::set_size() as f64).log2()
+    }
+
+    fn linf_bound() -> u128 {
+        1
+    }
+
+    fn operator_norm_bound() -> f64 {
+        Self::operator_norm_bound()
+    }
+
+    /// Differences are $\pm X^i$, $\pm X^i (X^k + 1)$ or $\pm X^i (X^k - 1)$ for $0 < k < N$, and $2 X^i$, which are invertible iff 2 is, see [`MonomialChallengeSet`].
+    fn difference_invertible() -> bool {
+        (BaseRing::one() + BaseRing::one()).inverse().is_some()
+    }
+}
+
+impl<BaseRing: NttRing<N>, const N: usize> FromRandomBytes<Pow2CyclotomicPolyRingNTT<BaseRing, N>>
+    for MonomialChallengeSet<Pow2CyclotomicPolyRingNTT<BaseRing, N>>
+{
+    fn has_no_bias() -> bool {
+        true
+    }
+
+    fn needs_bytes() -> usize {
+        MonomialChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>>::byte_size()
+    }
+
+    fn try_from_random_bytes_inner(bytes: &[u8]) -> Option<Pow2CyclotomicPolyRingNTT<BaseRing, N>> {
+        MonomialChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>>::try_from_random_bytes(bytes)
+            .map(|x| x.into())
+    }
+}
+
+impl<BaseRing: NttRing<N>, const N: usize> ChallengeSet<Pow2CyclotomicPolyRingNTT<BaseRing, N>>
+    for MonomialChallengeSet<Pow2CyclotomicPolyRingNTT<BaseRing, N>>
+{
+    fn set_size_log2() -> f64 {
+        MonomialChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>>::set_size_log2()
+    }
+
+    fn linf_bound() -> u128 {
+        1
+    }
+
+    fn operator_norm_bound() -> f64 {
+        1.
+    }
+
+    fn difference_invertible() -> bool {
+        MonomialChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>>::difference_invertible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_std::rand::Rng;
+    use ark_std::{test_rng, UniformRand};
+    use num_traits::One;
+
+    use crate::assert_distribution;
+    use crate::ring::ntt::ntt_prime;
+    use crate::ring::Zq1;
+
+    use super::*;
+
+    const Q: u64 = ntt_prime::<64>(32);
+    const N: usize = 16;
+
+    type R = Zq1<Q>;
+    type PolyR = Pow2CyclotomicPolyRing<R, N>;
+    type CS = MonomialChallengeSet<PolyR>;
+
+    /// Return all elements of the challenge set, in the order of their encoding.
+    fn challenges() -> Vec<PolyR> {
+        (0..CS::set_size())
+            .map(|v| CS::try_from_random_bytes(&vec![v as u8; CS::byte_size()]).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_mul_by_challenge() {
+        let rng = &mut test_rng();
+        let challenges = challenges();
+        assert_eq!(challenges[0], PolyR::zero());
+        assert_eq!(challenges[1], PolyR::one());
+        assert_eq!(challenges[N + 1], -PolyR::one());
+        for c in challenges {
+            let p = PolyR::rand(rng);
+            assert_eq!(CS::mul_by_challenge(&c, &p), c * p);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mul_by_non_challenge() {
+        CS::mul_by_challenge(&(PolyR::one() + PolyR::one()), &PolyR::one());
+    }
+
+    #[test]
+    fn test_uniformity() {
+        let rng = &mut test_rng();
+        let challenges = challenges();
+        let m = CS::set_size();
+        let mut bytes = vec![0u8; CS::byte_size()];
+        let samples = (0..200 * m).map(|_| {
+            rng.fill(bytes.as_mut_slice());
+            let c = CS::try_from_random_bytes(&bytes).unwrap();
+            challenges.iter().position(|x| *x == c).unwrap()
+        });
+        let pmf: Vec<(usize, f64)> = (0..m).map(|i| (i, 1. / m as f64)).collect();
+        assert_distribution!(samples, pmf = &pmf);
+    }
+
+    #[test]
+    fn test_rejection() {
+        // Attempts are in {0, ..., 63} and accepted iff they are at most 2N = 32
+        assert_eq!(CS::set_size(), 2 * N + 1);
+        let rejection_probability = 1. - CS::set_size() as f64 / 64.;
+        assert!(rejection_probability.powi(CS::byte_size() as i32) <= 2f64.powi(-40));
+
+        let mut bytes = vec![0xff; CS::byte_size()];
+        assert!(CS::try_from_random_bytes(&bytes).is_none());
+        // The first accepted attempt is used
+        bytes[5] = 33;
+        bytes[7] = N as u8 + 1;
+        bytes[8] = 3;
+        assert_eq!(CS::try_from_random_bytes(&bytes), Some(-PolyR::one()));
+        assert!(CS::try_from_random_bytes(&bytes[1..]).is_none());
+    }
+}
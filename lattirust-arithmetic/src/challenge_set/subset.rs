@@ -0,0 +1,258 @@
+use num_bigint::BigUint;
+use num_traits::One;
+
+use crate::challenge_set::binary::BinaryPolyChallengeSet;
+use crate::challenge_set::weighted_ternary::FixedWeightTernaryChallengeSet;
+use crate::challenge_set::ChallengeSet;
+use crate::ring::pow2_cyclotomic_poly_ring::Pow2CyclotomicPolyRing;
+use crate::ring::pow2_cyclotomic_poly_ring_ntt::Pow2CyclotomicPolyRingNTT;
+use crate::ring::{NttRing, Ring};
+use crate::traits::FromRandomBytes;
+
+/// Challenge set of polynomials in $\mathbb{Z}\_q\[X\]/(X^N+1)$ with exactly `W` coefficients equal to 1 and all other coefficients 0, i.e., of sums of `W` distinct elements of $\\{X^i : 0 \leq i < N\\}$.
+/// There are $\binom{N}{W}$ such elements, they have $\ell\_1$ norm $W$, and hence operator norm at most $W$. Multiplying by a challenge takes $W N$ additions, see [`SubsetChallengeSet::mul_by_challenge`].
+///
+/// Positions are sampled as for [`FixedWeightTernaryChallengeSet`], such that [`FromRandomBytes::try_from_random_bytes`] returns `None` with probability at most $2^{-40}$.
+pub struct SubsetChallengeSet<R, const W: usize> {
+    _marker: std::marker::PhantomData<R>,
+}
+
+impl<R, const W: usize> SubsetChallengeSet<R, W> {
+    pub const WEIGHT: usize = W;
+    pub const LINF_NORM: u128 = 1;
+    pub const L1_NORM: usize = W;
+    pub const L2_NORM_SQUARED: usize = W;
+    /// Bound on the operator norm, i.e., on $\\|c x\\| / \\|x\\|$, which follows from the triangle inequality.
+    pub const OPERATOR_NORM_BOUND: f64 = W as f64;
+
+    /// $\log\_2$ of the probability that [`FromRandomBytes::try_from_random_bytes`] fails on uniformly random bytes.
+    pub const LOG2_FAILURE_PROBABILITY: f64 = -40.;
+
+    pub fn weight() -> usize {
+        W
+    }
+}
+
+impl<BaseRing: Ring, const N: usize, const W: usize>
+    SubsetChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>, W>
+{
+    /// Return $\log\_2 \binom{N}{W}$.
+    pub fn log2_set_size() -> f64 {
+        (0..W)
+            .map(|i| ((N - i) as f64).log2() - ((i + 1) as f64).log2())
+            .sum()
+    }
+
+    /// Return $\binom{N}{W}$.
+    pub fn set_size() -> BigUint {
+        (0..W).fold(BigUint::one(), |acc, i| acc * (N - i) / (i + 1))
+    }
+
+    /// Return `challenge * poly` using only additions and subtractions, see [`BinaryPolyChallengeSet::mul_by_challenge`].
+    /// Panics if `challenge` has coefficients not in $\\{0, 1\\}$.
+    pub fn mul_by_challenge(
+        challenge: &Pow2CyclotomicPolyRing<BaseRing, N>,
+        poly: &Pow2CyclotomicPolyRing<BaseRing, N>,
+    ) -> Pow2CyclotomicPolyRing<BaseRing, N> {
+        BinaryPolyChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>>::mul_by_challenge(
+            challenge, poly,
+        )
+    }
+}
+
+impl<BaseRing: Ring, const N: usize, const W: usize>
+    FromRandomBytes<Pow2CyclotomicPolyRing<BaseRing, N>>
+    for SubsetChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>, W>
+{
+    fn has_no_bias() -> bool {
+        true
+    }
+
+    fn needs_bytes() -> usize {
+        FixedWeightTernaryChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>, W>::positions_byte_size()
+    }
+
+    fn try_from_random_bytes_inner(bytes: &[u8]) -> Option<Pow2CyclotomicPolyRing<BaseRing, N>> {
+        let positions =
+            FixedWeightTernaryChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>, W>::positions_from_random_bytes(bytes)?;
+        let mut coeffs = vec![BaseRing::zero(); N];
+        for i in positions {
+            coeffs[i] = BaseRing::one();
+        }
+        Some(Pow2CyclotomicPolyRing::from(coeffs))
+    }
+}
+
+impl<BaseRing: Ring, const N: usize, const W: usize>
+    ChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>>
+    for SubsetChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>, W>
+{
+    fn set_size_log2() -> f64 {
+        Self::log2_set_size()
+    }
+
+    fn linf_bound() -> u128 {
+        Self::LINF_NORM
+    }
+
+    fn operator_norm_bound() -> f64 {
+        Self::OPERATOR_NORM_BOUND
+    }
+
+    /// Differences have coefficients in $\\{-1, 0, 1\\}$, which are not invertible in general.
+    fn difference_invertible() -> bool {
+        false
+    }
+}
+
+impl<BaseRing: NttRing<N>, const N: usize, const W: usize>
+    FromRandomBytes<Pow2CyclotomicPolyRingNTT<BaseRing, N>>
+    for SubsetChallengeSet<Pow2CyclotomicPolyRingNTT<BaseRing, N>, W>
+{
+    fn has_no_bias() -> bool {
+        true
+    }
+
+    fn needs_bytes() -> usize {
+        SubsetChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>, W>::byte_size()
+    }
+
+    fn try_from_random_bytes_inner(bytes: &[u8]) -> Option<Pow2CyclotomicPolyRingNTT<BaseRing, N>> {
+        SubsetChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>, W>::try_from_random_bytes(bytes)
+            .map(|x| x.into())
+    }
+}
+
+impl<BaseRing: NttRing<N>, const N: usize, const W: usize>
+    ChallengeSet<Pow2CyclotomicPolyRingNTT<BaseRing, N>>
+    for SubsetChallengeSet<Pow2CyclotomicPolyRingNTT<BaseRing, N>, W>
+{
+    fn set_size_log2() -> f64 {
+        SubsetChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>, W>::log2_set_size()
+    }
+
+    fn linf_bound() -> u128 {
+        Self::LINF_NORM
+    }
+
+    fn operator_norm_bound() -> f64 {
+        Self::OPERATOR_NORM_BOUND
+    }
+
+    fn difference_invertible() -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_std::rand::Rng;
+    use ark_std::{test_rng, UniformRand};
+    use num_traits::Zero;
+
+    use crate::assert_distribution;
+    use crate::ring::ntt::ntt_prime;
+    use crate::ring::{PolyRing, Zq1};
+
+    use super::*;
+
+    const Q: u64 = ntt_prime::<64>(32);
+
+    type R = Zq1<Q>;
+    type PolyR<const N: usize> = Pow2CyclotomicPolyRing<R, N>;
+    type CS<const N: usize, const W: usize> = SubsetChallengeSet<PolyR<N>, W>;
+
+    const NUM_REPETITIONS: usize = 1000;
+
+    fn sample<const N: usize, const W: usize>(rng: &mut impl Rng) -> PolyR<N> {
+        let mut bytes = vec![0u8; CS::<N, W>::byte_size()];
+        rng.fill(bytes.as_mut_slice());
+        CS::<N, W>::try_from_random_bytes(&bytes).unwrap()
+    }
+
+    /// Return the positions of the coefficients equal to 1, panicking on coefficients other than 0 and 1.
+    fn positions<const N: usize>(c: &PolyR<N>) -> Vec<usize> {
+        c.coefficients()
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| {
+                assert!(x.is_zero() || x.is_one());
+                x.is_one()
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    fn check_weight<const N: usize, const W: usize>() {
+        let rng = &mut test_rng();
+        assert_eq!(CS::<N, W>::weight(), W);
+        for _ in 0..NUM_REPETITIONS {
+            assert_eq!(positions(&sample::<N, W>(rng)).len(), W);
+        }
+    }
+
+    #[test]
+    fn test_fixed_weight() {
+        check_weight::<64, 0>();
+        check_weight::<64, 1>();
+        check_weight::<64, 31>();
+        check_weight::<64, 64>();
+        check_weight::<512, 60>();
+    }
+
+    #[test]
+    fn test_uniform_positions() {
+        let rng = &mut test_rng();
+        let samples = (0..NUM_REPETITIONS).flat_map(|_| positions(&sample::<64, 20>(rng)));
+        // Positions are sampled without replacement, which only makes the test more conservative
+        let pmf: Vec<(usize, f64)> = (0..64).map(|i| (i, 1. / 64.)).collect();
+        assert_distribution!(samples, pmf = &pmf);
+
+        // All subsets of size 2 of the 8 positions are equally likely
+        let samples = (0..NUM_REPETITIONS).map(|_| positions(&sample::<8, 2>(rng)));
+        let pmf: Vec<(Vec<usize>, f64)> = (0..8)
+            .flat_map(|i| (i + 1..8).map(move |j| (vec![i, j], 1. / 28.)))
+            .collect();
+        assert_distribution!(samples, pmf = &pmf);
+    }
+
+    #[test]
+    fn test_mul_by_challenge() {
+        let rng = &mut test_rng();
+        for _ in 0..20 {
+            let c = sample::<64, 31>(rng);
+            let p = PolyR::<64>::rand(rng);
+            assert_eq!(CS::<64, 31>::mul_by_challenge(&c, &p), c * p);
+        }
+    }
+
+    #[test]
+    fn test_byte_size() {
+        // Positions are sampled from the same budgets as for fixed-weight ternary challenges, without the sign bits
+        assert_eq!(
+            CS::<64, 31>::byte_size() + 4,
+            FixedWeightTernaryChallengeSet::<PolyR<64>, 31>::byte_size()
+        );
+        assert_eq!(
+            CS::<512, 60>::byte_size() + 8,
+            FixedWeightTernaryChallengeSet::<PolyR<512>, 60>::byte_size()
+        );
+
+        // Bytes exhausting the budget are rejected, here for the second position in {1, ..., 63}
+        let bytes = vec![0xff; CS::<64, 31>::byte_size()];
+        assert!(CS::<64, 31>::try_from_random_bytes(&bytes).is_none());
+        let mut bytes = vec![0u8; CS::<64, 31>::byte_size()];
+        test_rng().fill(bytes.as_mut_slice());
+        assert!(CS::<64, 31>::try_from_random_bytes(&bytes).is_some());
+        assert!(CS::<64, 31>::try_from_random_bytes(&bytes[1..]).is_none());
+    }
+
+    #[test]
+    fn test_set_size() {
+        assert_eq!(CS::<64, 1>::set_size(), BigUint::from(64u32));
+        assert_eq!(CS::<64, 2>::set_size(), BigUint::from(64u32 * 63 / 2));
+        assert_eq!(CS::<64, 64>::set_size(), BigUint::one());
+        let log2_size = CS::<256, 60>::log2_set_size();
+        assert!((log2_size - CS::<256, 60>::set_size().bits() as f64).abs() < 1.);
+    }
+}
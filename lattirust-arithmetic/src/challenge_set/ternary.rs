@@ -5,6 +5,7 @@ use nalgebra::Scalar;
 use num_traits::{One, Zero};
 use rayon::prelude::*;
 
+use crate::challenge_set::ChallengeSet;
 use crate::linear_algebra::Matrix;
 use crate::linear_algebra::SymmetricMatrix;
 use crate::ring::Ring;
@@ -29,6 +30,26 @@ impl<F: Ring> FromRandomBytes<F> for TernaryChallengeSet<F> {
     }
 }
 
+impl<F: Ring> ChallengeSet<F> for TernaryChallengeSet<F> {
+    /// Only the last byte is reduced modulo 3, such that the most likely challenge (0) has probability $86/256$.
+    fn set_size_log2() -> f64 {
+        (256f64 / 86.).log2()
+    }
+
+    fn linf_bound() -> u128 {
+        1
+    }
+
+    fn operator_norm_bound() -> f64 {
+        1.
+    }
+
+    /// Differences are in $\{\pm 1, \pm 2\}$, which are invertible iff 2 is.
+    fn difference_invertible() -> bool {
+        (F::one() + F::one()).inverse().is_some()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Trit {
     MinusOne,
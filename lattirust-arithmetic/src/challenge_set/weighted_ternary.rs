@@ -1,7 +1,10 @@
 use std::ops::Neg;
 
+use bitter::BitReader;
+use num_bigint::BigUint;
 use num_traits::{One, Zero};
 
+use crate::challenge_set::ChallengeSet;
 use crate::ring::pow2_cyclotomic_poly_ring::Pow2CyclotomicPolyRing;
 use crate::ring::pow2_cyclotomic_poly_ring_ntt::Pow2CyclotomicPolyRingNTT;
 use crate::ring::{NttRing, Ring};
@@ -35,6 +38,26 @@ impl<T: Zero + One + Neg<Output = T>> FromRandomBytes<T> for WeightedTernaryChal
     }
 }
 
+impl<F: Ring> ChallengeSet<F> for WeightedTernaryChallengeSet<F> {
+    /// The most likely challenge is 0, with probability 1/2.
+    fn set_size_log2() -> f64 {
+        1.
+    }
+
+    fn linf_bound() -> u128 {
+        1
+    }
+
+    fn operator_norm_bound() -> f64 {
+        1.
+    }
+
+    /// Differences are in $\{\pm 1, \pm 2\}$, which are invertible iff 2 is.
+    fn difference_invertible() -> bool {
+        (F::one() + F::one()).inverse().is_some()
+    }
+}
+
 pub struct WeightedTernaryPolyChallengeSet<R> {
     _marker: std::marker::PhantomData<R>,
 }
@@ -64,6 +87,30 @@ where
     }
 }
 
+impl<BaseRing: Ring, const N: usize> ChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>>
+    for WeightedTernaryPolyChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>>
+where
+    WeightedTernaryChallengeSet<BaseRing>: FromRandomBytes<BaseRing>,
+{
+    /// The most likely challenge is 0, with probability $2^{-N}$.
+    fn set_size_log2() -> f64 {
+        N as f64
+    }
+
+    fn linf_bound() -> u128 {
+        1
+    }
+
+    /// The $\ell\_1$ norm of challenges, which is at most $N$, bounds their operator norm.
+    fn operator_norm_bound() -> f64 {
+        N as f64
+    }
+
+    fn difference_invertible() -> bool {
+        false
+    }
+}
+
 impl<BaseRing: NttRing<N>, const N: usize> FromRandomBytes<Pow2CyclotomicPolyRingNTT<BaseRing, N>>
     for WeightedTernaryPolyChallengeSet<Pow2CyclotomicPolyRingNTT<BaseRing, N>>
 where
@@ -74,10 +121,368 @@ where
     }
 
     fn needs_bytes() -> usize {
-        Pow2CyclotomicPolyRing::<BaseRing, N>::byte_size()
+        WeightedTernaryPolyChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>>::byte_size()
+    }
+
+    fn try_from_random_bytes_inner(bytes: &[u8]) -> Option<Pow2CyclotomicPolyRingNTT<BaseRing, N>> {
+        WeightedTernaryPolyChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>>::try_from_random_bytes(
+            bytes,
+        )
+        .map(|x| x.into())
+    }
+}
+
+impl<BaseRing: NttRing<N>, const N: usize> ChallengeSet<Pow2CyclotomicPolyRingNTT<BaseRing, N>>
+    for WeightedTernaryPolyChallengeSet<Pow2CyclotomicPolyRingNTT<BaseRing, N>>
+where
+    WeightedTernaryChallengeSet<BaseRing>: FromRandomBytes<BaseRing>,
+{
+    fn set_size_log2() -> f64 {
+        WeightedTernaryPolyChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>>::set_size_log2()
+    }
+
+    fn linf_bound() -> u128 {
+        WeightedTernaryPolyChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>>::linf_bound()
+    }
+
+    fn operator_norm_bound() -> f64 {
+        WeightedTernaryPolyChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>>::operator_norm_bound(
+        )
+    }
+
+    fn difference_invertible() -> bool {
+        WeightedTernaryPolyChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>>::difference_invertible()
+    }
+}
+
+/// Challenge set of polynomials in $\mathbb{Z}\_q\[X\]/(X^N+1)$ with exactly `W` coefficients in $\\{-1, 1\\}$ and all other coefficients 0.
+/// There are $\binom{N}{W} 2^W$ such elements (see [`FixedWeightTernaryChallengeSet::log2_set_size`]), they have $\ell\_1$ norm $W$, and hence operator norm at most $W$, i.e., $\\|c x\\| \leq W \\|x\\|$ for any $x$ and any norm $\\|\cdot\\|$.
+/// Unlike for [`WeightedTernaryPolyChallengeSet`], the weight of the challenges is fixed, which allows protocols to trade off the size of the challenge set (and hence the knowledge error) against the norm growth of the extracted witness.
+///
+/// Challenges are sampled by choosing `W` signs and the first `W` positions of a Fisher–Yates shuffle of $\\{0, \ldots, N-1\\}$, with each position sampled by rejection sampling from a fixed budget of random bytes such that all positions are sampled except with probability at most $2^{-40}$.
+/// [`FromRandomBytes::try_from_random_bytes`] returns `None` if some budget is exhausted.
+pub struct FixedWeightTernaryChallengeSet<R, const W: usize> {
+    _marker: std::marker::PhantomData<R>,
+}
+
+impl<R, const W: usize> FixedWeightTernaryChallengeSet<R, W> {
+    pub const WEIGHT: usize = W;
+    pub const LINF_NORM: u128 = 1;
+    pub const L1_NORM: usize = W;
+    pub const L2_NORM_SQUARED: usize = W;
+    /// Bound on the operator norm, i.e., on $\\|c x\\| / \\|x\\|$, which follows from the triangle inequality.
+    pub const OPERATOR_NORM_BOUND: f64 = W as f64;
+
+    /// $\log\_2$ of the probability that [`FromRandomBytes::try_from_random_bytes`] fails on uniformly random bytes.
+    pub const LOG2_FAILURE_PROBABILITY: f64 = -40.;
+
+    pub fn weight() -> usize {
+        W
+    }
+}
+
+impl<BaseRing: Ring, const N: usize, const W: usize>
+    FixedWeightTernaryChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>, W>
+{
+    /// Return $\log\_2 \left( \binom{N}{W} 2^W \right)$.
+    pub fn log2_set_size() -> f64 {
+        (0..W)
+            .map(|i| ((N - i) as f64).log2() - ((i + 1) as f64).log2() + 1.)
+            .sum()
+    }
+
+    /// Return $\binom{N}{W} 2^W$.
+    pub fn set_size() -> BigUint {
+        (0..W).fold(BigUint::one(), |acc, i| acc * (N - i) * 2u32 / (i + 1))
+    }
+
+    /// Number of bytes per attempt at sampling a position in $\\{0, \ldots, N-1\\}$.
+    fn index_byte_size() -> usize {
+        (usize::BITS - (N - 1).leading_zeros()).div_ceil(8).max(1) as usize
+    }
+
+    /// Return the number of attempts for sampling the `i`-th position, which is uniform in $\\{i, \ldots, N-1\\}$.
+    /// By a union bound over the `W` positions, all positions are sampled except with probability at most $2^\texttt{LOG2\\_FAILURE\\_PROBABILITY}$.
+    fn num_attempts(i: usize) -> usize {
+        let m = N - i;
+        // Pr[failure] = (1 - m/P)^k, where P is the next power of 2 above m and k is the number of attempts
+        let rejection_probability = 1. - m as f64 / m.next_power_of_two() as f64;
+        let log2_target = Self::LOG2_FAILURE_PROBABILITY - (W as f64).log2();
+        ((log2_target / rejection_probability.log2()).ceil() as usize).max(1)
+    }
+
+    /// Return a uniformly random integer in $\\{0, \ldots, m-1\\}$, or `None` if all attempts in `bytes` are rejected.
+    fn index_from_random_bytes(m: usize, bytes: &[u8]) -> Option<usize> {
+        let mask = m.next_power_of_two() - 1;
+        bytes
+            .chunks_exact(Self::index_byte_size())
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .rev()
+                    .fold(0usize, |acc, b| (acc << 8) | *b as usize)
+                    & mask
+            })
+            .find(|&j| j < m)
+    }
+
+    /// Number of bytes consumed by [`Self::positions_from_random_bytes`].
+    pub(crate) fn positions_byte_size() -> usize {
+        (0..W).map(Self::num_attempts).sum::<usize>() * Self::index_byte_size()
+    }
+
+    /// Return `W` distinct uniformly random positions in $\\{0, \ldots, N-1\\}$, i.e., the first `W` positions of a Fisher–Yates shuffle, or `None` if some budget of random bytes is exhausted.
+    pub(crate) fn positions_from_random_bytes(mut bytes: &[u8]) -> Option<Vec<usize>> {
+        assert!(W <= N, "weight {W} exceeds the ring degree {N}");
+        let mut indices = (0..N).collect::<Vec<usize>>();
+        for i in 0..W {
+            let (budget, rest) = bytes.split_at(Self::num_attempts(i) * Self::index_byte_size());
+            bytes = rest;
+            let j = i + Self::index_from_random_bytes(N - i, budget)?;
+            indices.swap(i, j);
+        }
+        indices.truncate(W);
+        Some(indices)
+    }
+
+    /// Return the coefficients in $\\{-1, 0, 1\\}$ encoded by `bytes`, see [`FixedWeightTernaryChallengeSet`].
+    fn coeffs_from_random_bytes(bytes: &[u8]) -> Option<[i8; N]> {
+        let (sign_bytes, bytes) = bytes.split_at(W.div_ceil(8));
+        let mut sign_bits = bitter::LittleEndianReader::new(sign_bytes);
+        let mut coeffs = [0i8; N];
+        for i in Self::positions_from_random_bytes(bytes)? {
+            coeffs[i] = if sign_bits.read_bit().unwrap() { -1 } else { 1 };
+        }
+        Some(coeffs)
+    }
+}
+
+impl<BaseRing: Ring, const N: usize, const W: usize>
+    FromRandomBytes<Pow2CyclotomicPolyRing<BaseRing, N>>
+    for FixedWeightTernaryChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>, W>
+{
+    fn has_no_bias() -> bool {
+        true
+    }
+
+    fn needs_bytes() -> usize {
+        W.div_ceil(8) + Self::positions_byte_size()
+    }
+
+    fn try_from_random_bytes_inner(bytes: &[u8]) -> Option<Pow2CyclotomicPolyRing<BaseRing, N>> {
+        let coeffs = Self::coeffs_from_random_bytes(bytes)?;
+        Some(Pow2CyclotomicPolyRing::<BaseRing, N>::from_fn(
+            |i| match coeffs[i] {
+                0 => BaseRing::zero(),
+                1 => BaseRing::one(),
+                _ => -BaseRing::one(),
+            },
+        ))
+    }
+}
+
+impl<BaseRing: Ring, const N: usize, const W: usize>
+    ChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>>
+    for FixedWeightTernaryChallengeSet<Pow2CyclotomicPolyRing<BaseRing, N>, W>
+{
+    fn set_size_log2() -> f64 {
+        Self::log2_set_size()
+    }
+
+    fn linf_bound() -> u128 {
+        Self::LINF_NORM
+    }
+
+    fn operator_norm_bound() -> f64 {
+        Self::OPERATOR_NORM_BOUND
+    }
+
+    fn difference_invertible() -> bool {
+        false
+    }
+}
+
+impl<BaseRing: NttRing<N>, const N: usize, const W: usize>
+    FromRandomBytes<Pow2CyclotomicPolyRingNTT<BaseRing, N>>
+    for FixedWeightTernaryChallengeSet<Pow2CyclotomicPolyRingNTT<BaseRing, N>, W>
+{
+    fn has_no_bias() -> bool {
+        true
+    }
+
+    fn needs_bytes() -> usize {
+        FixedWeightTernaryChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>, W>::byte_size()
     }
 
     fn try_from_random_bytes_inner(bytes: &[u8]) -> Option<Pow2CyclotomicPolyRingNTT<BaseRing, N>> {
-        Pow2CyclotomicPolyRing::<BaseRing, N>::try_from_random_bytes(bytes).map(|x| x.into())
+        FixedWeightTernaryChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>, W>::try_from_random_bytes(bytes)
+            .map(|x| x.into())
+    }
+}
+
+impl<BaseRing: NttRing<N>, const N: usize, const W: usize>
+    ChallengeSet<Pow2CyclotomicPolyRingNTT<BaseRing, N>>
+    for FixedWeightTernaryChallengeSet<Pow2CyclotomicPolyRingNTT<BaseRing, N>, W>
+{
+    fn set_size_log2() -> f64 {
+        FixedWeightTernaryChallengeSet::<Pow2CyclotomicPolyRing<BaseRing, N>, W>::log2_set_size()
+    }
+
+    fn linf_bound() -> u128 {
+        Self::LINF_NORM
+    }
+
+    fn operator_norm_bound() -> f64 {
+        Self::OPERATOR_NORM_BOUND
+    }
+
+    fn difference_invertible() -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_std::rand::Rng;
+    use ark_std::test_rng;
+
+    use crate::assert_distribution;
+    use crate::ring::ntt::ntt_prime;
+    use crate::ring::{PolyRing, Zq1};
+
+    use super::*;
+
+    const Q: u64 = ntt_prime::<64>(32);
+
+    type R = Zq1<Q>;
+    type PolyR<const N: usize> = Pow2CyclotomicPolyRing<R, N>;
+    type CS<const N: usize, const W: usize> = FixedWeightTernaryChallengeSet<PolyR<N>, W>;
+
+    const NUM_REPETITIONS: usize = 1000;
+
+    fn sample<const N: usize, const W: usize>(rng: &mut impl Rng) -> PolyR<N>
+    where
+        CS<N, W>: FromRandomBytes<PolyR<N>>,
+    {
+        let mut bytes = vec![0u8; CS::<N, W>::byte_size()];
+        rng.fill(bytes.as_mut_slice());
+        CS::<N, W>::try_from_random_bytes(&bytes).unwrap()
+    }
+
+    /// Return the coefficients of `c` as integers, panicking on coefficients that are not in $\\{-1, 0, 1\\}$.
+    fn signs<const N: usize>(c: &PolyR<N>) -> Vec<i8> {
+        c.coefficients()
+            .iter()
+            .map(|x| {
+                if x.is_one() {
+                    1
+                } else if (-*x).is_one() {
+                    -1
+                } else {
+                    assert!(x.is_zero());
+                    0
+                }
+            })
+            .collect()
+    }
+
+    fn check_weight<const N: usize, const W: usize>() {
+        let rng = &mut test_rng();
+        assert_eq!(CS::<N, W>::weight(), W);
+        for _ in 0..NUM_REPETITIONS {
+            let signs = signs(&sample::<N, W>(rng));
+            assert_eq!(signs.iter().filter(|x| **x != 0).count(), W);
+        }
+    }
+
+    #[test]
+    fn test_fixed_weight() {
+        check_weight::<64, 0>();
+        check_weight::<64, 1>();
+        check_weight::<64, 31>();
+        check_weight::<64, 64>();
+        check_weight::<512, 60>();
+    }
+
+    #[test]
+    fn test_balanced_signs() {
+        let rng = &mut test_rng();
+        let samples: Vec<Vec<i8>> = (0..NUM_REPETITIONS)
+            .map(|_| signs(&sample::<64, 31>(rng)))
+            .collect();
+        let non_zero = || {
+            samples
+                .iter()
+                .flat_map(|c| c.iter().enumerate().filter(|(_, x)| **x != 0))
+        };
+        assert_distribution!(non_zero().map(|(_, x)| *x), pmf = &[(-1, 0.5), (1, 0.5)]);
+        // Positions of non-zero coefficients are sampled without replacement, which only makes the test more conservative
+        let positions: Vec<(usize, f64)> = (0..64).map(|i| (i, 1. / 64.)).collect();
+        assert_distribution!(non_zero().map(|(i, _)| i), pmf = &positions);
+    }
+
+    #[test]
+    fn test_weighted_ternary_poly_distribution() {
+        const N: usize = 64;
+        let rng = &mut test_rng();
+        let mut bytes = vec![0u8; WeightedTernaryPolyChallengeSet::<PolyR<N>>::byte_size()];
+        let samples: Vec<Vec<i8>> = (0..NUM_REPETITIONS)
+            .map(|_| {
+                rng.fill(bytes.as_mut_slice());
+                signs(
+                    &WeightedTernaryPolyChallengeSet::<PolyR<N>>::try_from_random_bytes(&bytes)
+                        .unwrap(),
+                )
+            })
+            .collect();
+        // Each coefficient is 0 with probability 1/2 and 1 and -1 with probability 1/4 each, independently of its position
+        let pmf: Vec<((usize, i8), f64)> = (0..N)
+            .flat_map(|i| [((i, -1), 0.25), ((i, 0), 0.5), ((i, 1), 0.25)])
+            .map(|(x, p)| (x, p / N as f64))
+            .collect();
+        let coefficients = || samples.iter().flat_map(|c| c.iter().copied().enumerate());
+        assert_distribution!(coefficients(), pmf = &pmf);
+        assert_distribution!(
+            coefficients().filter(|(_, x)| *x != 0).map(|(_, x)| x),
+            pmf = &[(-1, 0.5), (1, 0.5)]
+        );
+    }
+
+    /// Return $\log\_2$ of the probability that some position exhausts its budget of random bytes.
+    fn log2_failure_probability<const N: usize, const W: usize>() -> f64 {
+        (0..W)
+            .map(|i| {
+                let m = N - i;
+                let rejection_probability = 1. - m as f64 / m.next_power_of_two() as f64;
+                rejection_probability.powi(CS::<N, W>::num_attempts(i) as i32)
+            })
+            .sum::<f64>()
+            .log2()
+    }
+
+    #[test]
+    fn test_byte_size() {
+        assert!(log2_failure_probability::<64, 31>() <= -40.);
+        assert!(log2_failure_probability::<64, 60>() <= -40.);
+        assert!(log2_failure_probability::<512, 60>() <= -40.);
+        assert!(log2_failure_probability::<1024, 300>() <= -40.);
+        // Positions are encoded on a single byte iff N <= 256
+        assert_eq!(CS::<64, 31>::index_byte_size(), 1);
+        assert_eq!(CS::<512, 60>::index_byte_size(), 2);
+
+        // Bytes exhausting the budget are rejected, here for the second position in {1, ..., 63}
+        let bytes = vec![0xff; CS::<64, 31>::byte_size()];
+        assert!(CS::<64, 31>::try_from_random_bytes(&bytes).is_none());
+        assert!(CS::<64, 31>::try_from_random_bytes(&bytes[1..]).is_none());
+    }
+
+    #[test]
+    fn test_set_size() {
+        assert_eq!(CS::<64, 1>::set_size(), BigUint::from(128u32));
+        assert_eq!(CS::<64, 2>::set_size(), BigUint::from(64u32 * 63 / 2 * 4));
+        // Labrador-style challenges with 60 non-zero coefficients exceed 2^128
+        let log2_size = CS::<64, 60>::log2_set_size();
+        assert!((log2_size - CS::<64, 60>::set_size().bits() as f64).abs() < 1.);
+        assert!(CS::<256, 60>::log2_set_size() > 128.);
     }
 }
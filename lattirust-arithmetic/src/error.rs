@@ -0,0 +1,28 @@
+//! Errors of fallible constructors and conversions in this crate.
+
+use ark_serialize::SerializationError;
+use displaydoc::Display;
+use num_bigint::BigUint;
+
+/// Recoverable failures of constructors and conversions, returned instead of panicking on invalid inputs.
+#[derive(Display, Debug)]
+pub enum ArithmeticError {
+    /// expected {expected} elements, got {got}
+    LengthMismatch { expected: usize, got: usize },
+    /// {value} does not fit in the range [0, {modulus})
+    ConversionOverflow { value: BigUint, modulus: BigUint },
+    /// the element is not invertible
+    NotInvertible,
+    /// invalid parameters: {0}
+    InvalidParameters(String),
+    /// an error during (de)serialization: {0}
+    SerializationFailure(SerializationError),
+}
+
+impl std::error::Error for ArithmeticError {}
+
+impl From<SerializationError> for ArithmeticError {
+    fn from(e: SerializationError) -> Self {
+        Self::SerializationFailure(e)
+    }
+}
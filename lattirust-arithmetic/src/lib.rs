@@ -9,13 +9,18 @@
 #![feature(const_for)]
 #![feature(array_try_map)]
 #![feature(more_qualified_paths)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 
 extern crate core;
 
 pub mod challenge_set;
 pub mod decomposition;
+pub mod error;
 pub mod linear_algebra;
 pub mod nimue;
 pub mod ring;
+pub mod sampler;
 pub mod serde;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 pub mod traits;
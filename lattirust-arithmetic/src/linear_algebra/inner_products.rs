@@ -6,7 +6,7 @@ use rayon::prelude::*;
 use crate::linear_algebra::{
     ClosedAddAssign, ClosedMulAssign, Matrix, Scalar, SymmetricMatrix, Vector,
 };
-use crate::ring::{PolyRing, Ring};
+use crate::ring::{dot_z2_64, PolyRing, Ring, Z2_64};
 
 /// Convert the entries of a lower triangular n x n matrix (in sparse representation) to a vector of length (n*(n+1)) / 2
 #[inline(always)]
@@ -95,6 +95,35 @@ pub fn inner_products2<R: Ring>(s: &[Vector<R>], t: &[Vector<R>]) -> SymmetricMa
     .into()
 }
 
+/// Rings with a specialized kernel for inner products of slices, used by [`inner_products_fast`].
+/// The provided kernel is the straightforward scalar loop, so that rings without a faster kernel only need an empty `impl`.
+pub trait FastDot: Ring {
+    fn fast_dot(a: &[Self], b: &[Self]) -> Self {
+        debug_assert_eq!(a.len(), b.len());
+        a.iter().zip(b).map(|(x, y)| *x * *y).sum()
+    }
+}
+
+impl FastDot for Z2_64 {
+    fn fast_dot(a: &[Self], b: &[Self]) -> Self {
+        dot_z2_64(a, b)
+    }
+}
+
+/// Compute $(\langle s_i, s_j\rangle)\_{i, j \in \[n\]}$ like [`inner_products`], computing each inner product with [`FastDot::fast_dot`].
+pub fn inner_products_fast<R: FastDot>(s: &[Vector<R>]) -> SymmetricMatrix<R> {
+    let ranges = lower_triang_indices(s.len());
+
+    lowertriang_from_vec(
+        ranges
+            .into_par_iter()
+            .map(|(i, j)| R::fast_dot(s[i].as_slice(), s[j].as_slice()))
+            .collect::<VecDeque<_>>(),
+        s.len(),
+    )
+    .into()
+}
+
 #[cfg(test)]
 mod tests {
     use ark_std::test_rng;
@@ -144,6 +173,32 @@ mod tests {
         assert_eq!(inner_products_serial(&v), inner_products(&v));
     }
 
+    #[test]
+    fn test_inner_products_fast() {
+        let rng = &mut test_rng();
+        for n in [1, 9, 1027] {
+            let v: Vec<Vector<Z2_64>> = (0..5).map(|_| Vector::<Z2_64>::rand(n, rng)).collect();
+            assert_eq!(inner_products_fast(&v), inner_products(&v));
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_inner_products_fast() {
+        let rng = &mut test_rng();
+        let v: Vec<Vector<Z2_64>> = (0..16)
+            .map(|_| Vector::<Z2_64>::rand(1 << 17, rng))
+            .collect();
+        let start = std::time::Instant::now();
+        let expected = inner_products(&v);
+        let scalar = start.elapsed();
+        let start = std::time::Instant::now();
+        let fast = inner_products_fast(&v);
+        let elapsed = start.elapsed();
+        assert_eq!(fast, expected);
+        println!("inner_products: {scalar:?}, inner_products_fast: {elapsed:?}");
+    }
+
     #[test]
     fn test_inner_products_mat() {
         let rng = &mut test_rng();
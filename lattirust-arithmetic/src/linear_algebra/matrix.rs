@@ -12,6 +12,7 @@ use rayon::prelude::*;
 use crate::linear_algebra::generic_matrix::GenericMatrix;
 use crate::linear_algebra::{RowVector, Vector};
 use crate::linear_algebra::{Scalar, SymmetricMatrix};
+use crate::traits::{try_from_random_bytes_chunks, FromRandomBytes};
 
 pub type Const<const S: usize> = nalgebra::Const<S>;
 pub type Matrix<T> = GenericMatrix<T, Dyn, Dyn, VecStorage<T, Dyn, Dyn>>;
@@ -83,6 +84,23 @@ where
     }
 }
 
+impl<T: Scalar + FromRandomBytes<T>> Matrix<T> {
+    /// Returns the number of bytes consumed by [`Matrix::try_from_random_bytes_with_dims`], i.e., `nrows * ncols * T::byte_size()`.
+    pub fn random_byte_size(nrows: usize, ncols: usize) -> usize {
+        nrows * ncols * T::byte_size()
+    }
+
+    /// Returns the `nrows` x `ncols` matrix whose entries are created from consecutive chunks of `T::byte_size()` bytes in column-major order (as in [`Matrix::from_vec`]), or `None` if `bytes` is shorter than [`Matrix::random_byte_size`] or some entry cannot be created.
+    pub fn try_from_random_bytes_with_dims(
+        bytes: &[u8],
+        nrows: usize,
+        ncols: usize,
+    ) -> Option<Self> {
+        try_from_random_bytes_chunks::<T, T>(bytes, nrows * ncols)
+            .map(|entries| Self::from_vec(nrows, ncols, entries))
+    }
+}
+
 impl<T: Scalar + UniformRand> Matrix<T> {
     pub fn rand<Rng: rand::Rng + ?Sized>(m: usize, n: usize, rng: &mut Rng) -> Self {
         Self::from_fn(m, n, |_, _| T::rand(rng))
@@ -142,4 +160,18 @@ mod tests {
         assert_eq!(A.nrows(), m);
         assert_eq!(A.ncols(), n);
     }
+
+    #[test]
+    fn test_matrix_from_random_bytes() {
+        let (m, n) = (3, 4);
+        let bytes: Vec<u8> = (0..Matrix::<R>::random_byte_size(m, n))
+            .map(|i| (i * 37 + 11) as u8)
+            .collect();
+        let A = Matrix::<R>::try_from_random_bytes_with_dims(&bytes, m, n).unwrap();
+        assert_eq!((A.nrows(), A.ncols()), (m, n));
+        // Entries are filled in column-major order
+        let v = Vector::<R>::try_from_random_bytes_with_len(&bytes, m * n).unwrap();
+        assert_eq!(A, Matrix::<R>::from_vec(m, n, v.as_slice().to_vec()));
+        assert!(Matrix::<R>::try_from_random_bytes_with_dims(&bytes[1..], m, n).is_none());
+    }
 }
@@ -173,9 +173,12 @@ where
         compress: Compress,
         validate: Validate,
     ) -> Result<Self, SerializationError> {
-        let arr: [[T; R]; C] = from_fn(|_| {
-            from_fn(|_| T::deserialize_with_mode(&mut reader, compress, validate).unwrap())
-        });
+        let mut data = Vec::<T>::with_capacity(R * C);
+        for _ in 0..R * C {
+            data.push(T::deserialize_with_mode(&mut reader, compress, validate)?);
+        }
+        let mut entries = data.into_iter();
+        let arr: [[T; R]; C] = from_fn(|_| from_fn(|_| entries.next().unwrap()));
         Ok(Self(Self::Inner::from_data(ArrayStorage(arr))))
     }
 }
@@ -184,6 +187,7 @@ where
 mod test {
     use std::fmt::Debug;
 
+    use crate::error::ArithmeticError;
     use crate::linear_algebra::{Matrix, SMatrix, SRowVector, Vector};
     use ark_std::UniformRand;
 
@@ -243,4 +247,20 @@ mod test {
         let mat = SMatrix::<u64, M, N>::rand(rng);
         test_canonical_serialization_deserialization(mat);
     }
+
+    #[test]
+    fn test_deserialize_truncated_const_const() {
+        let rng = &mut ark_std::test_rng();
+        let mat = SMatrix::<u64, 3, 2>::rand(rng);
+        let mut bytes = vec![];
+        mat.serialize_compressed(&mut bytes).unwrap();
+        let deserialize = |bytes: &[u8]| -> Result<SMatrix<u64, 3, 2>, ArithmeticError> {
+            Ok(SMatrix::<u64, 3, 2>::deserialize_compressed(bytes)?)
+        };
+        assert_eq!(deserialize(&bytes).unwrap(), mat);
+        assert!(matches!(
+            deserialize(&bytes[..bytes.len() - 1]),
+            Err(ArithmeticError::SerializationFailure(_))
+        ));
+    }
 }
@@ -1,6 +1,10 @@
 use std::error::Error;
+use std::io::{Read, Write};
 use std::ops::{AddAssign, Mul};
 
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
 use delegate::delegate;
 use derive_more::{From, Index, IndexMut, Into, Mul, MulAssign};
 use nalgebra::{Dim, Dyn, RawStorage};
@@ -65,6 +69,60 @@ where
     }
 }
 
+/// Serialized as its dimensions followed by its CSC representation (column offsets, row indices, values).
+impl<R: CanonicalSerialize> CanonicalSerialize for SparseMatrix<R> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        let (col_offsets, row_indices, values) = self.0.csc_data();
+        self.0.nrows().serialize_with_mode(&mut writer, compress)?;
+        self.0.ncols().serialize_with_mode(&mut writer, compress)?;
+        col_offsets.serialize_with_mode(&mut writer, compress)?;
+        row_indices.serialize_with_mode(&mut writer, compress)?;
+        values.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        let (col_offsets, row_indices, values) = self.0.csc_data();
+        self.0.nrows().serialized_size(compress)
+            + self.0.ncols().serialized_size(compress)
+            + col_offsets.serialized_size(compress)
+            + row_indices.serialized_size(compress)
+            + values.serialized_size(compress)
+    }
+}
+
+impl<R: Valid> Valid for SparseMatrix<R> {
+    fn check(&self) -> Result<(), SerializationError> {
+        R::batch_check(self.0.values().iter())
+    }
+}
+
+impl<R: CanonicalDeserialize> CanonicalDeserialize for SparseMatrix<R> {
+    fn deserialize_with_mode<Rd: Read>(
+        mut reader: Rd,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let nrows = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let ncols = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+        let col_offsets = Vec::<usize>::deserialize_with_mode(&mut reader, compress, validate)?;
+        let row_indices = Vec::<usize>::deserialize_with_mode(&mut reader, compress, validate)?;
+        let values = Vec::<R>::deserialize_with_mode(&mut reader, compress, validate)?;
+        nalgebra_sparse::CscMatrix::try_from_csc_data(
+            nrows,
+            ncols,
+            col_offsets,
+            row_indices,
+            values,
+        )
+        .map(Self)
+        .map_err(|_| SerializationError::InvalidData)
+    }
+}
+
 impl<R: Scalar + Zero> From<SparseMatrix<R>> for Matrix<R> {
     fn from(val: SparseMatrix<R>) -> Self {
         let mut dense = Matrix::<R>::zeros(val.nrows(), val.ncols());
@@ -176,4 +234,20 @@ mod tests {
         let expected = &dense_mat * &vec;
         assert_eq!(res, expected);
     }
+
+    #[test]
+    fn test_sparsematrix_serialization() {
+        let triplets = Vec::from_iter((0..NUM_ROWS).map(|i| (i, i % NUM_COLS, i as u64)));
+        let sparse_mat =
+            SparseMatrix::<u64>::try_from_triplets(NUM_ROWS, NUM_COLS, triplets).unwrap();
+        let mut bytes = vec![];
+        sparse_mat.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), sparse_mat.compressed_size());
+        let deserialized = SparseMatrix::<u64>::deserialize_compressed(bytes.as_slice()).unwrap();
+        assert_eq!(deserialized, sparse_mat);
+
+        // Truncated data is rejected
+        bytes.truncate(bytes.len() - 1);
+        assert!(SparseMatrix::<u64>::deserialize_compressed(bytes.as_slice()).is_err());
+    }
 }
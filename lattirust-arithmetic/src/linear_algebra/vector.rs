@@ -12,7 +12,7 @@ use num_traits::Zero;
 use crate::linear_algebra::generic_matrix::GenericMatrix;
 use crate::linear_algebra::Scalar;
 use crate::ring::representatives::WithSignedRepresentative;
-use crate::traits::{WithL2Norm, WithLinfNorm};
+use crate::traits::{try_from_random_bytes_chunks, FromRandomBytes, WithL2Norm, WithLinfNorm};
 
 pub type GenericVector<T, R, S> = GenericMatrix<T, R, Const<1>, S>;
 pub type Vector<T> = GenericVector<T, Dyn, VecStorage<T, Dyn, Const<1>>>;
@@ -94,6 +94,35 @@ impl<T: Scalar, const N: usize> SVector<T, N> {
     }
 }
 
+impl<T: Scalar + FromRandomBytes<T>> Vector<T> {
+    /// Returns the number of bytes consumed by [`Vector::try_from_random_bytes_with_len`], i.e., `len * T::byte_size()`.
+    pub fn random_byte_size(len: usize) -> usize {
+        len * T::byte_size()
+    }
+
+    /// Returns the vector whose `i`-th entry is created from the `i`-th chunk of `T::byte_size()` bytes, or `None` if `bytes` is shorter than [`Vector::random_byte_size`] or some entry cannot be created.
+    pub fn try_from_random_bytes_with_len(bytes: &[u8], len: usize) -> Option<Self> {
+        try_from_random_bytes_chunks::<T, T>(bytes, len).map(Self::from)
+    }
+}
+
+/// Entries are created independently as in [`Vector::try_from_random_bytes_with_len`], such that each handles its own bias.
+impl<T: Scalar + FromRandomBytes<T>, const N: usize> FromRandomBytes<SVector<T, N>>
+    for SVector<T, N>
+{
+    fn has_no_bias() -> bool {
+        true
+    }
+
+    fn needs_bytes() -> usize {
+        N * T::byte_size()
+    }
+
+    fn try_from_random_bytes_inner(bytes: &[u8]) -> Option<SVector<T, N>> {
+        Self::try_from(try_from_random_bytes_chunks::<T, T>(bytes, N)?).ok()
+    }
+}
+
 impl<T: Scalar + Zero> Vector<T> {
     pub fn zeros(n: usize) -> Self {
         Self::Inner::zeros(n).into()
@@ -164,3 +193,64 @@ impl<T: Scalar, S: RawStorage<T, Const<1>, Dyn> + IsContiguous> GenericRowVector
          }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::ring::pow2_cyclotomic_poly_ring::Pow2CyclotomicPolyRing;
+    use crate::ring::Zq1;
+
+    use super::*;
+
+    type R = Pow2CyclotomicPolyRing<Zq1<65537>, 16>;
+    const LEN: usize = 5;
+
+    fn bytes(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 37 + 11) as u8).collect()
+    }
+
+    #[test]
+    fn test_vector_from_random_bytes() {
+        let size = Vector::<R>::random_byte_size(LEN);
+        assert_eq!(size, LEN * R::byte_size());
+        let bytes = bytes(size);
+        let v = Vector::<R>::try_from_random_bytes_with_len(&bytes, LEN).unwrap();
+        assert_eq!(v.len(), LEN);
+        assert_eq!(
+            v,
+            Vector::<R>::try_from_random_bytes_with_len(&bytes, LEN).unwrap()
+        );
+        for (i, chunk) in bytes.chunks_exact(R::byte_size()).enumerate() {
+            assert_eq!(v[i], R::try_from_random_bytes(chunk).unwrap());
+        }
+        // Trailing bytes are ignored
+        let mut longer = bytes.clone();
+        longer.push(0);
+        assert_eq!(
+            Vector::<R>::try_from_random_bytes_with_len(&longer, LEN),
+            Some(v)
+        );
+    }
+
+    #[test]
+    fn test_vector_from_random_bytes_short() {
+        let bytes = bytes(Vector::<R>::random_byte_size(LEN) - 1);
+        assert!(Vector::<R>::try_from_random_bytes_with_len(&bytes, LEN).is_none());
+        assert!(SVector::<R, LEN>::try_from_random_bytes(&bytes).is_none());
+        assert_eq!(
+            Vector::<R>::try_from_random_bytes_with_len(&[], 0),
+            Some(Vector::<R>::from(vec![]))
+        );
+    }
+
+    #[test]
+    fn test_svector_from_random_bytes() {
+        assert_eq!(
+            SVector::<R, LEN>::byte_size(),
+            Vector::<R>::random_byte_size(LEN)
+        );
+        let bytes = bytes(SVector::<R, LEN>::byte_size());
+        let s = SVector::<R, LEN>::try_from_random_bytes(&bytes).unwrap();
+        let v = Vector::<R>::try_from_random_bytes_with_len(&bytes, LEN).unwrap();
+        assert_eq!(s.0.as_slice(), v.as_slice());
+    }
+}
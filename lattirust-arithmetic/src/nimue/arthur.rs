@@ -1,14 +1,15 @@
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use nimue::{Arthur, ByteReader, DuplexHash, IOPatternError};
+use nimue::{Arthur, ByteChallenges, BytePublic, ByteReader, DuplexHash, IOPatternError};
 use num_traits::Zero;
 
 use crate::linear_algebra::{Matrix, Scalar, SymmetricMatrix, Vector};
 use crate::nimue::serialization::{FromBytes, ToBytes};
+use crate::nimue::traits::{end_message, RatchetTranscript};
 
 pub trait SerArthur<H>
 where
     H: DuplexHash<u8>,
-    Self: ByteReader,
+    Self: ByteReader + RatchetTranscript + BytePublic + ByteChallenges,
 {
     fn err_to_io_pattern_error<E>(e: E) -> IOPatternError
     where
@@ -19,6 +20,7 @@ where
     fn next_with_size<S: FromBytes>(&mut self, size: usize) -> Result<S, IOPatternError> {
         let mut buf = vec![0u8; size];
         self.fill_next_bytes(&mut buf)?;
+        end_message(self)?;
         S::from_bytes(buf.as_slice()).map_err(Self::err_to_io_pattern_error)
     }
 
@@ -36,6 +38,7 @@ where
     ) -> Result<S, IOPatternError> {
         let mut buf = vec![0u8; size];
         self.fill_next_bytes(&mut buf)?;
+        end_message(self)?;
         S::deserialize_compressed(buf.as_slice()).map_err(Self::err_to_io_pattern_error)
     }
 
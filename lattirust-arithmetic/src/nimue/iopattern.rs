@@ -5,15 +5,20 @@ use num_traits::Zero;
 
 use crate::linear_algebra::{Matrix, SymmetricMatrix, Vector};
 use crate::nimue::serialization::ToBytes;
+use crate::nimue::traits::{
+    challenge_attempts, SUBTRANSCRIPT_DIGEST_SIZE, TRANSCRIPT_RNG_SEED_SIZE,
+};
 use crate::traits::FromRandomBytes;
 
+/// Declarations of structured prover messages.
+/// Every message is followed by a ratchet, which nimue does not merge with adjacent operations, such that a message whose size differs from its declaration fails when it is absorbed (or read) rather than at some later operation.
 pub trait SerIOPattern
 where
-    Self: ByteIOPattern + Sized,
+    Self: ByteIOPattern + RatchetIOPattern + Sized,
 {
     fn absorb_serializable_like<S: ToBytes + Sized>(self, like: &S, label: &'static str) -> Self {
         let s = like.to_bytes().unwrap().len();
-        self.add_bytes(s, label)
+        self.add_bytes(s, label).ratchet()
     }
 
     fn absorb_canonical_serializable_like<S: CanonicalSerialize>(
@@ -22,7 +27,7 @@ where
         label: &'static str,
     ) -> Self {
         let s = like.serialized_size(Compress::Yes);
-        self.add_bytes(s, label)
+        self.add_bytes(s, label).ratchet()
     }
 
     fn absorb_vec<S>(self, size: usize, label: &'static str) -> Self
@@ -31,7 +36,7 @@ where
         Vec<S>: ToBytes,
     {
         let s = vec![S::zero(); size].to_bytes().unwrap().len();
-        self.add_bytes(s, label)
+        self.add_bytes(s, label).ratchet()
     }
 
     fn absorb_vector<S: Scalar + Clone + Zero>(self, size: usize, label: &'static str) -> Self
@@ -145,6 +150,34 @@ where
     fn squeeze_binary_matrix(self, nrows: usize, ncols: usize, label: &'static str) -> Self {
         self.challenge_bytes(nrows * ncols, label)
     }
+
+    /// Declare the bytes squeezed by [`ChallengeFromRandomBytes::challenge_ring`](crate::nimue::traits::ChallengeFromRandomBytes::challenge_ring), i.e., [`challenge_attempts`] candidates.
+    fn challenge_ring_io<R: FromRandomBytes<R>>(self, label: &'static str) -> Self {
+        self.challenge_bytes(challenge_attempts::<R>(1) * R::byte_size(), label)
+    }
+
+    fn challenge_ring_vector_io<R: FromRandomBytes<R>>(
+        self,
+        n: usize,
+        label: &'static str,
+    ) -> Self {
+        self.challenge_bytes(n * challenge_attempts::<R>(n) * R::byte_size(), label)
+    }
+
+    fn challenge_ring_matrix_io<R: FromRandomBytes<R>>(
+        self,
+        nrows: usize,
+        ncols: usize,
+        label: &'static str,
+    ) -> Self {
+        let n = nrows * ncols;
+        self.challenge_bytes(n * challenge_attempts::<R>(n) * R::byte_size(), label)
+    }
+
+    /// Declare the seed squeezed by [`RngFromTranscript::rng_from_transcript`](crate::nimue::traits::RngFromTranscript::rng_from_transcript).
+    fn rng_io(self, label: &'static str) -> Self {
+        self.challenge_bytes(TRANSCRIPT_RNG_SEED_SIZE, label)
+    }
 }
 
 impl<H: DuplexHash<u8>> SqueezeFromRandomBytes for IOPattern<H> {}
@@ -159,3 +192,91 @@ impl<H: DuplexHash<u8>> RatchetIOPattern for IOPattern<H> {
         self.ratchet()
     }
 }
+
+pub trait SubprotocolIOPattern
+where
+    Self: ByteIOPattern + RatchetIOPattern + Sized,
+{
+    /// Declare a sub-protocol with IO pattern `pattern` run in a transcript forked with `label`, see [`ForkTranscript::fork`](crate::nimue::traits::ForkTranscript::fork).
+    fn subprotocol(self, label: &[u8], pattern: impl FnOnce(Self) -> Self) -> Self {
+        assert!(!label.is_empty(), "fork label must be non-empty");
+        pattern(self.ratchet().add_bytes(label.len(), "fork"))
+            .ratchet()
+            .challenge_bytes(SUBTRANSCRIPT_DIGEST_SIZE, "subprotocol digest")
+            .ratchet()
+            .add_bytes(SUBTRANSCRIPT_DIGEST_SIZE, "merge")
+    }
+}
+
+impl<H: DuplexHash<u8>> SubprotocolIOPattern for IOPattern<H> {}
+
+#[cfg(test)]
+mod test {
+    use nimue::{DefaultHash, IOPattern, IOPatternError};
+
+    use crate::nimue::arthur::SerArthur;
+    use crate::nimue::merlin::SerMerlin;
+    use crate::nimue::traits::ChallengeFromRandomBytes;
+    use crate::ring::Zq1;
+
+    use super::*;
+
+    type R = Zq1<65537>;
+
+    fn io() -> IOPattern<DefaultHash> {
+        IOPattern::<DefaultHash>::new("test_io_sizes")
+            .absorb_matrix::<R>(3, 4, "mat")
+            .absorb_vector::<R>(5, "vec")
+            .absorb_symmetric_matrix::<R>(3, "sym")
+            .challenge_ring_io::<R>("challenge")
+    }
+
+    /// Run a prover absorbing a `3 x mat_cols` matrix, a vector of length 5 and a symmetric matrix of size `sym_size` against [`io`].
+    fn prove(mat_cols: usize, sym_size: usize) -> Result<R, IOPatternError> {
+        let mut merlin = io().to_merlin();
+        merlin.absorb(&Matrix::<R>::zeros(3, mat_cols))?;
+        merlin.absorb(&Vector::<R>::zeros(5))?;
+        merlin.absorb(&SymmetricMatrix::<R>::zero(sym_size))?;
+        merlin.challenge_ring::<R>()
+    }
+
+    #[test]
+    fn test_declared_sizes_match() {
+        prove(4, 3).unwrap();
+    }
+
+    #[test]
+    fn test_altered_dimension() {
+        assert!(prove(5, 3).is_err());
+        assert!(prove(3, 3).is_err());
+        assert!(prove(4, 2).is_err());
+        assert!(prove(4, 4).is_err());
+    }
+
+    #[test]
+    fn test_mismatch_fails_at_absorb() {
+        // Each absorb is checked against its own declaration, even if the total size matches
+        let io = IOPattern::<DefaultHash>::new("test_mismatch")
+            .absorb_vector::<R>(2, "a")
+            .absorb_vector::<R>(4, "b")
+            .challenge_ring_io::<R>("challenge");
+        let mut merlin = io.to_merlin();
+        assert!(merlin.absorb(&Vector::<R>::zeros(4)).is_err());
+        let mut merlin = io.to_merlin();
+        assert!(merlin.absorb(&Vector::<R>::zeros(1)).is_err());
+        // The transcript cannot be continued after a mismatch
+        assert!(merlin.absorb(&Vector::<R>::zeros(4)).is_err());
+
+        let mut merlin = io.to_merlin();
+        merlin.absorb(&Vector::<R>::zeros(2)).unwrap();
+        merlin.absorb(&Vector::<R>::zeros(4)).unwrap();
+        let transcript = merlin.transcript().to_vec();
+        let mut arthur = io.to_arthur(&transcript);
+        assert!(arthur.next_vector::<R>(4).is_err());
+        let mut arthur = io.to_arthur(&transcript);
+        assert!(arthur.next_vector::<R>(1).is_err());
+        let mut arthur = io.to_arthur(&transcript);
+        arthur.next_vector::<R>(2).unwrap();
+        arthur.next_vector::<R>(4).unwrap();
+    }
+}
@@ -1,85 +1,302 @@
+use std::io::{BufWriter, Write};
+
 use ark_serialize::CanonicalSerialize;
 use ark_std::rand::{CryptoRng, RngCore};
-use nimue::{ByteWriter, DuplexHash, IOPatternError, Merlin};
+use nimue::{ByteChallenges, BytePublic, ByteWriter, DuplexHash, IOPatternError, Merlin};
 
 use crate::linear_algebra::{Matrix, Scalar, SymmetricMatrix, Vector};
 use crate::nimue::serialization::ToBytes;
+use crate::nimue::traits::{end_message, RatchetTranscript};
+
+/// Size of the chunks in which [`SerMerlin::absorb_canonical_streaming`] absorbs serialized messages.
+pub const STREAMING_CHUNK_SIZE: usize = 1 << 16;
+
+/// Adapter absorbing everything written to it into the transcript of a [`ByteWriter`], such that messages can be serialized directly into the transcript.
+pub struct TranscriptWriter<'a, W: ByteWriter + ?Sized>(pub &'a mut W);
+
+impl<W: ByteWriter + ?Sized> Write for TranscriptWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0
+            .add_bytes(buf)
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
 
 pub trait SerMerlin<H, R>
 where
     H: DuplexHash<u8>,
     R: RngCore + CryptoRng,
-    Self: ByteWriter,
+    Self: ByteWriter + RatchetTranscript + BytePublic + ByteChallenges,
 {
+    /// Absorb `msg`, serializing it directly into the transcript in chunks of [`STREAMING_CHUNK_SIZE`] bytes.
+    /// Returns an error if the size of `msg` differs from the size declared for it with [`SerIOPattern`](crate::nimue::iopattern::SerIOPattern).
+    /// Since [`ToBytes`] is implemented for all [`CanonicalSerialize`] types, this covers ring elements, vectors, matrices, symmetric and sparse matrices alike, and produces the same transcript as the deprecated type-specific `absorb_*` methods.
+    fn absorb<S: CanonicalSerialize>(&mut self, msg: &S) -> Result<(), IOPatternError> {
+        let mut writer = BufWriter::with_capacity(STREAMING_CHUNK_SIZE, TranscriptWriter(self));
+        msg.serialize_compressed(&mut writer)
+            .map_err(|e| IOPatternError::from(e.to_string()))?;
+        writer.flush().map_err(IOPatternError::from)?;
+        drop(writer);
+        end_message(self)
+    }
+
+    #[deprecated(note = "use `SerMerlin::absorb`")]
     fn absorb_serializable<S: ToBytes>(&mut self, msg: &S) -> Result<(), IOPatternError> {
         match &msg.to_bytes() {
-            Ok(bytes) => self.add_bytes(bytes.as_slice()),
-            Err(e) => Err(IOPatternError::from(format! {"{:?}", e})),
+            Ok(bytes) => self.add_bytes(bytes.as_slice())?,
+            Err(e) => return Err(IOPatternError::from(format! {"{:?}", e})),
         }
+        end_message(self)
     }
 
+    #[deprecated(note = "use `SerMerlin::absorb`")]
     fn absorb_canonical_serializable<S: CanonicalSerialize>(
         &mut self,
         msg: &S,
     ) -> Result<(), IOPatternError> {
         let mut bytes = vec![];
         match msg.serialize_compressed(&mut bytes) {
-            Ok(()) => self.add_bytes(bytes.as_slice()),
-            Err(e) => Err(IOPatternError::from(e.to_string())),
+            Ok(()) => self.add_bytes(bytes.as_slice())?,
+            Err(e) => return Err(IOPatternError::from(e.to_string())),
         }
+        end_message(self)
+    }
+
+    #[deprecated(note = "use `SerMerlin::absorb`")]
+    fn absorb_canonical_streaming<S: CanonicalSerialize>(
+        &mut self,
+        msg: &S,
+    ) -> Result<(), IOPatternError> {
+        self.absorb(msg)
     }
 
+    #[deprecated(note = "use `SerMerlin::absorb`")]
     fn absorb_vector<F: Scalar>(&mut self, vec: &Vector<F>) -> Result<(), IOPatternError>
     where
-        Vector<F>: ToBytes,
+        Vector<F>: CanonicalSerialize,
     {
-        self.absorb_serializable(vec)
+        self.absorb(vec)
     }
 
+    #[deprecated(note = "use `SerMerlin::absorb`")]
     fn absorb_vec<F: Scalar>(&mut self, vec: &Vec<F>) -> Result<(), IOPatternError>
     where
-        Vec<F>: ToBytes,
+        Vec<F>: CanonicalSerialize,
     {
-        self.absorb_serializable(vec)
+        self.absorb(vec)
     }
 
+    #[deprecated(note = "use `SerMerlin::absorb`")]
     fn absorb_vector_canonical<F: Scalar>(&mut self, vec: &Vector<F>) -> Result<(), IOPatternError>
     where
         Vector<F>: CanonicalSerialize,
     {
-        self.absorb_canonical_serializable(vec)
+        self.absorb(vec)
     }
 
+    #[deprecated(note = "use `SerMerlin::absorb`")]
     fn absorb_vectors<F: Scalar>(&mut self, vecs: &Vec<Vector<F>>) -> Result<(), IOPatternError>
     where
-        Vec<Vector<F>>: ToBytes,
+        Vec<Vector<F>>: CanonicalSerialize,
     {
-        self.absorb_serializable(vecs)
+        self.absorb(vecs)
     }
 
+    #[deprecated(note = "use `SerMerlin::absorb`")]
     fn absorb_symmetric_matrix<F: Clone>(
         &mut self,
         mat: &SymmetricMatrix<F>,
     ) -> Result<(), IOPatternError>
     where
-        SymmetricMatrix<F>: ToBytes,
+        SymmetricMatrix<F>: CanonicalSerialize,
     {
-        self.absorb_serializable(mat)
+        self.absorb(mat)
     }
 
+    #[deprecated(note = "use `SerMerlin::absorb`")]
     fn absorb_matrix<F: Scalar>(&mut self, mat: &Matrix<F>) -> Result<(), IOPatternError>
     where
         Matrix<F>: CanonicalSerialize,
     {
-        self.absorb_canonical_serializable(mat)
+        self.absorb(mat)
     }
 
+    #[deprecated(note = "use `SerMerlin::absorb`")]
     fn absorb_matrix_ser<F: Scalar>(&mut self, mat: &Matrix<F>) -> Result<(), IOPatternError>
     where
-        Matrix<F>: ToBytes,
+        Matrix<F>: CanonicalSerialize,
     {
-        self.absorb_serializable(mat)
+        self.absorb(mat)
     }
 }
 
 impl<H: DuplexHash<u8>, R: RngCore + CryptoRng> SerMerlin<H, R> for Merlin<H, u8, R> {}
+
+#[cfg(test)]
+mod test {
+    use ark_std::UniformRand;
+    use nimue::{Arthur, DefaultHash, IOPattern, Merlin};
+
+    use crate::linear_algebra::SparseMatrix;
+    use crate::nimue::arthur::SerArthur;
+    use crate::nimue::iopattern::{SerIOPattern, SqueezeFromRandomBytes};
+    use crate::nimue::traits::ChallengeFromRandomBytes;
+    use crate::ring::{Pow2CyclotomicPolyRing, Zq1};
+
+    use super::*;
+
+    const Q: u64 = 65537;
+    type R = Zq1<Q>;
+
+    #[test]
+    fn test_prover_verifier_round_trip() {
+        let rng = &mut ark_std::test_rng();
+        let mat = Matrix::<R>::rand(3, 4, rng);
+        let sym = SymmetricMatrix::<R>::from(
+            (0..3)
+                .map(|i| (0..=i).map(|_| R::rand(rng)).collect())
+                .collect::<Vec<Vec<R>>>(),
+        );
+
+        let io = IOPattern::<DefaultHash>::new("test_round_trip")
+            .absorb_matrix::<R>(3, 4, "mat")
+            .absorb_symmetric_matrix::<R>(3, "sym")
+            .squeeze_elem::<R, R>("challenge");
+
+        let mut merlin = io.to_merlin();
+        merlin.absorb(&mat).unwrap();
+        merlin.absorb(&sym).unwrap();
+        let challenge_prover = merlin.challenge::<R, R>().unwrap();
+
+        let mut arthur = Arthur::<DefaultHash>::new(&io, merlin.transcript());
+        assert_eq!(arthur.next_matrix::<R>(3, 4).unwrap(), mat);
+        assert_eq!(arthur.next_symmetric_matrix::<R>(3).unwrap(), sym);
+        let challenge_verifier = arthur.challenge::<R, R>().unwrap();
+
+        assert_eq!(challenge_prover, challenge_verifier);
+    }
+
+    #[allow(deprecated)]
+    fn absorb_buffered_and_streaming(mat: &Matrix<R>) -> (Vec<u8>, Vec<u8>, R, R) {
+        let io = IOPattern::<DefaultHash>::new("test_streaming")
+            .absorb_matrix::<R>(mat.nrows(), mat.ncols(), "mat")
+            .squeeze_elem::<R, R>("challenge");
+
+        let mut buffered = io.to_merlin();
+        buffered.absorb_canonical_serializable(mat).unwrap();
+        let challenge_buffered = buffered.challenge::<R, R>().unwrap();
+
+        let mut streaming = io.to_merlin();
+        streaming.absorb(mat).unwrap();
+        let challenge_streaming = streaming.challenge::<R, R>().unwrap();
+
+        (
+            buffered.transcript().to_vec(),
+            streaming.transcript().to_vec(),
+            challenge_buffered,
+            challenge_streaming,
+        )
+    }
+
+    #[test]
+    fn test_streaming_absorb() {
+        let rng = &mut ark_std::test_rng();
+        // Large enough to span several chunks
+        let mat = Matrix::<R>::rand(256, 257, rng);
+        let (buffered, streaming, challenge_buffered, challenge_streaming) =
+            absorb_buffered_and_streaming(&mat);
+        assert!(buffered.len() > 2 * STREAMING_CHUNK_SIZE);
+        assert_eq!(buffered, streaming);
+        assert_eq!(challenge_buffered, challenge_streaming);
+
+        // Absorbing more than declared fails
+        let io = IOPattern::<DefaultHash>::new("test_streaming").absorb_matrix::<R>(2, 2, "mat");
+        let mut merlin = io.to_merlin();
+        assert!(merlin.absorb(&mat).is_err());
+    }
+
+    #[test]
+    #[ignore]
+    #[allow(deprecated)]
+    fn bench_streaming_absorb() {
+        let rng = &mut ark_std::test_rng();
+        let mat = Matrix::<R>::rand(1 << 11, 1 << 11, rng);
+        let io = IOPattern::<DefaultHash>::new("bench_streaming").absorb_matrix::<R>(
+            mat.nrows(),
+            mat.ncols(),
+            "mat",
+        );
+
+        let start = std::time::Instant::now();
+        io.to_merlin().absorb_canonical_serializable(&mat).unwrap();
+        let buffered = start.elapsed();
+        let start = std::time::Instant::now();
+        io.to_merlin().absorb(&mat).unwrap();
+        let streaming = start.elapsed();
+        println!(
+            "buffered: {buffered:?} with a {} byte buffer, streaming: {streaming:?} with a {STREAMING_CHUNK_SIZE} byte buffer",
+            mat.compressed_size()
+        );
+    }
+
+    /// Assert that absorbing `msg` with [`SerMerlin::absorb`] and with `absorb_specific` yields the same transcript and the same subsequent challenge.
+    fn assert_absorb_matches<S: CanonicalSerialize>(
+        msg: &S,
+        absorb_specific: impl FnOnce(&mut Merlin<DefaultHash>, &S) -> Result<(), IOPatternError>,
+    ) {
+        let io = IOPattern::<DefaultHash>::new("test_absorb")
+            .absorb_canonical_serializable_like(msg, "msg")
+            .squeeze_elem::<R, R>("challenge");
+        let mut consolidated = io.to_merlin();
+        consolidated.absorb(msg).unwrap();
+        let mut specific = io.to_merlin();
+        absorb_specific(&mut specific, msg).unwrap();
+        assert_eq!(consolidated.transcript(), specific.transcript());
+        assert_eq!(
+            consolidated.challenge::<R, R>().unwrap(),
+            specific.challenge::<R, R>().unwrap()
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_absorb_consolidated() {
+        type P = Pow2CyclotomicPolyRing<R, 16>;
+        let rng = &mut ark_std::test_rng();
+
+        let x = R::rand(rng);
+        let p = P::rand(rng);
+        assert_absorb_matches(&x, |m, x| m.absorb_serializable(x));
+        assert_absorb_matches(&p, |m, p| m.absorb_canonical_serializable(p));
+
+        let v = Vector::<P>::rand(5, rng);
+        assert_absorb_matches(&v, |m, v| m.absorb_vector(v));
+        assert_absorb_matches(&v, |m, v| m.absorb_vector_canonical(v));
+
+        let vec: Vec<R> = (0..5).map(|_| R::rand(rng)).collect();
+        assert_absorb_matches(&vec, |m, vec| m.absorb_vec(vec));
+
+        let vecs: Vec<Vector<R>> = (0..3).map(|_| Vector::<R>::rand(4, rng)).collect();
+        assert_absorb_matches(&vecs, |m, vecs| m.absorb_vectors(vecs));
+
+        let mat = Matrix::<R>::rand(3, 4, rng);
+        assert_absorb_matches(&mat, |m, mat| m.absorb_matrix(mat));
+        assert_absorb_matches(&mat, |m, mat| m.absorb_matrix_ser(mat));
+
+        let sym = SymmetricMatrix::<R>::from(
+            (0..3)
+                .map(|i| (0..=i).map(|_| R::rand(rng)).collect())
+                .collect::<Vec<Vec<R>>>(),
+        );
+        assert_absorb_matches(&sym, |m, sym| m.absorb_symmetric_matrix(sym));
+
+        let triplets = vec![(0, 1, R::rand(rng)), (2, 0, R::rand(rng))];
+        let sparse = SparseMatrix::<R>::try_from_triplets(3, 2, triplets).unwrap();
+        assert_absorb_matches(&sparse, |m, sparse| m.absorb_serializable(sparse));
+    }
+}
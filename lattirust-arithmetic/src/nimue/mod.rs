@@ -1,5 +1,8 @@
 pub mod arthur;
 pub mod iopattern;
 pub mod merlin;
+pub mod poseidon;
 pub mod serialization;
+#[cfg(feature = "transcript-trace")]
+pub mod trace;
 pub mod traits;
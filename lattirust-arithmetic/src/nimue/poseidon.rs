@@ -0,0 +1,1280 @@
+use std::sync::OnceLock;
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::{CryptoRng, RngCore};
+use nimue::hash::sponge::{DuplexSponge, Sponge};
+use nimue::hash::Unit;
+use nimue::{Arthur, DuplexHash, IOPatternError, Merlin, UnitTranscript};
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use zeroize::Zeroize;
+
+use crate::linear_algebra::Vector;
+use crate::ring::{Ring, Zq, Zq1, ZqConfig};
+
+/// The prime $2^{63} - 7 \cdot 2^{32} + 1$, i.e., the largest prime below $2^{63}$ of the form $k \cdot 2^{32} + 1$.
+pub const POSEIDON_MODULUS: u64 = 0x7fff_fff9_0000_0001;
+
+/// The field over which [`PoseidonSponge`] operates.
+pub type PoseidonField = Zq1<POSEIDON_MODULUS>;
+
+/// Width $t$ of the Poseidon permutation, i.e., number of field elements in the state.
+pub const WIDTH: usize = 12;
+/// Number of state elements absorbed and squeezed per permutation; the remaining 4 elements (256 bits) are the capacity.
+pub const RATE: usize = 8;
+/// Number of full rounds $R\_F$, half of which are applied before and half after the partial rounds.
+/// The round numbers are those of the reference script for 128-bit security, including its security margin.
+pub const FULL_ROUNDS: usize = 8;
+/// Number of partial rounds $R\_P$.
+pub const PARTIAL_ROUNDS: usize = 41;
+/// Exponent $\alpha$ of the S-box $x \mapsto x^\alpha$, the smallest $\alpha$ with $\gcd(\alpha, p-1) = 1$.
+pub const ALPHA: u64 = 3;
+
+// Generated with the Grain LFSR of the reference parameter script of [GKRRS21] for (field = 1, sbox = 0, n = 63, t = 12, R_F = 8, R_P = 41): first (R_F + R_P) * t round constants, then a Cauchy matrix 1 / (x_i + y_j) from 2t further elements.
+// The invariant-subspace checks of the reference script are not re-run here.
+const ROUND_CONSTANTS: [[u64; WIDTH]; FULL_ROUNDS + PARTIAL_ROUNDS] = [
+    [
+        0x670ef772ab74d2c7,
+        0x60d042c0dd6338f0,
+        0x4672dee41dde1bb8,
+        0x39710baf91ae3e29,
+        0x25bea90ebe0b733a,
+        0x1413f420e8fed3c1,
+        0x312256334eebe3a7,
+        0x7b739255e9f794f3,
+        0x70432873f8d9d7fd,
+        0x71a32fbe777a9f0f,
+        0x2e82f967e01e031b,
+        0x67d3ecc1a70d2745,
+    ],
+    [
+        0x0a98bc4bf9f2d217,
+        0x4ecd80393279d44e,
+        0x69391512347382b8,
+        0x04cf356f11abd2d2,
+        0x74a55f88d099853a,
+        0x12244fbea248cc67,
+        0x64cd240513e1d0ab,
+        0x3b59ab7694dafc80,
+        0x49b1fc39ea5799bf,
+        0x3ef5bc2b7f642c23,
+        0x7422409dabb838d8,
+        0x29606911127bba57,
+    ],
+    [
+        0x310c0f072f2be5ff,
+        0x5ae5e71d09b0eb99,
+        0x1a32748cbb7d8f3e,
+        0x6c84ac199dc8ad0e,
+        0x6c232ef2cfc188a0,
+        0x75ce97c5261b7d3d,
+        0x19d11ea1871acbff,
+        0x767e11052edb761a,
+        0x419624f93725d69c,
+        0x28147a3922f37f3d,
+        0x4eda260576421baf,
+        0x61c2a83b5adce636,
+    ],
+    [
+        0x2f8b17216b91fc80,
+        0x2e25df9baa1fdf64,
+        0x31c209cd9d68441c,
+        0x0765c562f599ab9b,
+        0x7f1b569fec88d1d2,
+        0x245192ce712f7fcd,
+        0x58dde5c9dcc01389,
+        0x152f1eeaba07881e,
+        0x7005e6146c87afbe,
+        0x067c69552a7c7754,
+        0x1fccc551366ff3f0,
+        0x7f7d48cd5b5dd420,
+    ],
+    [
+        0x3dcc41e92d315995,
+        0x2154815ae008e006,
+        0x43c67f9588eb69c2,
+        0x5e9db09356e90346,
+        0x03ed0165cf3609bf,
+        0x558d55dd63e42da5,
+        0x472dad4d4c5c6524,
+        0x685012fbc29d2af6,
+        0x0785d63326e2b7e8,
+        0x310ebb5ce56da1c5,
+        0x4c55eebabdab09e4,
+        0x013fcba7d633d4f3,
+    ],
+    [
+        0x72cebba2aea991bc,
+        0x0baa535b0bf05a95,
+        0x33658da042dce5d8,
+        0x2ced7de1c178fcdd,
+        0x1680b566c3913891,
+        0x119a338328fa6efa,
+        0x70e6816a3861c505,
+        0x28880d4f644f0e29,
+        0x7fc89686b74b2aef,
+        0x585086beed5b0e86,
+        0x48b43eeb012fdc80,
+        0x796b26249ff04f63,
+    ],
+    [
+        0x0f665dec466fc6d4,
+        0x5f9637ad78925ca3,
+        0x5595a796b33655dd,
+        0x3b5578b0ce6ff878,
+        0x4579130efa528b7d,
+        0x18b3f21b6c84dc47,
+        0x6f645f9457891790,
+        0x5cae36d3370355c5,
+        0x54c84f43f2f76725,
+        0x630d9afbccb4f387,
+        0x2e4d41659bf93d53,
+        0x27e6b31b9587820e,
+    ],
+    [
+        0x7080775a5984eca0,
+        0x5e569c1dd7f79b29,
+        0x6e4f361598cceec3,
+        0x47fbd6e390f0384a,
+        0x49fc97b68019654e,
+        0x70d7c242c36bd837,
+        0x3bd646532df4c26a,
+        0x57ead4e150fb2b67,
+        0x5f2d4e1e52a45ee9,
+        0x73d000fca39909f1,
+        0x13bb4682d5fc2b01,
+        0x06fbdd3790e6121b,
+    ],
+    [
+        0x0fdcb3f20a835da5,
+        0x5ac84eb5ec5a2e72,
+        0x4911d8b8f1dcae8f,
+        0x1b283c7079c7d14e,
+        0x029b486e380b706a,
+        0x619e932d90a12b71,
+        0x1ae384c7e066f18c,
+        0x386d4ee62387c84d,
+        0x71bc4e563e1749bf,
+        0x52b2b7a6582d76a0,
+        0x00ec454ffbf2effb,
+        0x257a22dbff2f6db6,
+    ],
+    [
+        0x00b8db33ba841ee7,
+        0x38ae61f381853e66,
+        0x662070ad53d1289a,
+        0x3ee75b1f73285d73,
+        0x3f100a60f0cfef6f,
+        0x2b5bc495a6ed1d91,
+        0x5652617cf469d846,
+        0x5d1a2b2f58700654,
+        0x27910642cda7b41a,
+        0x72bc98c8ec074e2b,
+        0x599e274630db9236,
+        0x1afc60d4ef22ff30,
+    ],
+    [
+        0x3f0c8b3b4cbc23f3,
+        0x5d919de9df593e75,
+        0x047ecc118e358b40,
+        0x15f06e8f855ef974,
+        0x60ddd3d19a70b9a3,
+        0x522d472a9b05ab32,
+        0x7464429e298a2bd8,
+        0x46ce6e270b590092,
+        0x5066d8e45ea199bb,
+        0x09ceaf250e1e35ae,
+        0x7a416012d331feb6,
+        0x769a3d971c273c97,
+    ],
+    [
+        0x70ce72d1c4131cb6,
+        0x3411981c3cffcc14,
+        0x484217983db5429d,
+        0x58ee7f198e3291e8,
+        0x52bfea60fcb458ff,
+        0x6f84230515050696,
+        0x5d87bd5bef05b166,
+        0x39ee6e51bbfd39ce,
+        0x3f20414240c86a30,
+        0x0e7126bef010f339,
+        0x533d1261382150ea,
+        0x5f78fdb76893788d,
+    ],
+    [
+        0x4cebd6ecaefa3aee,
+        0x6fdfe43d780b5260,
+        0x1b76b25d76d154f4,
+        0x45268667d563fab4,
+        0x60e5c67db974b070,
+        0x34187827d9599c8a,
+        0x6c9baae19b7d5915,
+        0x1778a9548e0dba6e,
+        0x4ab3fc8d0a6bc153,
+        0x145f530528d218f8,
+        0x0a5ec5c05929d967,
+        0x4ce2da146fe612a4,
+    ],
+    [
+        0x3a097ee3d9ac9329,
+        0x41b877ff083ca985,
+        0x2bfee2f13de9776c,
+        0x2b6e30a1a4e7222e,
+        0x6f7cb48c5b193d0b,
+        0x19001ddbda4c819c,
+        0x2ba6f4ef0ff64961,
+        0x52d024174ae273ac,
+        0x1806c2fcf9fe3fde,
+        0x6231ca2315548386,
+        0x569ad060ed774b8e,
+        0x01bf256c502d4657,
+    ],
+    [
+        0x0b9580ee6614e1a0,
+        0x517799e47a213792,
+        0x32f4002e790c8a1e,
+        0x5f9fe1359ad362b9,
+        0x13d4058b201351e6,
+        0x31cb5453f9387bbb,
+        0x1bd9685cb5512673,
+        0x664ca526d55eac75,
+        0x48a2cdc882691e58,
+        0x5520d8d79c2d2e32,
+        0x7ad300a2aec2734a,
+        0x4130b5a4488319e5,
+    ],
+    [
+        0x0d08c304d7208d7c,
+        0x7bb12677f73f1381,
+        0x4faed4fab7bc7f7c,
+        0x158ef33dcb7d1652,
+        0x5ae616621d15198e,
+        0x20966a717fa448d8,
+        0x130462e6850747af,
+        0x15becda7b90d6fc1,
+        0x023297d573f6f78b,
+        0x19d6566aed062dd6,
+        0x265197d9ab12f412,
+        0x52610a925b89f34b,
+    ],
+    [
+        0x74dc2c8c864bb061,
+        0x1bd6b922db9f3b31,
+        0x12634bfdd32c210a,
+        0x403030047aa5c961,
+        0x0cf58afbe08583b7,
+        0x08e1f30385376394,
+        0x6b6b7db6f6284949,
+        0x0faa41b9fc3b80ba,
+        0x005317766d862f97,
+        0x27fec96e2aeb5025,
+        0x2aa2e8446bfe5b1f,
+        0x457dd7a545c1c77f,
+    ],
+    [
+        0x2d0c6281ed83d1b2,
+        0x22f717d7b255c538,
+        0x1a4979647b664c65,
+        0x19e383e38c5f423b,
+        0x53a17e7e31b62d7c,
+        0x1e229f1e98300461,
+        0x5b94b16dd9e95ebe,
+        0x12b748340f63677a,
+        0x0f519f55b0b4a12e,
+        0x6aacceec3042ea07,
+        0x57ee3c611077554f,
+        0x3a9eca7f13ec7833,
+    ],
+    [
+        0x3e9480562109c6ef,
+        0x6e2e4217dc6f767e,
+        0x005888c5c9c66d15,
+        0x7da976da869d99a1,
+        0x48b7e5a4546f6b10,
+        0x1bd3c07e0c606005,
+        0x4d72a122c5d96cbc,
+        0x5a024fdbd61ac5cd,
+        0x38d57676781623d9,
+        0x5d4879ebfe91848d,
+        0x38465cd558fb08e4,
+        0x648c1e45e7e76af0,
+    ],
+    [
+        0x57efcc6e54409181,
+        0x253baa3f6f693a08,
+        0x0f28a96e7bd8b67b,
+        0x24d0ee7541821df7,
+        0x1dc879d26c97c427,
+        0x13e9cf94f5119050,
+        0x63c23b36a2ca06b1,
+        0x5ca5f10208409e62,
+        0x09feb94a8b9f6701,
+        0x1de5134de3418906,
+        0x2343351da5da5f65,
+        0x7ebf54d0bb3c0bb3,
+    ],
+    [
+        0x765354e96a1c9b45,
+        0x4214cb4a681f2330,
+        0x40af336b0d0ad87b,
+        0x2c8ba58686606848,
+        0x031c426658523f7b,
+        0x1af20d197a2e8f74,
+        0x17b7ad84c7b19e06,
+        0x4a2db7d72ed4612c,
+        0x05ba40fd5f63770f,
+        0x5b22e96715188765,
+        0x48c8b13249a52912,
+        0x52192d733e3772a5,
+    ],
+    [
+        0x6484e5e1116cffed,
+        0x046db5144e0c57e9,
+        0x669a34f80183a5db,
+        0x7983a78e48fd8672,
+        0x2248f9bbfc176ffe,
+        0x359eeb2c1c3b7905,
+        0x59a6d6880ea0419e,
+        0x3e30ebb391c7cdeb,
+        0x3b606bb1829c6db0,
+        0x42bd63b3d3634a0b,
+        0x4e25cd0563b93707,
+        0x534f7d0fd5ba6cf6,
+    ],
+    [
+        0x03d112c135579c6d,
+        0x76a045f344349ea9,
+        0x42b59aa83049a004,
+        0x097d95a6acdc1d5c,
+        0x2d1a61d1d0104b2c,
+        0x2ecece453660ac05,
+        0x799da843423f2c25,
+        0x0408b3abec976cd9,
+        0x0498b7bbc2094f9b,
+        0x2ca5503943194fc5,
+        0x6cf06373910b9068,
+        0x48f1609df7c42c70,
+    ],
+    [
+        0x3210123c15162a1b,
+        0x686612ccaadf46a8,
+        0x7e1a55f854ba235b,
+        0x22b6071997f41e34,
+        0x15f025cd7952c612,
+        0x073f5edefec485bd,
+        0x545d6d8b309be883,
+        0x18d18943659951fa,
+        0x7262dfa156d13009,
+        0x6569e8065674d820,
+        0x6616aa511ef9c8e5,
+        0x462bd79c9f294f1f,
+    ],
+    [
+        0x6b18549e3c92cd05,
+        0x461f7df0aa8cf51f,
+        0x212cb68f8e834dd4,
+        0x3debae2100095280,
+        0x3cacda1b84c933aa,
+        0x35a5cec3410d2d40,
+        0x3b3a5a7c99d69ca6,
+        0x7fb1af736faf4723,
+        0x28a830bce8ab60ef,
+        0x20d7640080757331,
+        0x7a6f96826e14101a,
+        0x43f52265005c3251,
+    ],
+    [
+        0x758c824dadbe61d2,
+        0x575d337add53db10,
+        0x269ffe736ae4fbd4,
+        0x5ba61e9e415bd47c,
+        0x03008530c7f7fcf8,
+        0x5bf7afa572b4ce5d,
+        0x5f128a77aba26b5d,
+        0x067109178376b42f,
+        0x39f4c1297437df88,
+        0x3df06537567bf347,
+        0x16cf41f2be473bbc,
+        0x7475115299a2e6c9,
+    ],
+    [
+        0x0ebe72db2a545a8d,
+        0x7598b686da3e2bf1,
+        0x75a0339d9c2842a9,
+        0x666f3f721fd87234,
+        0x25d4b580a45d2697,
+        0x4d848254e65176b3,
+        0x77857bfb67b013aa,
+        0x7a23227cbbd26da0,
+        0x31767e47c5d22b9f,
+        0x101710a6695ca585,
+        0x0f480d84c6ad5bdb,
+        0x61b81e0924df552c,
+    ],
+    [
+        0x454edbe652b37083,
+        0x452f4ae7bd657b76,
+        0x3d4759173ccee0dc,
+        0x10ac3f8ffdcbe8e4,
+        0x7d054296f8a6c641,
+        0x2e56a7750c7deae0,
+        0x05ba8e6bd5f3d958,
+        0x5407cfbc116bd0ae,
+        0x1623f25ffa0f0b1a,
+        0x63d911c3cc712249,
+        0x2395aed68106ce9f,
+        0x31686905d4270685,
+    ],
+    [
+        0x3934f306e92dd326,
+        0x6b295e23fdccb3fd,
+        0x0f35e704d5d46548,
+        0x634fbf27e2cdcf04,
+        0x616cddd76182589d,
+        0x0e0296c5424575e7,
+        0x1fa05dd2a394eb56,
+        0x195aec664f09cc53,
+        0x7d67f08b75f18c21,
+        0x746ed140a4f67160,
+        0x6c78dd82b3d43e1f,
+        0x7b2f163c671900e2,
+    ],
+    [
+        0x73617780aff74228,
+        0x55ae85a2432a6982,
+        0x5c703d2086955d74,
+        0x5674a780cbb094b3,
+        0x116f1b906d84bf2b,
+        0x4ae8087c3ea0da42,
+        0x6d42b1ca98be76f2,
+        0x3db8eaae578d36b4,
+        0x247005cf35616356,
+        0x637f66dbf8592f92,
+        0x65c6e6dacbabe704,
+        0x5d541346a79c1dc6,
+    ],
+    [
+        0x52202ba9b7cee939,
+        0x69b6520c0d33b65e,
+        0x048a43a9c9fb71f8,
+        0x35fda7e8fe13c031,
+        0x7560242c08a0a3c1,
+        0x2c725cdde3bb1224,
+        0x3061bdf1d478ee48,
+        0x0f4d9a3963cb4d1d,
+        0x6020d8c4995229ab,
+        0x7a1fe234de5d8172,
+        0x5c7bc385a03ea177,
+        0x4b52b69c02d313c7,
+    ],
+    [
+        0x55fe709776b55581,
+        0x1361790be33da557,
+        0x08cdf69fb5f87708,
+        0x65b029729a16b7f4,
+        0x357f6950aed87284,
+        0x4a9c53aebd5b786b,
+        0x6834d30348db5898,
+        0x07b7a42ead14a587,
+        0x57b4032f9612dbaa,
+        0x77c02113aae2ed7f,
+        0x0b7671195954891f,
+        0x47e64e927f77f284,
+    ],
+    [
+        0x31a8b8082c83a22c,
+        0x4bf0950b8602a504,
+        0x31d6e08418c14f3d,
+        0x6bc122ec885e8061,
+        0x1ef58300f4efe69f,
+        0x03479fc0ddcd5353,
+        0x1db0315284a390e4,
+        0x047254627899f7e5,
+        0x331acaf7bb818061,
+        0x07204fdd1c0d887d,
+        0x29b714c4f627d686,
+        0x771b574a2db73a3d,
+    ],
+    [
+        0x716966d177a000b9,
+        0x1d6fac43a3fdfa6d,
+        0x5303976249ae4092,
+        0x3fb7b8b5e385223e,
+        0x4b349967b6674ed4,
+        0x073097f08a79d25a,
+        0x13ddcb753f64d655,
+        0x0ecaea1841c349bc,
+        0x7c3a9a27e400b24c,
+        0x302e6a2d02e9857f,
+        0x0d798d7453adf7ee,
+        0x0a2efcfd4fa3a8ad,
+    ],
+    [
+        0x7a37ecc85efd9094,
+        0x32ad84b7aaa19634,
+        0x14dc2c14b64b2872,
+        0x11c91e3395d2e9e8,
+        0x73f7b9a42c18509a,
+        0x0d8207a1e2f1a311,
+        0x138fc01396de7300,
+        0x19dd73c0f15b043e,
+        0x4103be291ce82b53,
+        0x38d7266ce1ea9b56,
+        0x7efe46e0a223a5c2,
+        0x2b2c90c2c70e5c31,
+    ],
+    [
+        0x668ddadb96a2a5f9,
+        0x031256caad0eea7c,
+        0x61b6c827cc4acde7,
+        0x199bcad459e1e2e4,
+        0x38004b2983e9ecde,
+        0x09b34d984e89be8c,
+        0x5fb157a6f01fee59,
+        0x3f8941227e664144,
+        0x4c8e10b9b3628096,
+        0x57af0884d0c373f9,
+        0x20cf68c32c6e134c,
+        0x5181a27db2f90336,
+    ],
+    [
+        0x07998a331e8ab3e3,
+        0x795edd769e9d2534,
+        0x25959f5d365f7680,
+        0x1b1cb8a6d40a21d7,
+        0x0e72ae8f3b7010b6,
+        0x3000d383938faa7e,
+        0x2e441001d29ed9f7,
+        0x4598397613668114,
+        0x1017c6513d2afcaf,
+        0x7dd9568a34e994c3,
+        0x1c37d3551cec6952,
+        0x3c917cf052f79be9,
+    ],
+    [
+        0x41715c0633804831,
+        0x77bb63ef92251972,
+        0x5b076894518c26ae,
+        0x18164a5bd2138a34,
+        0x5a61936f74bd078b,
+        0x3a5ce4238f0f01b7,
+        0x52b046682f53263d,
+        0x6ec7a3f1aa00fa9d,
+        0x3efa66a143b5a4b6,
+        0x7df9a55626b1d01c,
+        0x1758c6aa6bb6791e,
+        0x5603fc94c9670fcb,
+    ],
+    [
+        0x6a4a9a4c6699539b,
+        0x52dbc1f2d11d85d1,
+        0x12a042701b1a918f,
+        0x5b0b34c94857ada8,
+        0x21093225ec65d86f,
+        0x27028cd3de9c97fb,
+        0x1f555a872451d7a8,
+        0x39d3aec6482831f1,
+        0x7420b60ee5a69726,
+        0x43e7207f50a945eb,
+        0x52900b57f07af534,
+        0x7d3951eefdf3d18a,
+    ],
+    [
+        0x060e60383bf9795f,
+        0x5a0bd20a7ea1045b,
+        0x79b52668b0c08496,
+        0x45b63e24ca8861bc,
+        0x14c6e3512ba5d15a,
+        0x544f271631c03fbe,
+        0x264f80b3eac3312d,
+        0x036500f24730edd3,
+        0x38f7fcf5d554b100,
+        0x1b3482a9282b89ae,
+        0x3b43a9f11cfb775e,
+        0x0099a9900cc3ede0,
+    ],
+    [
+        0x6f3b73a582b7d24d,
+        0x67b67b69275d1e22,
+        0x3e18d7f3b29ba8a7,
+        0x122d8cbc9f23c286,
+        0x2dae29b745288ca6,
+        0x70503828151e17b3,
+        0x3f06a8b330518066,
+        0x7e2fea01b6f95c65,
+        0x5a776641ac7423ba,
+        0x7fc3b2fda3ad6665,
+        0x180837702ef451da,
+        0x3bd6dafe63869237,
+    ],
+    [
+        0x011c0e85df12b76a,
+        0x7d1b7bde6d415705,
+        0x0f3c7cd3e7d9f434,
+        0x7801f60036916601,
+        0x7ce333b37d946960,
+        0x716e36599ced7eec,
+        0x5a39fce3c0b72bf2,
+        0x085038fc5df3a0db,
+        0x22a2723b90b6f75a,
+        0x58dd23b77257697c,
+        0x03f8a06db2c14f4d,
+        0x4649c1de1f6e7122,
+    ],
+    [
+        0x5c5b70718ac40238,
+        0x6c93fe55cafb3cde,
+        0x443ed8d622be2d27,
+        0x0b3f5b72749fb969,
+        0x7dcb31a7c8f49f5d,
+        0x786447815ba66442,
+        0x4242b245117ef92a,
+        0x40639a528e676eb3,
+        0x10da8ea19526037b,
+        0x5d5a3009afc90584,
+        0x617e4e2f69c97cea,
+        0x201c9a022d65eb71,
+    ],
+    [
+        0x215d98efd15059c1,
+        0x38090ebc2c0fe4e4,
+        0x18e918591df006d8,
+        0x030b44308ff60be9,
+        0x19e000378b0346dc,
+        0x24158c2520ab2da8,
+        0x51ffdbc1909fa892,
+        0x2aab85a45c682ea6,
+        0x0970cc3c627b0210,
+        0x66b0c237f0dbcdf1,
+        0x6c438eba6671e0d8,
+        0x13ba4e1fe3e4afc0,
+    ],
+    [
+        0x484f961fd64acc39,
+        0x448a3a167e1d2b1f,
+        0x6f2f66ed7eb3833a,
+        0x731e3e26b0550e1d,
+        0x079746a448b3a679,
+        0x05fae5ae51e9e258,
+        0x01d8f7be3895cd95,
+        0x052e3ddae58eb662,
+        0x39de3d837a4c4d51,
+        0x29180483e17b7e71,
+        0x1d9e574b0b82b116,
+        0x34579327a6f401e0,
+    ],
+    [
+        0x61153dd98bc1448c,
+        0x5262c13f72affbf3,
+        0x289d87071ed19f03,
+        0x1074ad48dc6f6fae,
+        0x15ff417c71aee4b3,
+        0x1bf770f46fd35c3e,
+        0x0ad54ea954ad6eb4,
+        0x3b8929cb311a799b,
+        0x51449aff107eaf36,
+        0x24c8753acfc3e87b,
+        0x64ca9320c637abc0,
+        0x3332fc674cd87ec1,
+    ],
+    [
+        0x3f6cd5980e344ae8,
+        0x0c03b731b3375d19,
+        0x4e7ea052afc5e0e5,
+        0x5daffc37e62be50f,
+        0x6eebeb19aa97983c,
+        0x1f1d93f98d1da636,
+        0x455c1a524cc6fb96,
+        0x7442e1a99045bbba,
+        0x6c908e8d08fc46bc,
+        0x024fe396805dcafe,
+        0x64baf7df6364999b,
+        0x3bc1d0f9b5d033d0,
+    ],
+    [
+        0x6979f31eaad7a2b4,
+        0x2de690a2e343462f,
+        0x5ea02b05fcf3eb8b,
+        0x5740c6f0d305e3c1,
+        0x698c9711987665f0,
+        0x19aac675d82815eb,
+        0x742cf8fd4551f0ea,
+        0x699753e0a0cc169e,
+        0x09de25014328a4bf,
+        0x7b1539a8ad76e474,
+        0x27e8742e599d802a,
+        0x097bda46a233c09e,
+    ],
+    [
+        0x724eb8256ffbbeed,
+        0x05bea57fd86ebfd3,
+        0x0f42a68925cdfa60,
+        0x468ea5171a8bc3da,
+        0x04fcb24a79282755,
+        0x1e0b9975817d1657,
+        0x1b0971ee3100d890,
+        0x464a27c8ea3322e5,
+        0x16930a83b4c5a4ba,
+        0x1bd109e895e4ecd0,
+        0x165662ff33d29706,
+        0x6fdaa180e2a87195,
+    ],
+];
+
+const MDS: [[u64; WIDTH]; WIDTH] = [
+    [
+        0x099e8a09a3f7a018,
+        0x35f03d1974d44b17,
+        0x52154a7d5486f3ea,
+        0x33457fe223c63a1a,
+        0x0a9b6bae3d29418d,
+        0x2d75d2879c4db1ac,
+        0x4f636d315ec1e70a,
+        0x28d8c7e2854a0654,
+        0x0ba9135976167325,
+        0x5a2926a55af1aa9c,
+        0x66d69b622fe548c8,
+        0x61824f2354ce90ac,
+    ],
+    [
+        0x57b25e1d272d5f3d,
+        0x0cf7b4d54f0d8518,
+        0x2326f141eb4badee,
+        0x4adb6342f4043a14,
+        0x0e578213ab16e1a8,
+        0x78293653c07540c8,
+        0x0a758489d1094c97,
+        0x65c4eed68807e895,
+        0x076c0e6e670ef255,
+        0x36db9efe3929ea11,
+        0x702fa43ee671965f,
+        0x422ba0e18fd82e39,
+    ],
+    [
+        0x51d207d85c9bf352,
+        0x652e98339734785c,
+        0x2ebdcec62d98b743,
+        0x2327773e25f6d361,
+        0x1c46b174e86cab30,
+        0x11b1cae1ee9a805f,
+        0x10dd0c9434040771,
+        0x4d99d51248ce4840,
+        0x0ff0816b721c227f,
+        0x50443282dbc4941d,
+        0x1975e47b5906ce1b,
+        0x09fabf1a42ff91f0,
+    ],
+    [
+        0x3508b08abe2abed1,
+        0x6b3d91c2faf72d46,
+        0x19eeade1b7efedb5,
+        0x1b18fe4e22d33c62,
+        0x534517a789e30175,
+        0x36c9a5982b975bc7,
+        0x382dad895db1b82f,
+        0x48a5fc56ed26c4f3,
+        0x7de7547a33cc4e6b,
+        0x0c1dbf56c185f409,
+        0x5acbe21cbc97ffef,
+        0x2dc4c15e9f4df12c,
+    ],
+    [
+        0x41dd0d648ec9ad74,
+        0x039677ba2ac7297f,
+        0x2bd1e364ec5e98a5,
+        0x2dab961bd8a1bc42,
+        0x056ae74c28888f94,
+        0x1719fdd68702354a,
+        0x4a1e521010eed69d,
+        0x473b17217b2dfdae,
+        0x48b03f14af14e4f1,
+        0x61d42f53b918b3d2,
+        0x49bfec1e3bab1a76,
+        0x6bbe19c459bdd2ae,
+    ],
+    [
+        0x74806a2d2cf0b0e0,
+        0x36c7c03583c2b66c,
+        0x167b9d5f965861a8,
+        0x0109ef72e0c2b3ce,
+        0x0e47b34503cd1625,
+        0x57f9bd82f592b887,
+        0x569ff9deab326ead,
+        0x1f319ea2869d6cf5,
+        0x322820904a8d2d5c,
+        0x26bd2a3d36b8da06,
+        0x4ebd1606aac1f07e,
+        0x5a6cf07448e2dbf1,
+    ],
+    [
+        0x02ce34fff092be6a,
+        0x53e4aafd4d31192f,
+        0x669d2a10e3188599,
+        0x7e7a9edc51e9be7e,
+        0x395941e29713f86f,
+        0x652bac3f503b6097,
+        0x4f62985fd586d192,
+        0x6fd527280ab60abb,
+        0x089563e48762ba40,
+        0x3508e2aa55d30e5f,
+        0x12a9868b7be819d2,
+        0x32d9dfcb10c8f143,
+    ],
+    [
+        0x57b54bb07963188e,
+        0x568d7bcbb3c1982c,
+        0x3f18b1d88dc1ab4d,
+        0x56faa8dfc3aec986,
+        0x314dacefe7a2e0bf,
+        0x1590ac9a2031294d,
+        0x7b8c96d13b564db4,
+        0x3ef4be4db200dde5,
+        0x51284ad78cefd9df,
+        0x393d29c45bdbd91c,
+        0x785ec30d0209b3be,
+        0x32be49877a12704b,
+    ],
+    [
+        0x76ca72faf11705fe,
+        0x53bc2435e999c850,
+        0x07ca4080b7a71bc5,
+        0x00e451ef4f7a42a8,
+        0x21f39680a43e8958,
+        0x42e0f42e97a4b0a3,
+        0x421c2f67562d8d62,
+        0x03839f14f90195cd,
+        0x6d7db3c1ef5a1ecc,
+        0x7ae424d37478bedc,
+        0x07ae37f83ef5d168,
+        0x119826394cc9e0b5,
+    ],
+    [
+        0x3d2b025d551ab7be,
+        0x2c3ca74046f4fd7c,
+        0x0d3cb5268842c9ba,
+        0x67e9b8d8a00948b3,
+        0x4b758ace4aa5f592,
+        0x3a5f468148cf221d,
+        0x5ed51823ebd74e14,
+        0x2effccf47c437184,
+        0x401565cf0a0cd7ea,
+        0x381f5bafcba9d6de,
+        0x735c817dbe613254,
+        0x1aae25201fdbfabb,
+    ],
+    [
+        0x4329ff5c73e1944f,
+        0x411707f725f00976,
+        0x7b458b8bdb04f129,
+        0x3112f0240db02345,
+        0x2b7505bdbbef6118,
+        0x0379e5881c247746,
+        0x7cdec3a3b87dc04a,
+        0x113337b69ab82ef0,
+        0x619303e29ef5ab56,
+        0x3a51e9cd3ed30b72,
+        0x26ff317efd72a235,
+        0x5d6c92b802dc098b,
+    ],
+    [
+        0x069c19db6def01b7,
+        0x423de423482c5480,
+        0x0444e1dc89c5f0fc,
+        0x23e0d85e88bc7044,
+        0x097f0b9656c01eb9,
+        0x68cc8ae68f8c9ba3,
+        0x7450a3e60e067f6b,
+        0x729ed78eb47ca84e,
+        0x096818c9f908b085,
+        0x3736feae1281f80b,
+        0x1e45abe14aa6e549,
+        0x7f5b3497ef10d0d7,
+    ],
+];
+
+fn to_field(x: u64) -> PoseidonField {
+    PoseidonField::try_from(x).unwrap()
+}
+
+fn round_constants() -> &'static [[PoseidonField; WIDTH]; FULL_ROUNDS + PARTIAL_ROUNDS] {
+    static CONSTANTS: OnceLock<[[PoseidonField; WIDTH]; FULL_ROUNDS + PARTIAL_ROUNDS]> =
+        OnceLock::new();
+    CONSTANTS.get_or_init(|| ROUND_CONSTANTS.map(|round| round.map(to_field)))
+}
+
+fn mds() -> &'static [[PoseidonField; WIDTH]; WIDTH] {
+    static MATRIX: OnceLock<[[PoseidonField; WIDTH]; WIDTH]> = OnceLock::new();
+    MATRIX.get_or_init(|| MDS.map(|row| row.map(to_field)))
+}
+
+/// Apply the Poseidon permutation [GKRRS21] to `state`.
+pub fn poseidon_permutation(state: &mut [PoseidonField; WIDTH]) {
+    let mds = mds();
+    let partial_rounds = FULL_ROUNDS / 2..FULL_ROUNDS / 2 + PARTIAL_ROUNDS;
+    for (r, constants) in round_constants().iter().enumerate() {
+        for (x, c) in state.iter_mut().zip(constants) {
+            *x += c;
+        }
+        if !partial_rounds.contains(&r) {
+            for x in state.iter_mut() {
+                *x = x.pow(ALPHA);
+            }
+        } else {
+            state[0] = state[0].pow(ALPHA);
+        }
+        *state = mds.map(|row| row.iter().zip(state.iter()).map(|(m, x)| *m * x).sum());
+    }
+}
+
+impl<C: ZqConfig<L>, const L: usize> Unit for Zq<C, L> {
+    fn write(bunch: &[Self], w: &mut impl std::io::Write) -> Result<(), std::io::Error> {
+        for x in bunch {
+            x.serialize_compressed(&mut *w)
+                .map_err(|e| std::io::Error::other(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    fn read(r: &mut impl std::io::Read, bunch: &mut [Self]) -> Result<(), std::io::Error> {
+        for x in bunch.iter_mut() {
+            *x = Self::deserialize_compressed(&mut *r)
+                .map_err(|e| std::io::Error::other(e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+/// State of the Poseidon sponge over [`PoseidonField`], to be used in a [`DuplexSponge`] (see [`Poseidon`]).
+#[derive(Clone, Default, Zeroize)]
+pub struct PoseidonSponge {
+    state: [PoseidonField; WIDTH],
+}
+
+impl AsRef<[PoseidonField]> for PoseidonSponge {
+    fn as_ref(&self) -> &[PoseidonField] {
+        &self.state
+    }
+}
+
+impl AsMut<[PoseidonField]> for PoseidonSponge {
+    fn as_mut(&mut self) -> &mut [PoseidonField] {
+        &mut self.state
+    }
+}
+
+impl Sponge for PoseidonSponge {
+    type U = PoseidonField;
+    const N: usize = WIDTH;
+    const R: usize = RATE;
+
+    /// Write the `iv` into the capacity, as 4 little-endian 64-bit integers reduced modulo [`POSEIDON_MODULUS`].
+    fn new(iv: [u8; 32]) -> Self {
+        let mut sponge = Self::default();
+        for (x, chunk) in sponge.state[RATE..].iter_mut().zip(iv.chunks_exact(8)) {
+            *x = to_field(u64::from_le_bytes(chunk.try_into().unwrap()) % POSEIDON_MODULUS);
+        }
+        sponge
+    }
+
+    fn permute(&mut self) {
+        poseidon_permutation(&mut self.state);
+    }
+}
+
+/// Duplex hash absorbing and squeezing elements of [`PoseidonField`], for transcripts `Merlin<Poseidon, PoseidonField>` and `Arthur<Poseidon, PoseidonField>` whose hash is cheap to arithmetize.
+pub type Poseidon = DuplexSponge<PoseidonSponge>;
+
+/// Number of bytes packed into each field element absorbed by [`PoseidonBytes`].
+const BYTES_PER_ABSORBED_ELEMENT: usize = 7;
+/// Number of uniform bytes extracted from each field element squeezed by [`PoseidonBytes`].
+const BYTES_PER_SQUEEZED_ELEMENT: usize = 4;
+
+/// Byte-oriented duplex hash on top of [`Poseidon`], such that [`SerMerlin`](crate::nimue::merlin::SerMerlin), [`SerArthur`](crate::nimue::arthur::SerArthur) and the challenge traits work unchanged.
+/// Absorbed bytes are packed into field elements 7 at a time (little-endian, zero-padded before squeezing or ratcheting); since the [`IOPattern`](nimue::IOPattern) fixes the length of each absorption, the padding is unambiguous.
+/// Each squeezed field element $x \neq p - 1$ yields the 4 bytes of $x \bmod 2^{32}$, which are uniform since $2^{32}$ divides $p - 1$; $x = p - 1$ is rejected.
+#[derive(Clone, Default, Zeroize)]
+pub struct PoseidonBytes {
+    sponge: Poseidon,
+    absorb_buffer: Vec<u8>,
+    squeeze_buffer: Vec<u8>,
+}
+
+impl PoseidonBytes {
+    fn flush(&mut self) {
+        if self.absorb_buffer.is_empty() {
+            return;
+        }
+        let mut bytes = [0u8; 8];
+        bytes[..self.absorb_buffer.len()].copy_from_slice(&self.absorb_buffer);
+        self.sponge
+            .absorb_unchecked(&[to_field(u64::from_le_bytes(bytes))]);
+        self.absorb_buffer.clear();
+    }
+}
+
+impl DuplexHash<u8> for PoseidonBytes {
+    fn new(iv: [u8; 32]) -> Self {
+        PoseidonBytes {
+            sponge: DuplexHash::new(iv),
+            absorb_buffer: Vec::with_capacity(BYTES_PER_ABSORBED_ELEMENT),
+            squeeze_buffer: Vec::with_capacity(BYTES_PER_SQUEEZED_ELEMENT),
+        }
+    }
+
+    fn absorb_unchecked(&mut self, input: &[u8]) -> &mut Self {
+        self.squeeze_buffer.clear();
+        for byte in input {
+            self.absorb_buffer.push(*byte);
+            if self.absorb_buffer.len() == BYTES_PER_ABSORBED_ELEMENT {
+                self.flush();
+            }
+        }
+        self
+    }
+
+    fn squeeze_unchecked(&mut self, output: &mut [u8]) -> &mut Self {
+        self.flush();
+        for byte in output.iter_mut() {
+            while self.squeeze_buffer.is_empty() {
+                let mut x = [PoseidonField::ZERO];
+                self.sponge.squeeze_unchecked(&mut x);
+                let x = BigUint::from(x[0]).to_u64().unwrap();
+                if x != POSEIDON_MODULUS - 1 {
+                    // Stored in reverse, such that bytes are popped in little-endian order
+                    self.squeeze_buffer.extend((x as u32).to_be_bytes());
+                }
+            }
+            *byte = self.squeeze_buffer.pop().unwrap();
+        }
+        self
+    }
+
+    fn ratchet_unchecked(&mut self) -> &mut Self {
+        self.flush();
+        self.squeeze_buffer.clear();
+        self.sponge.ratchet_unchecked();
+        self
+    }
+}
+
+/// Absorb vectors of field elements into a field-native transcript without serializing them to bytes.
+pub trait FieldMerlin<F: Ring + Unit> {
+    fn absorb_field_vector(&mut self, v: &Vector<F>) -> Result<(), IOPatternError>;
+}
+
+impl<F: Ring + Unit, H: DuplexHash<F>, R: RngCore + CryptoRng> FieldMerlin<F> for Merlin<H, F, R> {
+    fn absorb_field_vector(&mut self, v: &Vector<F>) -> Result<(), IOPatternError> {
+        self.add_units(v.as_slice())
+    }
+}
+
+/// Read vectors of field elements from a field-native transcript, see [`FieldMerlin`].
+pub trait FieldArthur<F: Ring + Unit> {
+    fn next_field_vector(&mut self, n: usize) -> Result<Vector<F>, IOPatternError>;
+}
+
+impl<F: Ring + Unit, H: DuplexHash<F>> FieldArthur<F> for Arthur<'_, H, F> {
+    fn next_field_vector(&mut self, n: usize) -> Result<Vector<F>, IOPatternError> {
+        let mut v = vec![F::ZERO; n];
+        self.fill_next_units(&mut v)?;
+        Ok(Vector::<F>::from_vec(v))
+    }
+}
+
+/// Squeeze vectors of field elements from a field-native transcript.
+pub trait FieldChallenges<F: Ring + Unit>: UnitTranscript<F> {
+    fn challenge_field_vector(&mut self, n: usize) -> Result<Vector<F>, IOPatternError> {
+        let mut v = vec![F::ZERO; n];
+        self.fill_challenge_units(&mut v)?;
+        Ok(Vector::<F>::from_vec(v))
+    }
+}
+
+impl<F: Ring + Unit, T: UnitTranscript<F>> FieldChallenges<F> for T {}
+
+#[cfg(test)]
+mod test {
+    use nimue::{ByteChallenges, ByteIOPattern, DefaultHash, IOPattern};
+
+    use crate::linear_algebra::{Matrix, SymmetricMatrix};
+    use crate::nimue::arthur::SerArthur;
+    use crate::nimue::iopattern::{SerIOPattern, SqueezeFromRandomBytes};
+    use crate::nimue::merlin::SerMerlin;
+    use crate::nimue::traits::ChallengeFromRandomBytes;
+
+    use super::*;
+
+    type F = PoseidonField;
+
+    /// Grain LFSR of the reference parameter script, after the 160 discarded initialization rounds.
+    struct Grain([bool; 80]);
+
+    impl Grain {
+        fn new() -> Self {
+            let mut bits = vec![];
+            for (value, len) in [
+                (1, 2),
+                (0, 4),
+                (63, 12),
+                (WIDTH, 12),
+                (FULL_ROUNDS, 10),
+                (PARTIAL_ROUNDS, 10),
+            ] {
+                bits.extend((0..len).rev().map(|i| (value >> i) & 1 == 1));
+            }
+            bits.extend([true; 30]);
+            let mut grain = Grain(bits.try_into().unwrap());
+            for _ in 0..160 {
+                grain.step();
+            }
+            grain
+        }
+
+        fn step(&mut self) -> bool {
+            let s = &self.0;
+            let bit = s[62] ^ s[51] ^ s[38] ^ s[23] ^ s[13] ^ s[0];
+            self.0.rotate_left(1);
+            self.0[79] = bit;
+            bit
+        }
+
+        /// Self-shrinking output: a bit is output iff the previous bit is 1.
+        fn next_bit(&mut self) -> bool {
+            while !self.step() {
+                self.step();
+            }
+            self.step()
+        }
+
+        fn next_u63(&mut self) -> u64 {
+            (0..63).fold(0, |acc, _| (acc << 1) | self.next_bit() as u64)
+        }
+    }
+
+    #[test]
+    fn test_grain_constants() {
+        let mut grain = Grain::new();
+        for round in ROUND_CONSTANTS {
+            for c in round {
+                let mut x = grain.next_u63();
+                while x >= POSEIDON_MODULUS {
+                    x = grain.next_u63();
+                }
+                assert_eq!(x, c);
+            }
+        }
+        // The first 2t elements are distinct and all x_i + y_j are non-zero
+        let xy: Vec<F> = (0..2 * WIDTH)
+            .map(|_| to_field(grain.next_u63() % POSEIDON_MODULUS))
+            .collect();
+        let (xs, ys) = xy.split_at(WIDTH);
+        for (i, x) in xs.iter().enumerate() {
+            for (j, y) in ys.iter().enumerate() {
+                assert_eq!(mds()[i][j], (*x + y).inverse().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_permutation_known_answer() {
+        let mut state: [F; WIDTH] = core::array::from_fn(|i| to_field(i as u64));
+        poseidon_permutation(&mut state);
+        assert_eq!(
+            state.map(|x| BigUint::from(x).to_u64().unwrap()),
+            [
+                0x5466b3716ad70c74,
+                0x539549a88da631cf,
+                0x23edbb65a33f8197,
+                0x5e5ed425328579b2,
+                0x39fe19040656ee32,
+                0x6136db32584a7021,
+                0x3d1e32a20ab4e035,
+                0x27676ece5273c15c,
+                0x4fde2ec0d482f1cf,
+                0x322e212697dc2d10,
+                0x19e1f66e038886f3,
+                0x0c98c838a77146d0,
+            ]
+        );
+
+        let mut state = [F::ZERO; WIDTH];
+        poseidon_permutation(&mut state);
+        assert_eq!(
+            state.map(|x| BigUint::from(x).to_u64().unwrap()),
+            [
+                0x79c94c4745d73a20,
+                0x31242565ad9b8b67,
+                0x25cc5057031fcbca,
+                0x0a9e812ef1f75874,
+                0x3beb83db270a6e94,
+                0x17b7e66035c38635,
+                0x1765e55c4b82a4b0,
+                0x574292147af1c500,
+                0x686a78bcafb0a924,
+                0x4fb048d0508a739e,
+                0x2ce538342fcf73dd,
+                0x6129e6137d73fdc8,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_field_transcript() {
+        let rng = &mut ark_std::test_rng();
+        let v = Vector::<F>::rand(10, rng);
+        let io = IOPattern::<Poseidon, F>::new("test_poseidon_field")
+            .absorb(10, "v")
+            .squeeze(3, "challenge");
+
+        let mut merlin = io.to_merlin();
+        merlin.absorb_field_vector(&v).unwrap();
+        let challenge = merlin.challenge_field_vector(3).unwrap();
+        assert_eq!(merlin.transcript().len(), 10 * 8);
+
+        let mut arthur = io.to_arthur(merlin.transcript());
+        assert_eq!(arthur.next_field_vector(10).unwrap(), v);
+        assert_eq!(arthur.challenge_field_vector(3).unwrap(), challenge);
+
+        // A different message yields a different challenge
+        let mut merlin = io.to_merlin();
+        merlin
+            .absorb_field_vector(&(v + Vector::<F>::from_element(10, F::ONE)))
+            .unwrap();
+        assert_ne!(merlin.challenge_field_vector(3).unwrap(), challenge);
+    }
+
+    #[test]
+    fn test_byte_transcript() {
+        type R = Zq1<65537>;
+        let rng = &mut ark_std::test_rng();
+        let mat = Matrix::<R>::rand(3, 5, rng);
+        let sym = SymmetricMatrix::<R>::from(Matrix::<R>::identity(3, 3));
+
+        fn io<H: DuplexHash<u8>>() -> IOPattern<H> {
+            IOPattern::<H>::new("test_poseidon_bytes")
+                .absorb_matrix::<R>(3, 5, "mat")
+                .absorb_symmetric_matrix::<R>(3, "sym")
+                .squeeze_elem::<R, R>("challenge")
+                .challenge_bytes(33, "bytes")
+        }
+
+        let mut merlin = io::<PoseidonBytes>().to_merlin();
+        merlin.absorb(&mat).unwrap();
+        merlin.absorb(&sym).unwrap();
+        let challenge = merlin.challenge::<R, R>().unwrap();
+        let bytes: [u8; 33] = merlin.challenge_bytes().unwrap();
+
+        let mut arthur = io::<PoseidonBytes>().to_arthur(merlin.transcript());
+        assert_eq!(arthur.next_matrix::<R>(3, 5).unwrap(), mat);
+        assert_eq!(arthur.next_symmetric_matrix::<R>(3).unwrap(), sym);
+        assert_eq!(arthur.challenge::<R, R>().unwrap(), challenge);
+        assert_eq!(arthur.challenge_bytes::<33>().unwrap(), bytes);
+
+        // The same transcript over the default hash has the same messages, but different challenges
+        let mut merlin_default = io::<DefaultHash>().to_merlin();
+        merlin_default.absorb(&mat).unwrap();
+        merlin_default.absorb(&sym).unwrap();
+        assert_eq!(merlin_default.transcript(), merlin.transcript());
+        assert_ne!(merlin_default.challenge_bytes::<33>().unwrap(), bytes);
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use ark_std::rand::{CryptoRng, RngCore};
+use nimue::hash::Keccak;
+use nimue::{
+    Arthur, ByteReader, ByteWriter, DuplexHash, IOPattern, IOPatternError, Merlin, UnitTranscript,
+};
+
+use crate::nimue::arthur::SerArthur;
+use crate::nimue::merlin::SerMerlin;
+use crate::nimue::traits::{ChallengeFromRandomBytes, RatchetTranscript};
+
+/// Initialization vector of the sponge computing the digests of [`TraceEntry`]s.
+const DIGEST_IV: [u8; 32] = *b"lattirust/transcript-trace/v1\0\0\0";
+
+/// Direction of a transcript operation, from the point of view of the sponge.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Prover messages and public inputs.
+    Absorb,
+    /// Verifier challenges.
+    Squeeze,
+    Ratchet,
+}
+
+/// A single operation of the IO pattern, as performed on a transcript.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceEntry {
+    /// Label of the operation in the IO pattern.
+    pub label: String,
+    pub direction: Direction,
+    /// Number of bytes absorbed or squeezed, 0 for ratchets.
+    pub len: usize,
+    /// Keccak digest of the bytes absorbed or squeezed.
+    pub digest: [u8; 32],
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:?} {:?} ({} bytes, ",
+            self.direction, self.label, self.len
+        )?;
+        for b in &self.digest[..8] {
+            write!(f, "{b:02x}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Sequence of operations performed on a transcript, see [`Traced`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TranscriptTrace(pub Vec<TraceEntry>);
+
+impl TranscriptTrace {
+    /// Return the total number of bytes moved in `direction`, e.g., the proof size plus the size of the public inputs for [`Direction::Absorb`].
+    pub fn len_by(&self, direction: Direction) -> usize {
+        self.0
+            .iter()
+            .filter(|e| e.direction == direction)
+            .map(|e| e.len)
+            .sum()
+    }
+}
+
+impl fmt::Display for TranscriptTrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, entry) in self.0.iter().enumerate() {
+            writeln!(f, "{i:>4}: {entry}")?;
+        }
+        write!(
+            f,
+            "absorbed {} bytes, squeezed {} bytes",
+            self.len_by(Direction::Absorb),
+            self.len_by(Direction::Squeeze)
+        )
+    }
+}
+
+/// Mismatch between two traces at operation `index`; an entry is `None` if its trace ended before `index`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    pub index: usize,
+    pub left: Option<TraceEntry>,
+    pub right: Option<TraceEntry>,
+}
+
+impl Divergence {
+    /// Return the label of the diverging operation.
+    pub fn label(&self) -> &str {
+        self.left
+            .as_ref()
+            .or(self.right.as_ref())
+            .map_or("", |e| e.label.as_str())
+    }
+}
+
+/// Return all operations at which `trace_a` and `trace_b` differ, in order.
+/// Since every challenge depends on all previous operations, the first divergence is usually the culprit, and all subsequent challenges diverge as well.
+pub fn diff(trace_a: &TranscriptTrace, trace_b: &TranscriptTrace) -> Vec<Divergence> {
+    let (a, b) = (&trace_a.0, &trace_b.0);
+    (0..a.len().max(b.len()))
+        .filter(|&i| a.get(i) != b.get(i))
+        .map(|i| Divergence {
+            index: i,
+            left: a.get(i).cloned(),
+            right: b.get(i).cloned(),
+        })
+        .collect()
+}
+
+/// Wrapper around [`Merlin`] and [`Arthur`] recording every operation into a [`TranscriptTrace`], labeled as declared in the IO pattern.
+/// Successive calls within the same operation of the IO pattern (e.g., the chunks written by [`SerMerlin::absorb`]) are recorded as a single entry.
+pub struct Traced<T> {
+    inner: T,
+    ops: VecDeque<(Direction, usize, String)>,
+    pending: Vec<u8>,
+    trace: TranscriptTrace,
+}
+
+impl<T> Traced<T> {
+    /// Wrap `inner`, which needs to follow `io_pattern`.
+    pub fn new<H: DuplexHash<u8>>(inner: T, io_pattern: &IOPattern<H>) -> Self {
+        // Skip the domain separator
+        let ops = io_pattern
+            .as_bytes()
+            .split(|&b| b == 0)
+            .skip(1)
+            .map(|op| {
+                let digits = op[1..].iter().take_while(|b| b.is_ascii_digit()).count();
+                let count = op[1..1 + digits]
+                    .iter()
+                    .fold(0, |acc, b| acc * 10 + (b - b'0') as usize);
+                let label = String::from_utf8_lossy(&op[1 + digits..]).into_owned();
+                let direction = match op[0] {
+                    b'A' => Direction::Absorb,
+                    b'S' => Direction::Squeeze,
+                    _ => Direction::Ratchet,
+                };
+                (direction, count, label)
+            })
+            .collect();
+        Traced {
+            inner,
+            ops,
+            pending: vec![],
+            trace: TranscriptTrace::default(),
+        }
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Return the operations completed so far.
+    pub fn trace(&self) -> &TranscriptTrace {
+        &self.trace
+    }
+
+    /// Return the wrapped transcript and its trace, including the last operation if it was only partially performed.
+    pub fn into_parts(mut self) -> (T, TranscriptTrace) {
+        if let Some((direction, _, label)) = self.ops.pop_front() {
+            if !self.pending.is_empty() {
+                self.push(label, direction);
+            }
+        }
+        (self.inner, self.trace)
+    }
+
+    fn push(&mut self, label: String, direction: Direction) {
+        let mut digest = [0u8; 32];
+        Keccak::new(DIGEST_IV)
+            .absorb_unchecked(&self.pending)
+            .squeeze_unchecked(&mut digest);
+        self.trace.0.push(TraceEntry {
+            label,
+            direction,
+            len: self.pending.len(),
+            digest,
+        });
+        self.pending.clear();
+    }
+
+    fn record(&mut self, direction: Direction, mut bytes: &[u8]) {
+        while !bytes.is_empty() || direction == Direction::Ratchet {
+            let Some((expected, remaining, _)) = self.ops.front_mut() else {
+                // Not reachable for operations accepted by the wrapped transcript
+                self.pending.extend_from_slice(bytes);
+                return self.push("<undeclared>".to_string(), direction);
+            };
+            if *expected != direction {
+                self.pending.extend_from_slice(bytes);
+                return self.push("<undeclared>".to_string(), direction);
+            }
+            let take = bytes.len().min(*remaining);
+            self.pending.extend_from_slice(&bytes[..take]);
+            *remaining -= take;
+            bytes = &bytes[take..];
+            if *remaining == 0 {
+                let (_, _, label) = self.ops.pop_front().unwrap();
+                self.push(label, direction);
+                if direction == Direction::Ratchet {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl<T: UnitTranscript<u8>> UnitTranscript<u8> for Traced<T> {
+    fn public_units(&mut self, input: &[u8]) -> Result<(), IOPatternError> {
+        self.inner.public_units(input)?;
+        self.record(Direction::Absorb, input);
+        Ok(())
+    }
+
+    fn fill_challenge_units(&mut self, output: &mut [u8]) -> Result<(), IOPatternError> {
+        self.inner.fill_challenge_units(output)?;
+        self.record(Direction::Squeeze, output);
+        Ok(())
+    }
+}
+
+impl<T: ByteWriter> ByteWriter for Traced<T> {
+    fn add_bytes(&mut self, input: &[u8]) -> Result<(), IOPatternError> {
+        self.inner.add_bytes(input)?;
+        self.record(Direction::Absorb, input);
+        Ok(())
+    }
+}
+
+impl<T: ByteReader> ByteReader for Traced<T> {
+    fn fill_next_bytes(&mut self, input: &mut [u8]) -> Result<(), IOPatternError> {
+        self.inner.fill_next_bytes(input)?;
+        self.record(Direction::Absorb, input);
+        Ok(())
+    }
+}
+
+impl<T: RatchetTranscript> RatchetTranscript for Traced<T> {
+    fn ratchet_transcript(&mut self) -> Result<(), IOPatternError> {
+        self.inner.ratchet_transcript()?;
+        self.record(Direction::Ratchet, &[]);
+        Ok(())
+    }
+}
+
+impl<T: UnitTranscript<u8>> ChallengeFromRandomBytes for Traced<T> {}
+
+impl<H: DuplexHash<u8>, R: RngCore + CryptoRng> SerMerlin<H, R> for Traced<Merlin<H, u8, R>> {}
+
+impl<H: DuplexHash<u8>> SerArthur<H> for Traced<Arthur<'_, H, u8>> {}
+
+#[cfg(test)]
+mod test {
+    use nimue::DefaultHash;
+    use num_traits::One;
+
+    use crate::linear_algebra::{Matrix, Vector};
+    use crate::nimue::iopattern::{SerIOPattern, SqueezeFromRandomBytes};
+    use crate::ring::Zq1;
+
+    use super::*;
+
+    type R = Zq1<65537>;
+
+    fn io_pattern() -> IOPattern<DefaultHash> {
+        IOPattern::<DefaultHash>::new("test_trace")
+            .absorb_vector::<R>(4, "vector")
+            .absorb_matrix::<R>(3, 2, "matrix")
+            .challenge_ring_io::<R>("challenge")
+            .ratchet()
+            .absorb_vector::<R>(2, "response")
+    }
+
+    fn prove(matrix: &Matrix<R>) -> (Vec<u8>, TranscriptTrace) {
+        let io = io_pattern();
+        let mut merlin = Traced::new(io.to_merlin(), &io);
+        let vector = Vector::<R>::from_fn(4, |i, _| R::try_from(i as u64).unwrap());
+        merlin.absorb(&vector).unwrap();
+        merlin.absorb(matrix).unwrap();
+        let c = merlin.challenge_ring::<R>().unwrap();
+        merlin.ratchet_transcript().unwrap();
+        merlin
+            .absorb(&Vector::<R>::from_fn(2, |i, _| c * vector[i]))
+            .unwrap();
+        let (merlin, trace) = merlin.into_parts();
+        (merlin.transcript().to_vec(), trace)
+    }
+
+    fn verify(transcript: &[u8]) -> TranscriptTrace {
+        let io = io_pattern();
+        let mut arthur = Traced::new(io.to_arthur(transcript), &io);
+        arthur.next_vector::<R>(4).unwrap();
+        arthur.next_matrix::<R>(3, 2).unwrap();
+        arthur.challenge_ring::<R>().unwrap();
+        arthur.ratchet_transcript().unwrap();
+        arthur.next_vector::<R>(2).unwrap();
+        arthur.into_parts().1
+    }
+
+    fn matrix() -> Matrix<R> {
+        Matrix::<R>::from_fn(3, 2, |i, j| R::try_from((3 * i + j) as u64).unwrap())
+    }
+
+    #[test]
+    fn test_trace_prover_verifier() {
+        let (transcript, trace) = prove(&matrix());
+        let labels: Vec<_> = trace.0.iter().map(|e| e.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            // Every structured message is followed by a ratchet
+            ["vector", "", "matrix", "", "challenge", "", "response", ""].to_vec()
+        );
+        assert_eq!(trace.len_by(Direction::Absorb), transcript.len());
+        assert!(trace.len_by(Direction::Squeeze) > 0);
+        assert!(diff(&trace, &verify(&transcript)).is_empty());
+        println!("{trace}");
+    }
+
+    #[test]
+    fn test_trace_perturbed_matrix() {
+        let (_, honest) = prove(&matrix());
+        let mut perturbed = matrix();
+        perturbed[(1, 1)] += R::one();
+        let (transcript, _) = prove(&perturbed);
+
+        let divergences = diff(&honest, &verify(&transcript));
+        assert_eq!(divergences[0].index, 2);
+        assert_eq!(divergences[0].label(), "matrix");
+        let (left, right) = (
+            divergences[0].left.as_ref().unwrap(),
+            divergences[0].right.as_ref().unwrap(),
+        );
+        assert_eq!(left.len, right.len);
+        assert_ne!(left.digest, right.digest);
+        // The challenge and everything depending on it diverge as well
+        assert_eq!(divergences[1].label(), "challenge");
+    }
+
+    #[test]
+    fn test_trace_truncated() {
+        let io = io_pattern();
+        let mut merlin = Traced::new(io.to_merlin(), &io);
+        merlin
+            .absorb(&Vector::<R>::from_fn(4, |i, _| {
+                R::try_from(i as u64).unwrap()
+            }))
+            .unwrap();
+        let (_, trace) = merlin.into_parts();
+        let divergences = diff(&trace, &prove(&matrix()).1);
+        assert_eq!(divergences.len(), 6);
+        assert_eq!(divergences[0].label(), "matrix");
+        assert_eq!(divergences[0].left, None);
+    }
+}
@@ -1,12 +1,60 @@
-use ark_std::rand::{CryptoRng, RngCore};
-use nimue::{Arthur, ByteChallenges, DuplexHash, IOPatternError, Merlin};
+use std::fmt::Debug;
+use std::ops::{Deref, DerefMut};
+
+use ark_std::rand::{CryptoRng, RngCore, SeedableRng};
+use nimue::hash::Keccak;
+use nimue::{Arthur, ByteChallenges, BytePublic, DuplexHash, IOPatternError, Merlin};
+use rand_chacha::ChaCha20Rng;
 
 use crate::challenge_set::binary::BinaryChallengeSet;
 use crate::linear_algebra::Matrix;
 use crate::linear_algebra::Scalar;
 use crate::linear_algebra::Vector;
 use crate::ring::Z2;
-use crate::traits::FromRandomBytes;
+use crate::traits::{try_from_random_bytes_chunks, FromRandomBytes};
+
+/// $\log\_2$ of the largest acceptable probability that [`ChallengeFromRandomBytes::challenge_ring`] rejects all candidates for some element of a challenge vector or matrix.
+pub const LOG2_CHALLENGE_FAILURE_PROBABILITY: f64 = -40.;
+
+/// Return the number of candidates squeezed per element by [`ChallengeFromRandomBytes::challenge_ring`] when sampling `n` elements of `R`, such that rejection sampling fails for some element with probability at most $2^\texttt{LOG2\\_CHALLENGE\\_FAILURE\\_PROBABILITY}$ (see [`FromRandomBytes::rejection_probability`]).
+/// All candidates are squeezed up front, so that the prover and the verifier consume the same number of bytes regardless of which candidate is accepted.
+pub fn challenge_attempts<R: FromRandomBytes<R>>(n: usize) -> usize {
+    let rejection_probability = R::rejection_probability();
+    assert!(
+        (0. ..1.).contains(&rejection_probability),
+        "rejection probability must be in [0, 1)"
+    );
+    if rejection_probability == 0. {
+        return 1;
+    }
+    // n * p^k <= 2^LOG2_CHALLENGE_FAILURE_PROBABILITY
+    let log2_target = LOG2_CHALLENGE_FAILURE_PROBABILITY - (n.max(1) as f64).log2();
+    ((log2_target / rejection_probability.log2()).ceil() as usize).max(1)
+}
+
+/// Return the first of `attempts` candidates of `R` squeezed from `transcript` that is accepted by `R::try_from_random_bytes`.
+fn challenge_ring_with_attempts<T: ByteChallenges + ?Sized, R: FromRandomBytes<R>>(
+    transcript: &mut T,
+    attempts: usize,
+) -> Result<R, IOPatternError> {
+    let mut bytes = vec![0u8; attempts * R::byte_size()];
+    transcript.fill_challenge_bytes(&mut bytes)?;
+    bytes
+        .chunks_exact(R::byte_size())
+        .find_map(R::try_from_random_bytes)
+        .ok_or(IOPatternError::from(
+            "all candidates were rejected while generating ring element from random bytes",
+        ))
+}
+
+/// Number of bytes squeezed by [`RngFromTranscript::rng_from_transcript`] to seed a [`TranscriptRng`].
+pub const TRANSCRIPT_RNG_SEED_SIZE: usize = 32;
+
+/// Number of bytes of the digest of a sub-transcript that is merged into the parent transcript, see [`ForkTranscript::fork`].
+pub const SUBTRANSCRIPT_DIGEST_SIZE: usize = 32;
+
+/// Cryptographically secure RNG derived from a transcript, see [`RngFromTranscript::rng_from_transcript`].
+pub type TranscriptRng = ChaCha20Rng;
 
 pub trait ChallengeFromRandomBytes
 where
@@ -26,14 +74,11 @@ where
         &mut self,
         size: usize,
     ) -> Result<Vec<T>, IOPatternError> {
-        let mut vals = Vec::<T>::with_capacity(size);
-        for _ in 0..size {
-            match self.challenge::<T, A>() {
-                Ok(v) => vals.push(v),
-                Err(s) => return Err(s),
-            }
-        }
-        Ok(vals)
+        let mut bytes = vec![0u8; size * A::byte_size()];
+        self.fill_challenge_bytes(&mut bytes)?;
+        try_from_random_bytes_chunks::<T, A>(&bytes, size).ok_or(IOPatternError::from(
+            "error while generating ring element from random bytes",
+        ))
     }
 
     fn challenge_vector<T: Scalar, A: FromRandomBytes<T>>(
@@ -84,6 +129,35 @@ where
         Ok(vals)
     }
 
+    /// Squeeze a challenge by rejection sampling, returning the first of [`challenge_attempts`] candidates accepted by `R::try_from_random_bytes`.
+    /// Needs to be declared with [`SqueezeFromRandomBytes::challenge_ring_io`](crate::nimue::iopattern::SqueezeFromRandomBytes::challenge_ring_io).
+    fn challenge_ring<R: FromRandomBytes<R>>(&mut self) -> Result<R, IOPatternError> {
+        challenge_ring_with_attempts(self, challenge_attempts::<R>(1))
+    }
+
+    fn challenge_ring_vector<R: Scalar + FromRandomBytes<R>>(
+        &mut self,
+        n: usize,
+    ) -> Result<Vector<R>, IOPatternError> {
+        let attempts = challenge_attempts::<R>(n);
+        let vals = (0..n)
+            .map(|_| challenge_ring_with_attempts(self, attempts))
+            .collect::<Result<Vec<R>, IOPatternError>>()?;
+        Ok(Vector::<R>::from_vec(vals))
+    }
+
+    fn challenge_ring_matrix<R: Scalar + FromRandomBytes<R>>(
+        &mut self,
+        nrows: usize,
+        ncols: usize,
+    ) -> Result<Matrix<R>, IOPatternError> {
+        let attempts = challenge_attempts::<R>(nrows * ncols);
+        let vals = (0..nrows * ncols)
+            .map(|_| challenge_ring_with_attempts(self, attempts))
+            .collect::<Result<Vec<R>, IOPatternError>>()?;
+        Ok(Matrix::<R>::from_vec(nrows, ncols, vals))
+    }
+
     fn challenge_binary_matrix(
         &mut self,
         n_rows: usize,
@@ -97,3 +171,426 @@ where
 impl<H: DuplexHash<u8>> ChallengeFromRandomBytes for Arthur<'_, H, u8> {}
 
 impl<H: DuplexHash<u8>, R: RngCore + CryptoRng> ChallengeFromRandomBytes for Merlin<H, u8, R> {}
+
+pub trait RngFromTranscript
+where
+    Self: ByteChallenges,
+{
+    /// Squeeze a seed of [`TRANSCRIPT_RNG_SEED_SIZE`] bytes and return an RNG seeded with it and `label`, for randomness that the prover and the verifier both need to derive, e.g., for rejection sampling or public values that are expanded from the transcript.
+    /// Calls with different labels at the same position of the transcript yield independent RNGs.
+    /// Needs to be declared with [`SqueezeFromRandomBytes::rng_io`](crate::nimue::iopattern::SqueezeFromRandomBytes::rng_io).
+    fn rng_from_transcript(&mut self, label: &[u8]) -> Result<TranscriptRng, IOPatternError> {
+        let squeezed = self.challenge_bytes::<TRANSCRIPT_RNG_SEED_SIZE>()?;
+        let mut seed = [0u8; 32];
+        Keccak::new(squeezed)
+            .absorb_unchecked(label)
+            .squeeze_unchecked(&mut seed);
+        Ok(TranscriptRng::from_seed(seed))
+    }
+}
+
+impl<T: ByteChallenges> RngFromTranscript for T {}
+
+// NOTE: In nimue, ratchet() is not exposed through a trait. This trait allows other traits to call ratchet as part of their implementation.
+pub trait RatchetTranscript {
+    /// Ratchet the transcript, or return an error if the next operation of the IO pattern is not a ratchet.
+    fn ratchet_transcript(&mut self) -> Result<(), IOPatternError>;
+}
+
+// NOTE: nimue's ratchet() panics if the IO pattern is exhausted, which is the case after any IO pattern error since nimue then clears the pattern.
+// The remaining pattern is only exposed through `Debug`, so check it there before ratcheting.
+fn check_next_op_is_ratchet<T: Debug>(transcript: &T) -> Result<(), IOPatternError> {
+    let state = format!("{transcript:?}");
+    if state.contains("IO: [Ratchet") {
+        Ok(())
+    } else {
+        Err(IOPatternError::from(format!(
+            "expected a ratchet, remaining IO pattern: {state}"
+        )))
+    }
+}
+
+/// Clear the IO pattern of `transcript`, such that every further operation fails.
+// NOTE: An empty absorb and an empty squeeze cannot both match the next operation of the IO pattern, and nimue clears the pattern on a mismatch.
+fn clear_io_pattern<T: BytePublic + ByteChallenges + ?Sized>(transcript: &mut T) {
+    let _ = transcript.public_bytes(&[]);
+    let _ = transcript.fill_challenge_bytes(&mut []);
+}
+
+/// End a message absorbed into `transcript` as declared by the helpers of [`SerIOPattern`](crate::nimue::iopattern::SerIOPattern), which follow every message by a ratchet.
+/// Returns an error (and clears the IO pattern) if the message is shorter than declared; nimue already rejects longer messages.
+pub(crate) fn end_message<T>(transcript: &mut T) -> Result<(), IOPatternError>
+where
+    T: RatchetTranscript + BytePublic + ByteChallenges + ?Sized,
+{
+    transcript.ratchet_transcript().map_err(|e| {
+        clear_io_pattern(transcript);
+        IOPatternError::from(format!(
+            "message does not match the size declared in the IO pattern: {e}"
+        ))
+    })
+}
+
+impl<H: DuplexHash<u8>> RatchetTranscript for Arthur<'_, H, u8> {
+    fn ratchet_transcript(&mut self) -> Result<(), IOPatternError> {
+        check_next_op_is_ratchet(self)?;
+        self.ratchet()
+    }
+}
+
+impl<H: DuplexHash<u8>, R: RngCore + CryptoRng> RatchetTranscript for Merlin<H, u8, R> {
+    fn ratchet_transcript(&mut self) -> Result<(), IOPatternError> {
+        check_next_op_is_ratchet(self)?;
+        self.ratchet()
+    }
+}
+
+pub trait ForkTranscript
+where
+    Self: RatchetTranscript + BytePublic + ByteChallenges + Sized,
+{
+    /// Start a sub-protocol in its own domain: ratchet the transcript and absorb `label`, such that all challenges of the sub-protocol depend on `label`.
+    /// The returned [`SubTranscript`] dereferences to `self`, so the sub-protocol uses the usual extension traits.
+    /// Finishing (or dropping) it squeezes a digest of [`SUBTRANSCRIPT_DIGEST_SIZE`] bytes of the sub-transcript, ratchets, and absorbs the digest into the parent transcript.
+    /// Needs to be declared with [`SubprotocolIOPattern::subprotocol`](crate::nimue::iopattern::SubprotocolIOPattern::subprotocol).
+    fn fork(&mut self, label: &[u8]) -> Result<SubTranscript<'_, Self>, IOPatternError> {
+        self.ratchet_transcript()?;
+        self.public_bytes(label)?;
+        Ok(SubTranscript {
+            parent: self,
+            finished: false,
+        })
+    }
+}
+
+impl<T: RatchetTranscript + BytePublic + ByteChallenges> ForkTranscript for T {}
+
+/// Transcript of a sub-protocol, see [`ForkTranscript::fork`].
+pub struct SubTranscript<'a, T: ForkTranscript> {
+    parent: &'a mut T,
+    finished: bool,
+}
+
+impl<T: ForkTranscript> SubTranscript<'_, T> {
+    /// End the sub-protocol and return to the parent protocol.
+    /// Unlike dropping, this reports whether the sub-protocol followed its IO pattern up to its end.
+    pub fn finish(mut self) -> Result<(), IOPatternError> {
+        self.merge()
+    }
+
+    /// Merge a digest of the sub-transcript into the parent transcript.
+    /// If the sub-protocol did not follow its IO pattern up to its end, the IO pattern of the parent is cleared instead, such that every further operation of the parent protocol fails.
+    fn merge(&mut self) -> Result<(), IOPatternError> {
+        self.finished = true;
+        let result = self.parent.ratchet_transcript().and_then(|()| {
+            let digest = self.parent.challenge_bytes::<SUBTRANSCRIPT_DIGEST_SIZE>()?;
+            self.parent.ratchet_transcript()?;
+            self.parent.public_bytes(&digest)
+        });
+        if result.is_err() {
+            clear_io_pattern(self.parent);
+        }
+        result
+    }
+}
+
+impl<T: ForkTranscript> Deref for SubTranscript<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.parent
+    }
+}
+
+impl<T: ForkTranscript> DerefMut for SubTranscript<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.parent
+    }
+}
+
+impl<T: ForkTranscript> Drop for SubTranscript<'_, T> {
+    fn drop(&mut self) {
+        if !self.finished {
+            // Errors of the sub-protocol resurface at the next operation of the parent protocol
+            let _ = self.merge();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use nimue::{ByteIOPattern, DefaultHash, IOPattern, Merlin};
+
+    use crate::linear_algebra::Vector;
+    use crate::nimue::arthur::SerArthur;
+    use crate::nimue::iopattern::{SerIOPattern, SqueezeFromRandomBytes, SubprotocolIOPattern};
+    use crate::nimue::merlin::SerMerlin;
+    use crate::ring::Zq1;
+
+    use super::*;
+
+    type R = Zq1<65537>;
+
+    /// Integers modulo `M` sampled from a single byte, which rejects with probability $1 - M / 256$.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct ModM<const M: u8>(u8);
+
+    impl<const M: u8> FromRandomBytes<ModM<M>> for ModM<M> {
+        fn has_no_bias() -> bool {
+            true
+        }
+
+        fn needs_bytes() -> usize {
+            1
+        }
+
+        fn rejection_probability() -> f64 {
+            1. - M as f64 / 256.
+        }
+
+        fn try_from_random_bytes_inner(bytes: &[u8]) -> Option<ModM<M>> {
+            (bytes[0] < M).then_some(ModM(bytes[0]))
+        }
+    }
+
+    type Mod131 = ModM<131>;
+    type Mod3 = ModM<3>;
+
+    #[test]
+    fn test_challenge_ring_prover_verifier() {
+        let io = IOPattern::<DefaultHash>::new("test_challenge_ring")
+            .challenge_ring_io::<R>("elem")
+            .challenge_ring_vector_io::<R>(5, "vector")
+            .challenge_ring_matrix_io::<R>(2, 3, "matrix");
+
+        let mut merlin = io.to_merlin();
+        let elem = merlin.challenge_ring::<R>().unwrap();
+        let vector = merlin.challenge_ring_vector::<R>(5).unwrap();
+        let matrix = merlin.challenge_ring_matrix::<R>(2, 3).unwrap();
+
+        let mut arthur = io.to_arthur(merlin.transcript());
+        assert_eq!(arthur.challenge_ring::<R>().unwrap(), elem);
+        assert_eq!(arthur.challenge_ring_vector::<R>(5).unwrap(), vector);
+        assert_eq!(arthur.challenge_ring_matrix::<R>(2, 3).unwrap(), matrix);
+    }
+
+    #[test]
+    fn test_challenge_ring_retries() {
+        const N: usize = 64;
+        let io = IOPattern::<DefaultHash>::new("test_challenge_ring_retries")
+            .challenge_ring_vector_io::<Mod131>(N, "vector");
+
+        let mut merlin = io.to_merlin();
+        let vector = merlin.challenge_ring_vector::<Mod131>(N).unwrap();
+        let mut arthur = io.to_arthur(merlin.transcript());
+        assert_eq!(arthur.challenge_ring_vector::<Mod131>(N).unwrap(), vector);
+
+        // Replaying the pattern as raw bytes reveals which candidates were rejected
+        let mut arthur = io.to_arthur(merlin.transcript());
+        let attempts = challenge_attempts::<Mod131>(N);
+        let mut bytes = vec![0u8; N * attempts];
+        arthur.fill_challenge_bytes(&mut bytes).unwrap();
+        let mut retries = 0;
+        for (v, candidates) in vector.iter().zip(bytes.chunks_exact(attempts)) {
+            let accepted = candidates.iter().position(|b| *b < 131).unwrap();
+            assert_eq!(v.0, candidates[accepted]);
+            retries += accepted;
+        }
+        assert!(retries > 0);
+    }
+
+    #[test]
+    fn test_challenge_attempts() {
+        assert_eq!(challenge_attempts::<R>(64), 1);
+        for n in [1, 64, 1 << 20] {
+            let p = Mod131::rejection_probability();
+            let k = challenge_attempts::<Mod131>(n);
+            assert!(n as f64 * p.powi(k as i32) <= 2f64.powf(LOG2_CHALLENGE_FAILURE_PROBABILITY));
+            // One attempt less exceeds the target failure probability
+            assert!(
+                n as f64 * p.powi(k as i32 - 1) > 2f64.powf(LOG2_CHALLENGE_FAILURE_PROBABILITY)
+            );
+        }
+        assert!(challenge_attempts::<Mod3>(64) > 1000);
+    }
+
+    #[test]
+    fn test_challenge_ring_high_rejection() {
+        // Only 3 out of 256 bytes are accepted, so a fixed small number of attempts would fail almost surely
+        const N: usize = 64;
+        for label in ["a", "b", "c", "d"] {
+            let io = IOPattern::<DefaultHash>::new("test_challenge_ring_high_rejection")
+                .absorb_vector::<R>(1, label)
+                .challenge_ring_vector_io::<Mod3>(N, "vector")
+                .challenge_ring_matrix_io::<Mod3>(4, 4, "matrix");
+            let mut merlin = io.to_merlin();
+            merlin.absorb(&Vector::<R>::zeros(1)).unwrap();
+            let vector = merlin.challenge_ring_vector::<Mod3>(N).unwrap();
+            let matrix = merlin.challenge_ring_matrix::<Mod3>(4, 4).unwrap();
+            assert!(vector.iter().chain(matrix.iter()).all(|x| x.0 < 3));
+
+            let mut arthur = io.to_arthur(merlin.transcript());
+            arthur.next_vector::<R>(1).unwrap();
+            assert_eq!(arthur.challenge_ring_vector::<Mod3>(N).unwrap(), vector);
+            assert_eq!(arthur.challenge_ring_matrix::<Mod3>(4, 4).unwrap(), matrix);
+        }
+    }
+
+    fn fork_io(label: &[u8]) -> IOPattern<DefaultHash> {
+        IOPattern::<DefaultHash>::new("test_fork")
+            .absorb_vector::<R>(4, "v")
+            .subprotocol(label, |io| {
+                io.absorb_vector::<R>(4, "v")
+                    .challenge_ring_io::<R>("inner challenge")
+            })
+            .challenge_ring_io::<R>("outer challenge")
+    }
+
+    /// Run a prover absorbing `v` before and inside a sub-protocol forked with `label`, and return the inner and outer challenges and the transcript.
+    fn fork_prove(label: &[u8], v: &Vector<R>) -> (R, R, Vec<u8>) {
+        let mut merlin = fork_io(label).to_merlin();
+        merlin.absorb(v).unwrap();
+        let mut sub = merlin.fork(label).unwrap();
+        sub.absorb(v).unwrap();
+        let inner = sub.challenge_ring::<R>().unwrap();
+        sub.finish().unwrap();
+        let outer = merlin.challenge_ring::<R>().unwrap();
+        (inner, outer, merlin.transcript().to_vec())
+    }
+
+    #[test]
+    fn test_fork_labels() {
+        let rng = &mut ark_std::test_rng();
+        let v = Vector::<R>::rand(4, rng);
+        let (inner_a, outer_a, transcript_a) = fork_prove(b"ppk", &v);
+        let (inner_b, outer_b, transcript_b) = fork_prove(b"opening", &v);
+        assert_eq!(transcript_a, transcript_b);
+        assert_ne!(inner_a, inner_b);
+        assert_ne!(outer_a, outer_b);
+    }
+
+    #[test]
+    fn test_fork_prover_verifier() {
+        let rng = &mut ark_std::test_rng();
+        let v = Vector::<R>::rand(4, rng);
+        let (inner, outer, transcript) = fork_prove(b"ppk", &v);
+
+        let io = fork_io(b"ppk");
+        let mut arthur = io.to_arthur(&transcript);
+        assert_eq!(arthur.next_vector::<R>(4).unwrap(), v);
+        {
+            // The sub-transcript is finished on drop
+            let mut sub = arthur.fork(b"ppk").unwrap();
+            assert_eq!(sub.next_vector::<R>(4).unwrap(), v);
+            assert_eq!(sub.challenge_ring::<R>().unwrap(), inner);
+        }
+        assert_eq!(arthur.challenge_ring::<R>().unwrap(), outer);
+
+        // Skipping the fork breaks the transcript
+        let mut arthur = io.to_arthur(&transcript);
+        arthur.next_vector::<R>(4).unwrap();
+        assert!(arthur.next_vector::<R>(4).is_err());
+    }
+
+    #[test]
+    fn test_fork_error_in_subprotocol() {
+        let rng = &mut ark_std::test_rng();
+        let v = Vector::<R>::rand(4, rng);
+        let io = fork_io(b"ppk");
+
+        // A pattern mismatch inside the sub-protocol is propagated with `?`, dropping the sub-transcript
+        let mut merlin = io.to_merlin();
+        merlin.absorb(&v).unwrap();
+        let sub_protocol = |merlin: &mut Merlin<DefaultHash>| -> Result<R, IOPatternError> {
+            let mut sub = merlin.fork(b"ppk")?;
+            sub.absorb(&Vector::<R>::zeros(5))?;
+            sub.challenge_ring::<R>()
+        };
+        assert!(sub_protocol(&mut merlin).is_err());
+        assert!(merlin.challenge_ring::<R>().is_err());
+        assert!(merlin.fork(b"ppk").is_err());
+
+        // A sub-transcript dropped before the end of its IO pattern breaks the parent transcript
+        let mut merlin = io.to_merlin();
+        merlin.absorb(&v).unwrap();
+        merlin.fork(b"ppk").unwrap().absorb(&v).unwrap();
+        assert!(merlin.challenge_ring::<R>().is_err());
+
+        // So does finishing it early, which reports the error
+        let mut merlin = io.to_merlin();
+        merlin.absorb(&v).unwrap();
+        assert!(merlin.fork(b"ppk").unwrap().finish().is_err());
+        assert!(merlin.challenge_ring::<R>().is_err());
+
+        // Forking where the IO pattern does not declare a sub-protocol, or past its end, is an error
+        let mut merlin = io.to_merlin();
+        assert!(merlin.fork(b"ppk").is_err());
+        let (_, _, transcript) = fork_prove(b"ppk", &v);
+        let mut arthur = io.to_arthur(&transcript);
+        arthur.next_vector::<R>(4).unwrap();
+        arthur.fork(b"ppk").unwrap().finish().unwrap_err();
+        assert!(arthur.fork(b"ppk").is_err());
+    }
+
+    #[test]
+    fn test_fork_digest() {
+        // The challenges of the parent protocol depend on the messages of the sub-protocol
+        let rng = &mut ark_std::test_rng();
+        let v = Vector::<R>::rand(4, rng);
+        let io = fork_io(b"ppk");
+        let outer = |w: &Vector<R>| {
+            let mut merlin = io.to_merlin();
+            merlin.absorb(&v).unwrap();
+            let mut sub = merlin.fork(b"ppk").unwrap();
+            sub.absorb(w).unwrap();
+            sub.challenge_ring::<R>().unwrap();
+            sub.finish().unwrap();
+            merlin.challenge_ring::<R>().unwrap()
+        };
+        assert_eq!(outer(&v), fork_prove(b"ppk", &v).1);
+        assert_ne!(outer(&v), outer(&Vector::<R>::rand(4, rng)));
+    }
+
+    #[test]
+    fn test_rng_from_transcript() {
+        let io = IOPattern::<DefaultHash>::new("test_rng_from_transcript")
+            .absorb_vector::<R>(4, "v")
+            .rng_io("rng")
+            .challenge_bytes(1, "after");
+        let v = Vector::<R>::from_fn(4, |i, _| R::try_from(i as u64).unwrap());
+
+        let mut merlin = io.to_merlin();
+        merlin.absorb(&v).unwrap();
+        let mut prover_rng = merlin.rng_from_transcript(b"mask").unwrap();
+        let after = merlin.challenge_bytes::<1>().unwrap();
+        let transcript = merlin.transcript().to_vec();
+
+        let mut arthur = io.to_arthur(&transcript);
+        arthur.next_vector::<R>(4).unwrap();
+        let mut verifier_rng = arthur.rng_from_transcript(b"mask").unwrap();
+        // The seed accounts for exactly the declared bytes
+        assert_eq!(arthur.challenge_bytes::<1>().unwrap(), after);
+        let (mut a, mut b) = ([0u8; 100], [0u8; 100]);
+        prover_rng.fill_bytes(&mut a);
+        verifier_rng.fill_bytes(&mut b);
+        assert_eq!(a, b);
+
+        let mut arthur = io.to_arthur(&transcript);
+        arthur.next_vector::<R>(4).unwrap();
+        let mut other_rng = arthur.rng_from_transcript(b"other").unwrap();
+        other_rng.fill_bytes(&mut b);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_rng_from_transcript_size() {
+        let io = IOPattern::<DefaultHash>::new("test_rng_from_transcript_size").rng_io("rng");
+        assert!(io.to_merlin().rng_from_transcript(b"rng").is_ok());
+        let mut merlin = io.to_merlin();
+        merlin.challenge_bytes::<1>().unwrap();
+        assert!(merlin.rng_from_transcript(b"rng").is_err());
+        let io = IOPattern::<DefaultHash>::new("test_rng_from_transcript_size")
+            .challenge_bytes(TRANSCRIPT_RNG_SEED_SIZE - 1, "rng");
+        assert!(io.to_merlin().rng_from_transcript(b"rng").is_err());
+    }
+}
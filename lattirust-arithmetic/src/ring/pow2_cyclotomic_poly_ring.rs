@@ -13,10 +13,12 @@ use derive_more::{Add, AddAssign, From, Into, Sub, SubAssign, Sum};
 use num_bigint::BigUint;
 use num_traits::{One, Zero};
 
+use crate::error::ArithmeticError;
 use crate::linear_algebra::SVector;
 use crate::ring::{PolyRing, Ring};
 use crate::traits::{
-    FromRandomBytes, Modulus, WithConjugationAutomorphism, WithL2Norm, WithLinfNorm,
+    try_from_random_bytes_chunks, FromRandomBytes, Modulus, WithConjugationAutomorphism,
+    WithL2Norm, WithLinfNorm,
 };
 
 #[derive(
@@ -45,7 +47,26 @@ impl<BaseRing: Ring, const N: usize> Pow2CyclotomicPolyRing<BaseRing, N> {
         self.0 .0.into()
     }
 
+    /// Returns the polynomial with coefficients `coeffs`, or [`ArithmeticError::LengthMismatch`] if there are not exactly `N` of them.
+    pub fn try_from_coeffs(coeffs: &[BaseRing]) -> Result<Self, ArithmeticError> {
+        let arr: [BaseRing; N] =
+            coeffs
+                .try_into()
+                .map_err(|_| ArithmeticError::LengthMismatch {
+                    expected: N,
+                    got: coeffs.len(),
+                })?;
+        Ok(Self::from(arr))
+    }
+
+    /// Panics if the leading coefficient of `other` is not invertible, see [`Pow2CyclotomicPolyRing::try_div_rem`].
     pub fn div_rem(&self, other: &Self) -> (Self, Self) {
+        self.try_div_rem(other)
+            .expect("Divisor must have invertible leading coefficient")
+    }
+
+    /// Returns the quotient and remainder of the division by `other`, or [`ArithmeticError::NotInvertible`] if the leading coefficient of `other` is not invertible.
+    pub fn try_div_rem(&self, other: &Self) -> Result<(Self, Self), ArithmeticError> {
         let mut dividend = self.coefficients();
         let divisor = other.coefficients();
         let mut quotient = vec![BaseRing::zero(); N];
@@ -53,7 +74,7 @@ impl<BaseRing: Ring, const N: usize> Pow2CyclotomicPolyRing<BaseRing, N> {
         let divisor_deg = divisor.iter().rposition(|c| !c.is_zero()).unwrap_or(0);
         let divisor_lead_inv = divisor[divisor_deg]
             .inverse()
-            .expect("Divisor must have invertible leading coefficient");
+            .ok_or(ArithmeticError::NotInvertible)?;
 
         for i in (divisor_deg..N).rev() {
             if dividend[i].is_zero() {
@@ -66,9 +87,8 @@ impl<BaseRing: Ring, const N: usize> Pow2CyclotomicPolyRing<BaseRing, N> {
             }
         }
 
-        (Self::from(quotient), Self::from(dividend))
+        Ok((Self::from(quotient), Self::from(dividend)))
     }
-
 }
 
 impl<BaseRing: Ring, const N: usize> From<[BaseRing; N]> for Pow2CyclotomicPolyRing<BaseRing, N> {
@@ -176,7 +196,7 @@ impl<BaseRing: Ring, const N: usize> Ring for Pow2CyclotomicPolyRing<BaseRing, N
         let mut s1 = Self::one();
 
         while !r1.is_zero() {
-            let (q, r) = r0.div_rem(&r1);
+            let (q, r) = r0.try_div_rem(&r1).ok()?;
             let s = s0 - (q * s1);
             r0 = r1;
             r1 = r;
@@ -198,13 +218,7 @@ impl<BaseRing: Ring, const N: usize> FromRandomBytes<Self> for Pow2CyclotomicPol
     }
 
     fn try_from_random_bytes_inner(bytes: &[u8]) -> Option<Self> {
-        let coeffs = core::array::from_fn(|i| {
-            BaseRing::try_from_random_bytes(
-                &bytes[i * BaseRing::byte_size()..(i + 1) * BaseRing::byte_size()],
-            )
-            .unwrap()
-        });
-        Some(Self::from(coeffs))
+        try_from_random_bytes_chunks::<BaseRing, BaseRing>(bytes, N).map(Self::from)
     }
 }
 
@@ -411,8 +425,7 @@ impl<BaseRing: Ring, const N: usize> PolyRing for Pow2CyclotomicPolyRing<BaseRin
     }
 
     fn try_from_coefficients(coeffs: &[Self::BaseRing]) -> Option<Self> {
-        let arr: [_; N] = coeffs.try_into().unwrap();
-        Some(Self::from(arr))
+        Self::try_from_coeffs(coeffs).ok()
     }
 
     fn dimension() -> usize {
@@ -424,6 +437,7 @@ impl<BaseRing: Ring, const N: usize> PolyRing for Pow2CyclotomicPolyRing<BaseRin
     }
 }
 
+/// Panics if `value` does not have length `N`, see [`Pow2CyclotomicPolyRing::try_from_coeffs`].
 impl<BaseRing: Ring, const N: usize> From<Vec<BaseRing>> for Pow2CyclotomicPolyRing<BaseRing, N> {
     fn from(value: Vec<BaseRing>) -> Self {
         Self(SVector::<BaseRing, N>::try_from(value).unwrap())
@@ -479,4 +493,47 @@ mod test {
 
     test_conjugation_automorphism!(PR, NUM_TEST_REPETITIONS);
 
+    #[test]
+    fn test_try_from_coeffs() {
+        let coeffs: Vec<BR> = (0..N as u64).map(|i| BR::try_from(i).unwrap()).collect();
+        let p = PR::try_from_coeffs(&coeffs).unwrap();
+        assert_eq!(p.coefficients(), coeffs);
+        assert!(matches!(
+            PR::try_from_coeffs(&coeffs[1..]),
+            Err(ArithmeticError::LengthMismatch {
+                expected: N,
+                got: 63
+            })
+        ));
+        assert_eq!(PR::try_from_coefficients(&coeffs[1..]), None);
+        assert_eq!(
+            PR::try_from_coeffs(&[]).unwrap_err().to_string(),
+            "expected 64 elements, got 0"
+        );
+    }
+
+    #[test]
+    fn test_try_div_rem() {
+        let rng = &mut ark_std::test_rng();
+        let a = PR::rand(rng);
+        assert!(matches!(
+            a.try_div_rem(&PR::zero()),
+            Err(ArithmeticError::NotInvertible)
+        ));
+        // Division by a polynomial of degree 1
+        let b = PR::from_fn(|i| match i {
+            0 => BR::try_from(5u64).unwrap(),
+            1 => BR::try_from(3u64).unwrap(),
+            _ => BR::zero(),
+        });
+        let (q, r) = a.try_div_rem(&b).unwrap();
+        assert_eq!(q * b + r, a);
+        assert!(r.coefficients()[1..].iter().all(|c| c.is_zero()));
+    }
+
+    #[test]
+    fn test_from_random_bytes_short() {
+        let bytes = vec![0u8; PR::byte_size() - 1];
+        assert!(PR::try_from_random_bytes(&bytes).is_none());
+    }
 }
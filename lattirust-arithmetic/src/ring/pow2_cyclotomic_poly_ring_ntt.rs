@@ -13,6 +13,7 @@ use derive_more::{Add, AddAssign, Display, From, Into, Sub, SubAssign, Sum};
 use num_bigint::BigUint;
 use num_traits::{One, Zero};
 
+use crate::error::ArithmeticError;
 use crate::linear_algebra::SVector;
 use crate::ring::ntt::NttRing;
 use crate::ring::pow2_cyclotomic_poly_ring::Pow2CyclotomicPolyRing;
@@ -52,6 +53,18 @@ impl<BaseRing: NttRing<N>, const N: usize> Pow2CyclotomicPolyRingNTT<BaseRing, N
         Self::from_ntt_array(coeffs)
     }
 
+    /// Constructs a polynomial from coefficients in non-NTT form, or returns [`ArithmeticError::LengthMismatch`] if there are not exactly `N` of them.
+    pub fn try_from_coeffs(coeffs: &[BaseRing]) -> Result<Self, ArithmeticError> {
+        let arr: [BaseRing; N] =
+            coeffs
+                .try_into()
+                .map_err(|_| ArithmeticError::LengthMismatch {
+                    expected: N,
+                    got: coeffs.len(),
+                })?;
+        Ok(Self::from_coefficient_array(arr))
+    }
+
     /// Constructs a polynomial from the values of the polynomial in NTT form.
     pub fn from_ntt_array(coeffs_ntt: [BaseRing; N]) -> Self {
         Self(Self::Inner::const_from_array(coeffs_ntt))
@@ -80,7 +93,6 @@ impl<BaseRing: NttRing<N>, const N: usize> Pow2CyclotomicPolyRingNTT<BaseRing, N
     pub fn ntt_array(&self) -> [BaseRing; N] {
         self.0 .0.data.0[0]
     }
-
 }
 
 impl<BaseRing: NttRing<N>, const N: usize> Modulus for Pow2CyclotomicPolyRingNTT<BaseRing, N> {
@@ -416,8 +428,7 @@ impl<BaseRing: NttRing<N>, const N: usize> PolyRing for Pow2CyclotomicPolyRingNT
     }
 
     fn try_from_coefficients(coeffs: &[BaseRing]) -> Option<Self> {
-        let arr: [BaseRing; N] = coeffs.try_into().ok()?;
-        Some(Self::from_coefficient_array(arr))
+        Self::try_from_coeffs(coeffs).ok()
     }
 
     fn dimension() -> usize {
@@ -452,7 +463,7 @@ impl<BaseRing: NttRing<N>, const N: usize> From<BaseRing>
     fn from(value: BaseRing) -> Self {
         Self::from_scalar(value)
     }
-} 
+}
 
 impl<BaseRing: NttRing<N>, const N: usize> WithConjugationAutomorphism
     for Pow2CyclotomicPolyRingNTT<BaseRing, N>
@@ -500,6 +511,20 @@ mod test {
 
     test_polyring!(PR, NUM_TEST_REPETITIONS);
 
+    #[test]
+    fn test_try_from_coeffs() {
+        let coeffs = [BR::try_from(3u64).unwrap(), BR::try_from(5u64).unwrap()];
+        let p = PR::try_from_coeffs(&coeffs).unwrap();
+        assert_eq!(p, PR::from(coeffs.to_vec()));
+        assert!(matches!(
+            PR::try_from_coeffs(&[coeffs[0]; 3]),
+            Err(ArithmeticError::LengthMismatch {
+                expected: N,
+                got: 3
+            })
+        ));
+    }
+
     #[test]
     fn test_from_into_pow2cyclotomicpolyring() {
         let rng = &mut ark_std::test_rng();
@@ -523,7 +548,6 @@ mod test {
         }
         PR::from_ntt_array(reversed)
     }
-    
 
     test_conjugation_automorphism!(PR, NUM_TEST_REPETITIONS);
 }
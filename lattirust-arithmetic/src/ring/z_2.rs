@@ -231,6 +231,10 @@ impl FromRandomBytes<Self> for Z2 {
         true
     }
 
+    fn rejection_probability() -> f64 {
+        0.
+    }
+
     fn needs_bytes() -> usize {
         1
     }
@@ -169,6 +169,11 @@ impl FromRandomBytes<Self> for Z2_128 {
     fn has_no_bias() -> bool {
         true
     }
+
+    fn rejection_probability() -> f64 {
+        0.
+    }
+
     fn needs_bytes() -> usize {
         8
     }
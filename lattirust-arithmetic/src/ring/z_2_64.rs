@@ -166,6 +166,10 @@ impl FromRandomBytes<Self> for Z2_64 {
         true
     }
 
+    fn rejection_probability() -> f64 {
+        0.
+    }
+
     fn needs_bytes() -> usize {
         8
     }
@@ -278,16 +282,64 @@ impl WithSignedRepresentative for Z2_64 {
     fn signed_representative_to_bigint(repr: &Self::SignedRepresentative) -> BigInt {
         BigInt::from(*repr)
     }
-    
+
     fn signed_representative_from_bigint(value: BigInt) -> Option<Self::SignedRepresentative> {
         value.to_i64()
     }
 }
 
+/// Number of lanes of the SIMD kernel of [`dot_z2_64`].
+#[cfg(feature = "simd")]
+pub const DOT_LANES: usize = 8;
+
+/// Return $\langle a, b \rangle$ for $a, b \in \mathbb{Z}\_{2^{64}}^n$.
+/// With the `simd` feature, blocks of [`DOT_LANES`] entries are multiplied and accumulated with portable SIMD, and the remaining entries with scalar arithmetic.
+/// Since arithmetic in $\mathbb{Z}\_{2^{64}}$ wraps, the result is identical to the scalar computation.
+pub fn dot_z2_64(a: &[Z2_64], b: &[Z2_64]) -> Z2_64 {
+    assert_eq!(a.len(), b.len());
+    #[cfg(feature = "simd")]
+    {
+        use std::simd::num::SimdInt;
+        use std::simd::Simd;
+
+        let load = |chunk: &[Z2_64]| {
+            Simd::<i64, DOT_LANES>::from_array(core::array::from_fn(|k| chunk[k].0 .0))
+        };
+        let (a_chunks, b_chunks) = (a.chunks_exact(DOT_LANES), b.chunks_exact(DOT_LANES));
+        let tail = dot_z2_64_scalar(a_chunks.remainder(), b_chunks.remainder());
+        let acc = a_chunks
+            .zip(b_chunks)
+            .fold(Simd::splat(0), |acc, (x, y)| acc + load(x) * load(y));
+        Z2_64(Wrapping(acc.reduce_sum())) + tail
+    }
+    #[cfg(not(feature = "simd"))]
+    dot_z2_64_scalar(a, b)
+}
+
+fn dot_z2_64_scalar(a: &[Z2_64], b: &[Z2_64]) -> Z2_64 {
+    Z2_64(
+        a.iter()
+            .zip(b)
+            .fold(Wrapping(0i64), |acc, (x, y)| acc + x.0 * y.0),
+    )
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::*;
 
     test_ring!(Z2_64, 100);
+
+    #[test]
+    fn test_dot_z2_64() {
+        let rng = &mut ark_std::test_rng();
+        // Include lengths that are not multiples of the number of lanes
+        for n in [0, 1, 7, 8, 9, 63, 64, 1000, 1027] {
+            let a: Vec<Z2_64> = (0..n).map(|_| Z2_64::rand(rng)).collect();
+            let b: Vec<Z2_64> = (0..n).map(|_| Z2_64::rand(rng)).collect();
+            let expected: Z2_64 = a.iter().zip(&b).map(|(x, y)| *x * *y).sum();
+            assert_eq!(dot_z2_64(&a, &b), expected, "n = {n}");
+        }
+    }
 }
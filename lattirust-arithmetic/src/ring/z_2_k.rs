@@ -236,6 +236,10 @@ impl<const K: u32> FromRandomBytes<Self> for Z2_k<K> {
         true
     }
 
+    fn rejection_probability() -> f64 {
+        0.
+    }
+
     fn needs_bytes() -> usize {
         8
     }
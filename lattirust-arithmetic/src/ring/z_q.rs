@@ -28,6 +28,7 @@ use rounded_div::RoundedDiv;
 use zeroize::Zeroize;
 
 use crate::decomposition::DecompositionFriendlySignedRepresentative;
+use crate::error::ArithmeticError;
 use crate::impl_try_from_primitive_type;
 use crate::ring::ntt::{
     const_fq_from, const_pow_mod, generator, is_primitive_root_of_unity, two_adic_root_of_unity,
@@ -154,14 +155,18 @@ impl<C: ZqConfig<L>, const L: usize> From<bool> for Zq<C, L> {
     }
 }
 
+/// Returns [`ArithmeticError::ConversionOverflow`] if `value` is not smaller than the modulus.
 impl<C: ZqConfig<L>, const L: usize> TryFrom<BigUint> for Zq<C, L> {
-    type Error = ();
+    type Error = ArithmeticError;
 
     fn try_from(value: BigUint) -> Result<Self, Self::Error> {
-        match C::from_bigint(BigInt::<L>::try_from(value)?) {
-            None => Err(()),
-            Some(elem) => Ok(elem),
-        }
+        BigInt::<L>::try_from(value.clone())
+            .ok()
+            .and_then(C::from_bigint)
+            .ok_or_else(|| ArithmeticError::ConversionOverflow {
+                value,
+                modulus: Self::modulus(),
+            })
     }
 }
 
@@ -828,6 +833,20 @@ mod test {
     const Q9: u64 = 200560490131; // Not NTT-friendly
     const Q10: u64 = 7; // Not NTT-friendly
 
+    #[test]
+    fn test_try_from_overflow() {
+        type Z1 = Zq1<Q2>;
+        assert_eq!(Z1::try_from(Q2 - 1).unwrap(), -Z1::one());
+        let err = Z1::try_from(Q2).unwrap_err();
+        assert!(matches!(
+            &err,
+            ArithmeticError::ConversionOverflow { value, modulus }
+                if *value == BigUint::from(Q2) && *modulus == BigUint::from(Q2)
+        ));
+        assert_eq!(err.to_string(), "274177 does not fit in the range [0, 274177)");
+        assert!(Z1::try_from(BigUint::from(u128::MAX) * BigUint::from(u128::MAX)).is_err());
+    }
+
     #[cfg(test)]
     mod test_z1 {
         use super::*;
@@ -0,0 +1,847 @@
+use ark_std::rand::{CryptoRng, Rng, RngCore};
+use displaydoc::Display;
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+use crate::linear_algebra::Vector;
+use crate::ring::representatives::SignedRepresentative;
+use crate::ring::{PolyRing, Pow2CyclotomicPolyRing, Zq1};
+use crate::traits::{Modulus, WithLinfNorm};
+
+/// Errors returned by the constructors of the samplers in this module.
+#[derive(Clone, Debug, PartialEq, Display)]
+pub enum SamplerError {
+    /// sigma = {0} is outside the supported range [{1}, {2}]
+    UnsupportedSigma(f64, f64, f64),
+    /// invalid {0}: {1}
+    InvalidParameter(&'static str, String),
+}
+
+/// Sampler for masking polynomials $y$ over $\mathbb{Z}\_Q$ whose coefficients are uniformly random in $\[-B, B\]$, as used in Fiat-Shamir with aborts [Lyu09, Lyu12].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoundedUniform<const Q: u64> {
+    bound: u64,
+}
+
+impl<const Q: u64> BoundedUniform<Q> {
+    /// Return a sampler for the bound `bound`, or [`SamplerError::InvalidParameter`] if $2B + 1 > Q$, i.e., if $\[-B, B\]$ does not fit into $\mathbb{Z}\_Q$ without wrapping around.
+    pub fn new(bound: u64) -> Result<Self, SamplerError> {
+        let fits = bound
+            .checked_mul(2)
+            .and_then(|b| b.checked_add(1))
+            .is_some_and(|size| size <= Q);
+        if !fits {
+            return Err(SamplerError::InvalidParameter(
+                "bound",
+                format!("2 * {bound} + 1 exceeds the modulus {Q}"),
+            ));
+        }
+        Ok(BoundedUniform { bound })
+    }
+
+    pub fn bound(&self) -> u64 {
+        self.bound
+    }
+
+    /// Return a coefficient uniformly random in $\[-B, B\]$.
+    fn sample_coefficient(&self, rng: &mut (impl Rng + ?Sized)) -> Zq1<Q> {
+        let x = rng.gen_range(0..=2 * self.bound);
+        SignedRepresentative::<Zq1<Q>>::new(BigInt::from(x) - BigInt::from(self.bound)).into()
+    }
+
+    /// Return a polynomial whose coefficients are independent and uniformly random in $\[-B, B\]$.
+    pub fn sample_poly<const N: usize>(
+        &self,
+        rng: &mut (impl Rng + ?Sized),
+    ) -> Pow2CyclotomicPolyRing<Zq1<Q>, N> {
+        let coeffs: Vec<Zq1<Q>> = (0..N).map(|_| self.sample_coefficient(rng)).collect();
+        Pow2CyclotomicPolyRing::<Zq1<Q>, N>::from(coeffs)
+    }
+
+    /// Return a vector of `len` polynomials sampled with [`BoundedUniform::sample_poly`].
+    pub fn sample_vector<const N: usize>(
+        &self,
+        len: usize,
+        rng: &mut (impl Rng + ?Sized),
+    ) -> Vector<Pow2CyclotomicPolyRing<Zq1<Q>, N>> {
+        Vector::<Pow2CyclotomicPolyRing<Zq1<Q>, N>>::from_fn(len, |_, _| self.sample_poly(rng))
+    }
+
+    /// Return the probability that [`rejection_sample`] accepts $z = y + v$ for $y$ sampled from `self` and a center $v$ with $\\|v\\|\_\infty = $ `center_linf_norm`, over `num_coefficients` coefficients in total, i.e., $\left(\frac{2(B - \\|v\\|\_\infty) + 1}{2B + 1}\right)^{\texttt{num\\_coefficients}}$.
+    pub fn acceptance_probability(&self, center_linf_norm: u64, num_coefficients: usize) -> f64 {
+        if center_linf_norm > self.bound {
+            return 0.;
+        }
+        let accepted = 2 * (self.bound - center_linf_norm) + 1;
+        (accepted as f64 / (2 * self.bound + 1) as f64).powi(num_coefficients as i32)
+    }
+}
+
+/// Uniform rejection rule for $z = y + v$, where $y$ was sampled from [`BoundedUniform`] with bound `bound` and $v = c \cdot s$ is the `center` (for the challenge $c$ and secret $s$): accept iff every coefficient of $z$ lies in $\[-(B - \\|v\\|\_\infty), B - \\|v\\|\_\infty\]$.
+/// Conditioned on acceptance, the coefficients of $z$ are uniformly random in this interval, such that $z$ reveals nothing about $v$ beyond $\\|v\\|\_\infty$. To hide the latter as well, pass a center whose norm is (padded to) a public bound.
+/// Unlike rejection sampling from discrete Gaussians, the uniform rule is deterministic and needs no randomness.
+/// Panics if `z` and `center` have different lengths.
+pub fn rejection_sample<R: PolyRing>(z: &Vector<R>, center: &Vector<R>, bound: u64) -> bool {
+    assert_eq!(
+        z.len(),
+        center.len(),
+        "z and center must have the same length"
+    );
+    let bound = BigUint::from(bound);
+    let center_norm = center.linf_norm();
+    if center_norm > bound {
+        return false;
+    }
+    z.linf_norm() <= bound - center_norm
+}
+
+/// Sampler for the discrete Gaussian distribution $D\_{\mathbb{Z}, \sigma}$ with probabilities proportional to $\exp(-x^2 / (2 \sigma^2))$, restricted to $\[-\lceil \tau \sigma \rceil, \lceil \tau \sigma \rceil\]$ for the tail cut $\tau = $ [`ConstTimeGaussian::TAIL_CUT`], such that the mass cut off is below $2^{-120}$.
+/// Samples are drawn by inversion of a cumulative distribution table (CDT) with 63 bits of precision, which is scanned in full and compared without branches, such that the running time and the memory access pattern do not depend on the output. This makes the sampler suitable for noise whose magnitude must stay secret, e.g., in Lyubashevsky-style signatures.
+/// The table has $2 \lceil \tau \sigma \rceil + 1$ entries, so only $\sigma \in$ \[[`ConstTimeGaussian::MIN_SIGMA`], [`ConstTimeGaussian::MAX_SIGMA`]\] is supported. Its entries are computed in double precision, i.e., each probability has relative error about $2^{-52}$.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConstTimeGaussian {
+    sigma: f64,
+    tail_bound: u64,
+    /// `cdt[i]` is $2^{63} \Pr\[X \leq i - B\]$ for the tail bound $B$, rounded to the nearest integer.
+    cdt: Vec<u64>,
+}
+
+impl ConstTimeGaussian {
+    pub const MIN_SIGMA: f64 = 0.5;
+    pub const MAX_SIGMA: f64 = 20.;
+    pub const TAIL_CUT: f64 = 13.;
+
+    /// Return a sampler for standard deviation `sigma`, or [`SamplerError::UnsupportedSigma`] if `sigma` is outside \[[`ConstTimeGaussian::MIN_SIGMA`], [`ConstTimeGaussian::MAX_SIGMA`]\].
+    pub fn new(sigma: f64) -> Result<Self, SamplerError> {
+        if !(Self::MIN_SIGMA..=Self::MAX_SIGMA).contains(&sigma) {
+            return Err(SamplerError::UnsupportedSigma(
+                sigma,
+                Self::MIN_SIGMA,
+                Self::MAX_SIGMA,
+            ));
+        }
+        let tail_bound = (Self::TAIL_CUT * sigma).ceil() as u64;
+        let bound = tail_bound as i64;
+        let weights: Vec<f64> = (-bound..=bound)
+            .map(|x| (-((x * x) as f64) / (2. * sigma * sigma)).exp())
+            .collect();
+        let total: f64 = weights.iter().sum();
+        let scale = (1u64 << 63) as f64;
+        let mut cumulative = 0.;
+        let mut cdt: Vec<u64> = weights
+            .iter()
+            .map(|w| {
+                cumulative += w / total;
+                (cumulative * scale).round().min(scale) as u64
+            })
+            .collect();
+        *cdt.last_mut().unwrap() = 1 << 63;
+        Ok(ConstTimeGaussian {
+            sigma,
+            tail_bound,
+            cdt,
+        })
+    }
+
+    pub fn sigma(&self) -> f64 {
+        self.sigma
+    }
+
+    /// Return the bound $B = \lceil \tau \sigma \rceil$ on the absolute value of all samples.
+    pub fn tail_bound(&self) -> u64 {
+        self.tail_bound
+    }
+
+    /// Return the probability that [`ConstTimeGaussian::sample_signed`] returns `x`, according to the table.
+    pub fn probability(&self, x: i64) -> f64 {
+        if x.unsigned_abs() > self.tail_bound {
+            return 0.;
+        }
+        let i = (x + self.tail_bound as i64) as usize;
+        let below = if i == 0 { 0 } else { self.cdt[i - 1] };
+        (self.cdt[i] - below) as f64 / (1u64 << 63) as f64
+    }
+
+    /// Return a sample in $\[-B, B\]$, in time independent of its value.
+    pub fn sample_signed(&self, rng: &mut (impl Rng + ?Sized)) -> i64 {
+        let r = rng.next_u64() >> 1;
+        // Count the entries at most r, where r - t has its top bit set iff r < t (as both are below 2^63)
+        let index: u64 = self
+            .cdt
+            .iter()
+            .map(|&t| 1 ^ (r.wrapping_sub(t) >> 63))
+            .sum();
+        index as i64 - self.tail_bound as i64
+    }
+
+    /// Return a sample embedded in $\mathbb{Z}\_Q$.
+    /// The reduction modulo $Q$ is branchless and the embedding only uses additions, such that it inherits the timing behaviour of the arithmetic in [`Zq1`].
+    /// Panics if $Q \leq 2B$.
+    pub fn sample<const Q: u64>(&self, rng: &mut (impl Rng + ?Sized)) -> Zq1<Q> {
+        assert!(
+            Q > 2 * self.tail_bound,
+            "the modulus must exceed twice the tail bound"
+        );
+        let x = self.sample_signed(rng);
+        // Lift x to x + Q in [Q - B, Q + B] and subtract Q iff the result is at least Q
+        let u = (x as i128 + Q as i128) as u128;
+        let geq = 1 ^ (u.wrapping_sub(Q as u128) >> 127);
+        let u = (u - geq * Q as u128) as u64;
+        (0..u64::BITS - Q.leading_zeros())
+            .rev()
+            .fold(Zq1::<Q>::zero(), |acc, i| {
+                acc + acc + Zq1::<Q>::from((u >> i) & 1 == 1)
+            })
+    }
+
+    /// Return a polynomial whose coefficients are sampled independently with [`ConstTimeGaussian::sample`].
+    pub fn sample_poly<const Q: u64, const N: usize>(
+        &self,
+        rng: &mut (impl Rng + ?Sized),
+    ) -> Pow2CyclotomicPolyRing<Zq1<Q>, N> {
+        let coeffs: Vec<Zq1<Q>> = (0..N).map(|_| self.sample(rng)).collect();
+        Pow2CyclotomicPolyRing::<Zq1<Q>, N>::from(coeffs)
+    }
+
+    /// Return a vector of `len` polynomials sampled with [`ConstTimeGaussian::sample_poly`].
+    pub fn sample_vector<const Q: u64, const N: usize>(
+        &self,
+        len: usize,
+        rng: &mut (impl Rng + ?Sized),
+    ) -> Vector<Pow2CyclotomicPolyRing<Zq1<Q>, N>> {
+        Vector::<Pow2CyclotomicPolyRing<Zq1<Q>, N>>::from_fn(len, |_, _| self.sample_poly(rng))
+    }
+}
+
+/// Return a uniformly random integer in $\[0, m)$, by rejection sampling from the smallest power of two at least $m$.
+fn uniform_below<R: RngCore + CryptoRng + ?Sized>(m: &BigUint, rng: &mut R) -> BigUint {
+    debug_assert!(!m.is_zero());
+    let bits = m.bits();
+    let mut bytes = vec![0u8; bits.div_ceil(8) as usize];
+    loop {
+        rng.fill_bytes(&mut bytes);
+        if bits % 8 != 0 {
+            *bytes.last_mut().unwrap() &= (1u8 << (bits % 8)) - 1;
+        }
+        let x = BigUint::from_bytes_le(&bytes);
+        if &x < m {
+            return x;
+        }
+    }
+}
+
+/// Return a sample of $\mathsf{Bernoulli}(n / d)$ for $n \leq d$.
+fn bernoulli<R: RngCore + CryptoRng + ?Sized>(n: &BigUint, d: &BigUint, rng: &mut R) -> bool {
+    &uniform_below(d, rng) < n
+}
+
+/// Return a sample of $\mathsf{Bernoulli}(\exp(-n / d))$ for $n \leq d$: the number of consecutive successes of $\mathsf{Bernoulli}(n / (d k))$ for $k = 1, 2, \ldots$ is even with probability $\exp(-n / d)$.
+fn bernoulli_exp_minus_fraction<R: RngCore + CryptoRng + ?Sized>(
+    n: &BigUint,
+    d: &BigUint,
+    rng: &mut R,
+) -> bool {
+    let mut k = BigUint::one();
+    while bernoulli(n, &(d * &k), rng) {
+        k += 1u32;
+    }
+    k.is_odd()
+}
+
+/// Return a sample of $\mathsf{Bernoulli}(\exp(-n / d))$, following Algorithm 1 of [CKS20].
+fn bernoulli_exp_minus<R: RngCore + CryptoRng + ?Sized>(
+    n: &BigUint,
+    d: &BigUint,
+    rng: &mut R,
+) -> bool {
+    let (whole, fraction) = n.div_rem(d);
+    let one = BigUint::one();
+    let mut k = BigUint::zero();
+    while k < whole {
+        if !bernoulli_exp_minus_fraction(&one, &one, rng) {
+            return false;
+        }
+        k += 1u32;
+    }
+    bernoulli_exp_minus_fraction(&fraction, d, rng)
+}
+
+/// Return `x` reduced to the signed representative range of `M`.
+fn reduce_signed<M: Modulus>(x: BigInt) -> SignedRepresentative<M> {
+    let modulus = SignedRepresentative::<M>::modulus();
+    let mut x = x.mod_floor(&modulus);
+    if x > SignedRepresentative::<M>::max_inclusive() {
+        x -= modulus;
+    }
+    SignedRepresentative::new(x)
+}
+
+/// Return the positive rational $n / d$ in lowest terms, or [`SamplerError::InvalidParameter`] if $n$ or $d$ is zero.
+fn reduced_rational(
+    name: &'static str,
+    n: BigUint,
+    d: BigUint,
+) -> Result<(BigUint, BigUint), SamplerError> {
+    if n.is_zero() || d.is_zero() {
+        return Err(SamplerError::InvalidParameter(
+            name,
+            format!("{n}/{d} is not positive"),
+        ));
+    }
+    let gcd = n.gcd(&d);
+    Ok((n / &gcd, d / gcd))
+}
+
+/// Return the positive and finite `x` as a rational $n / d$ in lowest terms, which is exact since `x` is a dyadic rational.
+fn rational_from_f64(name: &'static str, x: f64) -> Result<(BigUint, BigUint), SamplerError> {
+    if !(x.is_finite() && x > 0.) {
+        return Err(SamplerError::InvalidParameter(
+            name,
+            format!("{x} is not positive and finite"),
+        ));
+    }
+    let bits = x.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i64;
+    let (mantissa, exponent) = if exponent == 0 {
+        // Subnormal
+        (bits & ((1 << 52) - 1), -1074)
+    } else {
+        ((bits & ((1 << 52) - 1)) | (1 << 52), exponent - 1075)
+    };
+    let (n, d) = if exponent >= 0 {
+        (BigUint::from(mantissa) << exponent as usize, BigUint::one())
+    } else {
+        (
+            BigUint::from(mantissa),
+            BigUint::one() << (-exponent) as usize,
+        )
+    };
+    reduced_rational(name, n, d)
+}
+
+/// Exact sampler for the discrete Laplace distribution $\mathcal{L}\_\mathbb{Z}(t)$ with probabilities proportional to $\exp(-|x| / t)$ for the rational scale $t$, following Algorithm 2 of [Canonne, Kamath, Steinke, "The Discrete Gaussian for Differential Privacy", 2020] (CKS20).
+/// Sampling only uses integer arithmetic on the numerator and denominator of $t$ and uniformly random integers, such that the output distribution is exact. Unlike [`ConstTimeGaussian`], the running time depends on the output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiscreteLaplace {
+    numerator: BigUint,
+    denominator: BigUint,
+}
+
+impl DiscreteLaplace {
+    /// Return a sampler for the scale `scale`, which is converted exactly to a rational, or [`SamplerError::InvalidParameter`] if `scale` is not positive and finite.
+    pub fn new(scale: f64) -> Result<Self, SamplerError> {
+        let (numerator, denominator) = rational_from_f64("scale", scale)?;
+        Ok(DiscreteLaplace {
+            numerator,
+            denominator,
+        })
+    }
+
+    /// Return a sampler for the scale `numerator / denominator`, or [`SamplerError::InvalidParameter`] if either is zero.
+    pub fn from_rational(numerator: u64, denominator: u64) -> Result<Self, SamplerError> {
+        let (numerator, denominator) =
+            reduced_rational("scale", numerator.into(), denominator.into())?;
+        Ok(DiscreteLaplace {
+            numerator,
+            denominator,
+        })
+    }
+
+    /// Return the scale as a rational `(numerator, denominator)` in lowest terms.
+    pub fn scale(&self) -> (&BigUint, &BigUint) {
+        (&self.numerator, &self.denominator)
+    }
+
+    /// Return an integer sample.
+    pub fn sample_integer<R: RngCore + CryptoRng + ?Sized>(&self, rng: &mut R) -> BigInt {
+        let (t, s) = (&self.numerator, &self.denominator);
+        let one = BigUint::one();
+        loop {
+            // X = U + t V is geometric with parameter 1 - exp(-1 / t), and floor(X / s) is geometric with parameter 1 - exp(-s / t)
+            let u = uniform_below(t, rng);
+            if !bernoulli_exp_minus(&u, t, rng) {
+                continue;
+            }
+            let mut v = BigUint::zero();
+            while bernoulli_exp_minus_fraction(&one, &one, rng) {
+                v += 1u32;
+            }
+            let y = (u + t * v) / s;
+            let negative = rng.gen::<bool>();
+            if negative && y.is_zero() {
+                continue;
+            }
+            return BigInt::from_biguint(if negative { Sign::Minus } else { Sign::Plus }, y);
+        }
+    }
+
+    /// Return a sample reduced to the signed representative range of `M`.
+    pub fn sample<M: Modulus, R: RngCore + CryptoRng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> SignedRepresentative<M> {
+        reduce_signed(self.sample_integer(rng))
+    }
+
+    /// Return a sample reduced modulo $Q$.
+    pub fn sample_zq<const Q: u64, R: RngCore + CryptoRng + ?Sized>(&self, rng: &mut R) -> Zq1<Q> {
+        self.sample::<Zq1<Q>, R>(rng).into()
+    }
+}
+
+/// Exact sampler for the discrete Gaussian distribution $\mathcal{N}\_\mathbb{Z}(0, \sigma^2)$ with probabilities proportional to $\exp(-x^2 / (2 \sigma^2))$ for rational $\sigma^2$, by rejection sampling from [`DiscreteLaplace`] following Algorithm 3 of [CKS20].
+/// As for [`DiscreteLaplace`], the output distribution is exact, without tail cut, and the running time depends on the output. Use [`ConstTimeGaussian`] where the noise must stay secret.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiscreteGaussianDP {
+    variance_numerator: BigUint,
+    variance_denominator: BigUint,
+    laplace: DiscreteLaplace,
+}
+
+impl DiscreteGaussianDP {
+    /// Return a sampler for the parameter `sigma`, which is converted exactly to a rational, or [`SamplerError::InvalidParameter`] if `sigma` is not positive and finite.
+    pub fn new(sigma: f64) -> Result<Self, SamplerError> {
+        let (n, d) = rational_from_f64("sigma", sigma)?;
+        Ok(Self::from_reduced_variance(&n * &n, &d * &d))
+    }
+
+    /// Return a sampler for $\sigma^2 = $ `numerator / denominator`, or [`SamplerError::InvalidParameter`] if either is zero.
+    pub fn from_rational_variance(numerator: u64, denominator: u64) -> Result<Self, SamplerError> {
+        let (n, d) = reduced_rational("variance", numerator.into(), denominator.into())?;
+        Ok(Self::from_reduced_variance(n, d))
+    }
+
+    fn from_reduced_variance(numerator: BigUint, denominator: BigUint) -> Self {
+        // Sample from the discrete Laplace distribution with scale t = floor(sigma) + 1
+        let t = (&numerator / &denominator).sqrt() + 1u32;
+        DiscreteGaussianDP {
+            variance_numerator: numerator,
+            variance_denominator: denominator,
+            laplace: DiscreteLaplace {
+                numerator: t,
+                denominator: BigUint::one(),
+            },
+        }
+    }
+
+    /// Return $\sigma^2$ as a rational `(numerator, denominator)` in lowest terms.
+    pub fn variance(&self) -> (&BigUint, &BigUint) {
+        (&self.variance_numerator, &self.variance_denominator)
+    }
+
+    /// Return an integer sample.
+    pub fn sample_integer<R: RngCore + CryptoRng + ?Sized>(&self, rng: &mut R) -> BigInt {
+        let (a, b) = (&self.variance_numerator, &self.variance_denominator);
+        let t = &self.laplace.numerator;
+        loop {
+            let y = self.laplace.sample_integer(rng);
+            // Accept with probability exp(-(|y| - sigma^2 / t)^2 / (2 sigma^2)), where the exponent is (|y| b t - a)^2 / (2 a b t^2) for sigma^2 = a / b
+            let diff = BigInt::from(y.magnitude() * b * t) - BigInt::from(a.clone());
+            let n = diff.magnitude().pow(2);
+            let d = BigUint::from(2u32) * a * b * t * t;
+            if bernoulli_exp_minus(&n, &d, rng) {
+                return y;
+            }
+        }
+    }
+
+    /// Return a sample reduced to the signed representative range of `M`.
+    pub fn sample<M: Modulus, R: RngCore + CryptoRng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> SignedRepresentative<M> {
+        reduce_signed(self.sample_integer(rng))
+    }
+
+    /// Return a sample reduced modulo $Q$.
+    pub fn sample_zq<const Q: u64, R: RngCore + CryptoRng + ?Sized>(&self, rng: &mut R) -> Zq1<Q> {
+        self.sample::<Zq1<Q>, R>(rng).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Instant;
+
+    use ark_std::rand::SeedableRng;
+    use ark_std::test_rng;
+    use num_traits::ToPrimitive;
+    use rand_chacha::ChaCha20Rng;
+
+    use crate::test_utils::stats::Moments;
+
+    use crate::assert_distribution;
+
+    use super::*;
+
+    const Q: u64 = 65537;
+    const N: usize = 8;
+
+    type R = Pow2CyclotomicPolyRing<Zq1<Q>, N>;
+
+    fn poly(coeffs: [i64; N]) -> R {
+        R::from(
+            coeffs
+                .iter()
+                .map(|&c| {
+                    let abs = Zq1::<Q>::try_from(c.unsigned_abs()).unwrap();
+                    if c < 0 {
+                        -abs
+                    } else {
+                        abs
+                    }
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn test_sample_bounded() {
+        let rng = &mut test_rng();
+        let sampler = BoundedUniform::<Q>::new(3).unwrap();
+        let v = sampler.sample_vector::<N>(100, rng);
+        assert_eq!(v.len(), 100);
+        assert_eq!(v.linf_norm(), BigUint::from(3u64));
+        assert!(BoundedUniform::<Q>::new(0)
+            .unwrap()
+            .sample_poly::<N>(rng)
+            .is_zero());
+    }
+
+    #[test]
+    fn test_bounded_invalid() {
+        assert!(BoundedUniform::<Q>::new((Q - 1) / 2).is_ok());
+        assert!(matches!(
+            BoundedUniform::<Q>::new((Q + 1) / 2),
+            Err(SamplerError::InvalidParameter("bound", _))
+        ));
+        // 2 * bound overflows
+        assert!(BoundedUniform::<Q>::new(u64::MAX / 2 + 1).is_err());
+    }
+
+    /// Return the representative of `c` in $\[-(Q-1)/2, (Q-1)/2\]$.
+    fn signed(c: Zq1<Q>) -> i64 {
+        let abs = c.linf_norm().to_u64().unwrap();
+        if Zq1::<Q>::try_from(abs).unwrap() == c {
+            abs as i64
+        } else {
+            -(abs as i64)
+        }
+    }
+
+    #[test]
+    fn test_sample_uniform() {
+        const B: u64 = 10;
+        let rng = &mut test_rng();
+        let sampler = BoundedUniform::<Q>::new(B).unwrap();
+        let v = sampler.sample_vector::<N>(1000, rng);
+        let pmf: Vec<(i64, f64)> = (-(B as i64)..=B as i64)
+            .map(|x| (x, 1. / (2 * B + 1) as f64))
+            .collect();
+        assert_distribution!(
+            v.iter().flat_map(|p| p.coefficients()).map(signed),
+            pmf = &pmf
+        );
+    }
+
+    #[test]
+    fn test_acceptance_probability() {
+        let rng = &mut test_rng();
+        let sampler = BoundedUniform::<Q>::new(100).unwrap();
+        let center = Vector::<R>::from(vec![poly([10, -3, 0, 7, -10, 1, 2, 5]); 2]);
+        let expected = sampler.acceptance_probability(10, 2 * N);
+        assert!((expected - (181. / 201.0f64).powi(16)).abs() < 1e-12);
+
+        let accepted = (0..4000).map(|_| {
+            let z = sampler.sample_vector::<N>(2, rng) + center.clone();
+            rejection_sample(&z, &center, 100) as u8 as f64
+        });
+        assert_distribution!(accepted, mean = expected);
+    }
+
+    #[test]
+    fn test_accepted_independent_of_secret() {
+        const B: u64 = 20;
+        const T: i64 = 5;
+        let rng = &mut test_rng();
+        let sampler = BoundedUniform::<Q>::new(B).unwrap();
+        let centers = [
+            poly([T, -T, 0, 1, 2, -3, 4, 0]),
+            poly([-2, 0, T, -T, 3, 3, -1, 2]),
+        ];
+
+        // (position, value) of accepted coefficients, for both centers
+        let [a, b] = centers.map(|center| {
+            let center = Vector::<R>::from(vec![center]);
+            let mut accepted = vec![];
+            for _ in 0..20000 {
+                let z = sampler.sample_vector::<N>(1, rng) + center.clone();
+                if rejection_sample(&z, &center, B) {
+                    for (j, c) in z[0].coefficients().into_iter().enumerate() {
+                        assert!(signed(c).abs() <= B as i64 - T);
+                        accepted.push((j, signed(c)));
+                    }
+                }
+            }
+            accepted
+        });
+        assert_distribution!(a, same_as = b);
+    }
+
+    #[test]
+    fn test_rejection_boundary() {
+        let center = Vector::<R>::from(vec![poly([5, 0, 0, 0, 0, 0, 0, -1])]);
+        let z = |c: i64| Vector::<R>::from(vec![poly([c, 0, 0, 0, 0, 0, 0, 0])]);
+        assert!(rejection_sample(&z(15), &center, 20));
+        assert!(rejection_sample(&z(-15), &center, 20));
+        assert!(!rejection_sample(&z(16), &center, 20));
+        assert!(!rejection_sample(&z(-16), &center, 20));
+
+        // If the center is as large as the bound, only zero is accepted
+        assert!(rejection_sample(&z(0), &center, 5));
+        assert!(!rejection_sample(&z(1), &center, 5));
+        // If the center exceeds the bound, everything is rejected
+        assert!(!rejection_sample(&z(0), &center, 4));
+        assert_eq!(
+            BoundedUniform::<Q>::new(4)
+                .unwrap()
+                .acceptance_probability(5, N),
+            0.
+        );
+        assert!(
+            (BoundedUniform::<Q>::new(5)
+                .unwrap()
+                .acceptance_probability(5, N)
+                - 11f64.powi(-8))
+            .abs()
+                < 1e-20
+        );
+    }
+
+    /// Return the probabilities of $D\_{\mathbb{Z}, \sigma}$ restricted to $\[-B, B\]$, computed independently of the table.
+    fn gaussian_pmf(sigma: f64, bound: i64) -> Vec<(i64, f64)> {
+        let rho = |x: i64| (-((x * x) as f64) / (2. * sigma * sigma)).exp();
+        let total: f64 = (-bound..=bound).map(rho).sum();
+        (-bound..=bound).map(|x| (x, rho(x) / total)).collect()
+    }
+
+    #[test]
+    fn test_unsupported_sigma() {
+        for sigma in [0., 0.49, 20.01, -1., f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                ConstTimeGaussian::new(sigma),
+                Err(SamplerError::UnsupportedSigma(..))
+            ));
+        }
+        let sampler = ConstTimeGaussian::new(20.).unwrap();
+        assert_eq!(sampler.tail_bound(), 260);
+        assert_eq!(ConstTimeGaussian::new(0.5).unwrap().tail_bound(), 7);
+        assert_eq!(
+            ConstTimeGaussian::new(21.).unwrap_err().to_string(),
+            "sigma = 21 is outside the supported range [0.5, 20]"
+        );
+    }
+
+    #[test]
+    fn test_const_time_gaussian_distribution() {
+        let rng = &mut test_rng();
+        for sigma in [0.5, 1.7, 6.4, 19.5] {
+            let sampler = ConstTimeGaussian::new(sigma).unwrap();
+            let bound = sampler.tail_bound() as i64;
+            let pmf = gaussian_pmf(sigma, bound);
+            for &(x, p) in &pmf {
+                assert!((sampler.probability(x) - p).abs() <= 1e-15 + 1e-12 * p);
+            }
+            assert_eq!(sampler.probability(bound + 1), 0.);
+
+            let samples: Vec<i64> = (0..100000).map(|_| sampler.sample_signed(rng)).collect();
+            assert!(samples.iter().all(|x| x.abs() <= bound));
+            assert_distribution!(samples.iter().copied(), pmf = &pmf);
+            let variance: f64 = pmf.iter().map(|&(x, p)| (x * x) as f64 * p).sum();
+            assert_distribution!(
+                samples.iter().map(|&x| x as f64),
+                mean = 0.,
+                variance = variance
+            );
+        }
+    }
+
+    #[test]
+    fn test_const_time_gaussian_zq() {
+        let sampler = ConstTimeGaussian::new(3.).unwrap();
+        // The embedding into Z_Q agrees with the signed samples for the same randomness
+        let (rng, expected_rng) = (&mut test_rng(), &mut test_rng());
+        for _ in 0..1000 {
+            assert_eq!(
+                signed(sampler.sample::<Q>(rng)),
+                sampler.sample_signed(expected_rng)
+            );
+        }
+        let v = sampler.sample_vector::<Q, N>(100, rng);
+        assert_eq!(v.len(), 100);
+        assert!(v.linf_norm() <= BigUint::from(sampler.tail_bound()));
+        assert!(!v.linf_norm().is_zero());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_const_time_gaussian_small_modulus() {
+        ConstTimeGaussian::new(3.)
+            .unwrap()
+            .sample::<67>(&mut test_rng());
+    }
+
+    /// dudect-style timing test: Welch's t-test between the running times of samples that are zero and samples that are at least sigma in absolute value, after cropping outliers.
+    /// Run with `cargo test --release -- --ignored test_const_time_gaussian_timing`, on an otherwise idle machine.
+    #[test]
+    #[ignore]
+    fn test_const_time_gaussian_timing() {
+        const NUM_MEASUREMENTS: usize = 2000000;
+        const T_THRESHOLD: f64 = 4.5;
+        let rng = &mut test_rng();
+        let sampler = ConstTimeGaussian::new(ConstTimeGaussian::MAX_SIGMA).unwrap();
+        let mut measurements: Vec<(f64, i64)> = (0..NUM_MEASUREMENTS)
+            .map(|_| {
+                let start = Instant::now();
+                let x = std::hint::black_box(sampler.sample_signed(rng));
+                (start.elapsed().as_nanos() as f64, x)
+            })
+            .collect();
+        measurements.sort_by(|a, b| a.0.total_cmp(&b.0));
+        measurements.truncate(NUM_MEASUREMENTS * 9 / 10);
+
+        let small = Moments::new(measurements.iter().filter(|m| m.1 == 0).map(|m| m.0));
+        let large = Moments::new(
+            measurements
+                .iter()
+                .filter(|m| m.1.abs() as f64 >= sampler.sigma())
+                .map(|m| m.0),
+        );
+        let t = (small.mean - large.mean)
+            / (small.variance / small.num_samples as f64
+                + large.variance / large.num_samples as f64)
+                .sqrt();
+        assert!(
+            t.abs() < T_THRESHOLD,
+            "timing depends on the output: t = {t}"
+        );
+    }
+
+    fn crypto_rng() -> ChaCha20Rng {
+        ChaCha20Rng::seed_from_u64(0)
+    }
+
+    #[test]
+    fn test_bernoulli_exp_minus() {
+        let rng = &mut crypto_rng();
+        for (n, d) in [(0u32, 1u32), (1, 3), (1, 1), (5, 2), (7, 7)] {
+            let (n, d) = (BigUint::from(n), BigUint::from(d));
+            let p = (-(n.to_f64().unwrap() / d.to_f64().unwrap())).exp();
+            let samples = (0..20000).map(|_| bernoulli_exp_minus(&n, &d, rng) as u8 as f64);
+            assert_distribution!(samples, mean = p);
+        }
+    }
+
+    #[test]
+    fn test_rational_parameters() {
+        let scale = |l: &DiscreteLaplace| {
+            let (n, d) = l.scale();
+            (n.to_u64().unwrap(), d.to_u64().unwrap())
+        };
+        assert_eq!(scale(&DiscreteLaplace::new(0.25).unwrap()), (1, 4));
+        assert_eq!(scale(&DiscreteLaplace::new(1.5).unwrap()), (3, 2));
+        assert_eq!(scale(&DiscreteLaplace::new(1e6).unwrap()), (1000000, 1));
+        assert_eq!(
+            scale(&DiscreteLaplace::new(0.1).unwrap()),
+            (3602879701896397, 1 << 55)
+        );
+        assert_eq!(
+            DiscreteLaplace::from_rational(6, 4),
+            DiscreteLaplace::new(1.5)
+        );
+        assert_eq!(
+            DiscreteLaplace::new(f64::MIN_POSITIVE / 4.)
+                .unwrap()
+                .scale()
+                .1,
+            &(BigUint::one() << 1024)
+        );
+        for scale in [0., -1., f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                DiscreteLaplace::new(scale),
+                Err(SamplerError::InvalidParameter("scale", _))
+            ));
+        }
+        assert!(DiscreteLaplace::from_rational(0, 3).is_err());
+        assert!(DiscreteLaplace::from_rational(3, 0).is_err());
+
+        // sigma = 3/2 has variance 9/4
+        let gaussian = DiscreteGaussianDP::new(1.5).unwrap();
+        assert_eq!(
+            gaussian.variance(),
+            (&BigUint::from(9u32), &BigUint::from(4u32))
+        );
+        assert_eq!(
+            Ok(gaussian),
+            DiscreteGaussianDP::from_rational_variance(18, 8)
+        );
+        assert!(DiscreteGaussianDP::from_rational_variance(0, 1).is_err());
+        assert!(DiscreteGaussianDP::new(-2.).is_err());
+    }
+
+    #[test]
+    fn test_discrete_laplace_distribution() {
+        let rng = &mut crypto_rng();
+        for (n, d) in [(1, 2), (1, 1), (37, 10), (10, 1)] {
+            let sampler = DiscreteLaplace::from_rational(n, d).unwrap();
+            let scale = n as f64 / d as f64;
+            let q = (-1. / scale).exp();
+            let bound = (40. * scale) as i64 + 10;
+            let pmf: Vec<(i64, f64)> = (-bound..=bound)
+                .map(|x| (x, (1. - q) / (1. + q) * q.powi(x.abs() as i32)))
+                .collect();
+            let samples = (0..20000).map(|_| sampler.sample_integer(rng).to_i64().unwrap());
+            assert_distribution!(samples, pmf = &pmf);
+        }
+    }
+
+    #[test]
+    fn test_discrete_gaussian_dp_distribution() {
+        let rng = &mut crypto_rng();
+        let samplers = [
+            DiscreteGaussianDP::new(0.8).unwrap(),
+            DiscreteGaussianDP::from_rational_variance(2, 1).unwrap(),
+            DiscreteGaussianDP::new(5.5).unwrap(),
+        ];
+        for sampler in samplers {
+            let (n, d) = sampler.variance();
+            let sigma = (n.to_f64().unwrap() / d.to_f64().unwrap()).sqrt();
+            let pmf = gaussian_pmf(sigma, (12. * sigma) as i64 + 5);
+            let samples = (0..20000).map(|_| sampler.sample_integer(rng).to_i64().unwrap());
+            assert_distribution!(samples, pmf = &pmf);
+        }
+    }
+
+    #[test]
+    fn test_dp_sample_zq() {
+        // The reduced samples agree with the integer samples for the same randomness
+        let sampler = DiscreteLaplace::new(1e5).unwrap();
+        let (rng, expected_rng) = (&mut crypto_rng(), &mut crypto_rng());
+        let mut reduced = false;
+        for _ in 0..100 {
+            let x = sampler.sample_zq::<Q, _>(rng);
+            let expected = sampler.sample_integer(expected_rng).to_i64().unwrap();
+            assert_eq!((signed(x) - expected) % Q as i64, 0);
+            reduced |= expected.unsigned_abs() > Q / 2;
+        }
+        assert!(reduced);
+
+        let sampler = DiscreteGaussianDP::new(3.).unwrap();
+        let (rng, expected_rng) = (&mut crypto_rng(), &mut crypto_rng());
+        for _ in 0..100 {
+            let x = sampler.sample::<Zq1<Q>, _>(rng);
+            assert_eq!(x.0, sampler.sample_integer(expected_rng));
+        }
+    }
+}
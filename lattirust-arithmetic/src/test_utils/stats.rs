@@ -0,0 +1,419 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Default false-positive probability of the tests in this module, i.e., the probability that a test fails for samples from the target distribution.
+pub const DEFAULT_ALPHA: f64 = 1. / (1u64 << 30) as f64;
+
+/// Minimum expected count of a bin in [`chi_square`]; bins with smaller expected counts are pooled.
+pub const MIN_EXPECTED_COUNT: f64 = 5.;
+
+/// Outcome of a statistical test: the test passes iff `statistic <= critical_value`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TestOutcome {
+    pub test: &'static str,
+    pub num_samples: usize,
+    pub statistic: f64,
+    pub critical_value: f64,
+}
+
+impl TestOutcome {
+    pub fn is_pass(&self) -> bool {
+        self.statistic <= self.critical_value
+    }
+}
+
+impl fmt::Display for TestOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} on {} samples: statistic {:.4} (critical value {:.4}), {}",
+            self.test,
+            self.num_samples,
+            self.statistic,
+            self.critical_value,
+            if self.is_pass() { "pass" } else { "FAIL" }
+        )
+    }
+}
+
+/// Return $z$ such that $Pr\[Z > z\] \leq \alpha$ for a standard normal $Z$, using the tail bound $Pr\[Z > z\] \leq e^{-z^2/2}$.
+fn normal_quantile_bound(alpha: f64) -> f64 {
+    (2. * (1. / alpha).ln()).sqrt()
+}
+
+/// Return the (approximate) critical value of the chi-square distribution with `dof` degrees of freedom for false-positive probability `alpha`, using the Wilson-Hilferty approximation.
+pub fn chi_square_critical_value(dof: usize, alpha: f64) -> f64 {
+    let k = dof as f64;
+    let z = normal_quantile_bound(alpha);
+    k * (1. - 2. / (9. * k) + z * (2. / (9. * k)).sqrt()).powi(3)
+}
+
+/// Return the outcome of a chi-square test for the statistic `statistic` with `bins` bins.
+fn chi_square_outcome(
+    test: &'static str,
+    num_samples: usize,
+    statistic: f64,
+    bins: usize,
+    alpha: f64,
+) -> TestOutcome {
+    TestOutcome {
+        test,
+        num_samples,
+        statistic,
+        critical_value: if bins <= 1 {
+            f64::INFINITY
+        } else {
+            chi_square_critical_value(bins - 1, alpha)
+        },
+    }
+}
+
+/// Chi-square goodness-of-fit test of `samples` against the probability mass function `pmf`, given as pairs of values and probabilities summing to 1.
+/// Bins with expected count less than [`MIN_EXPECTED_COUNT`] are pooled into a single bin. Samples outside of the support of `pmf` fail the test.
+pub fn chi_square<T: Ord>(
+    samples: impl IntoIterator<Item = T>,
+    pmf: &[(T, f64)],
+    alpha: f64,
+) -> TestOutcome {
+    let total_probability: f64 = pmf.iter().map(|(_, p)| p).sum();
+    assert!(
+        (total_probability - 1.).abs() < 1e-6,
+        "probabilities sum to {total_probability}"
+    );
+    let index: BTreeMap<&T, usize> = pmf.iter().enumerate().map(|(i, (t, _))| (t, i)).collect();
+    let mut counts = vec![0usize; pmf.len()];
+    let mut num_samples = 0;
+    let mut outside_support = false;
+    for s in samples {
+        num_samples += 1;
+        match index.get(&s) {
+            Some(&i) => counts[i] += 1,
+            None => outside_support = true,
+        }
+    }
+    if outside_support {
+        return TestOutcome {
+            test: "chi-square",
+            num_samples,
+            statistic: f64::INFINITY,
+            critical_value: 0.,
+        };
+    }
+
+    let n = num_samples as f64;
+    let (mut statistic, mut bins) = (0., 0);
+    let (mut pooled_count, mut pooled_expected) = (0., 0.);
+    for (&count, (_, p)) in counts.iter().zip(pmf) {
+        let expected = n * p;
+        if expected < MIN_EXPECTED_COUNT {
+            pooled_count += count as f64;
+            pooled_expected += expected;
+        } else {
+            statistic += (count as f64 - expected).powi(2) / expected;
+            bins += 1;
+        }
+    }
+    if pooled_expected > 0. {
+        statistic += (pooled_count - pooled_expected).powi(2) / pooled_expected;
+        bins += 1;
+    }
+    chi_square_outcome("chi-square", num_samples, statistic, bins, alpha)
+}
+
+/// Two-sample chi-square test of whether `a` and `b` are drawn from the same (discrete) distribution.
+pub fn chi_square_two_sample<T: Ord>(
+    a: impl IntoIterator<Item = T>,
+    b: impl IntoIterator<Item = T>,
+    alpha: f64,
+) -> TestOutcome {
+    let mut counts = BTreeMap::<T, (usize, usize)>::new();
+    for s in a {
+        counts.entry(s).or_default().0 += 1;
+    }
+    for s in b {
+        counts.entry(s).or_default().1 += 1;
+    }
+    let n_a: usize = counts.values().map(|c| c.0).sum();
+    let n_b: usize = counts.values().map(|c| c.1).sum();
+    let ratio = (n_b as f64 / n_a as f64).sqrt();
+    let statistic = counts
+        .values()
+        .map(|&(x, y)| (x as f64 * ratio - y as f64 / ratio).powi(2) / (x + y) as f64)
+        .sum();
+    chi_square_outcome(
+        "two-sample chi-square",
+        n_a + n_b,
+        statistic,
+        counts.len(),
+        alpha,
+    )
+}
+
+/// Kolmogorov-Smirnov test of integer `samples` against a continuous distribution with cumulative distribution function `cdf` that is rounded to the nearest integer, e.g., a rounded Gaussian.
+/// The statistic is the largest distance between the empirical and the target CDF, and the critical value follows from the Dvoretzky-Kiefer-Wolfowitz inequality.
+pub fn kolmogorov_smirnov(
+    samples: impl IntoIterator<Item = i64>,
+    cdf: impl Fn(f64) -> f64,
+    alpha: f64,
+) -> TestOutcome {
+    let mut counts = BTreeMap::<i64, usize>::new();
+    for s in samples {
+        *counts.entry(s).or_default() += 1;
+    }
+    let n = counts.values().sum::<usize>();
+    let mut below = 0;
+    let mut statistic: f64 = 0.;
+    for (&k, &count) in &counts {
+        // Compare right before and at each jump of the empirical CDF
+        statistic = statistic.max((below as f64 / n as f64 - cdf(k as f64 - 0.5)).abs());
+        below += count;
+        statistic = statistic.max((below as f64 / n as f64 - cdf(k as f64 + 0.5)).abs());
+    }
+    TestOutcome {
+        test: "Kolmogorov-Smirnov",
+        num_samples: n,
+        statistic,
+        critical_value: ((2. / alpha).ln() / (2. * n as f64)).sqrt(),
+    }
+}
+
+/// Empirical mean and variance of a sample, with normal-approximation confidence intervals.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Moments {
+    pub num_samples: usize,
+    pub mean: f64,
+    pub variance: f64,
+    /// Fourth central moment, which determines the standard error of the variance
+    pub fourth_moment: f64,
+}
+
+impl Moments {
+    pub fn new(samples: impl IntoIterator<Item = f64>) -> Self {
+        let samples: Vec<f64> = samples.into_iter().collect();
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.);
+        let fourth_moment = samples.iter().map(|x| (x - mean).powi(4)).sum::<f64>() / n;
+        Moments {
+            num_samples: samples.len(),
+            mean,
+            variance,
+            fourth_moment,
+        }
+    }
+
+    fn mean_standard_error(&self) -> f64 {
+        (self.variance / self.num_samples as f64).sqrt()
+    }
+
+    fn variance_standard_error(&self) -> f64 {
+        ((self.fourth_moment - self.variance.powi(2)).max(0.) / self.num_samples as f64).sqrt()
+    }
+
+    /// Return a confidence interval for the mean that fails to contain it with probability at most `alpha` (up to the normal approximation).
+    pub fn mean_confidence_interval(&self, alpha: f64) -> (f64, f64) {
+        let radius = normal_quantile_bound(alpha / 2.) * self.mean_standard_error();
+        (self.mean - radius, self.mean + radius)
+    }
+
+    /// Return a confidence interval for the variance that fails to contain it with probability at most `alpha` (up to the normal approximation).
+    pub fn variance_confidence_interval(&self, alpha: f64) -> (f64, f64) {
+        let radius = normal_quantile_bound(alpha / 2.) * self.variance_standard_error();
+        (self.variance - radius, self.variance + radius)
+    }
+
+    fn z_test(
+        &self,
+        test: &'static str,
+        deviation: f64,
+        standard_error: f64,
+        alpha: f64,
+    ) -> TestOutcome {
+        TestOutcome {
+            test,
+            num_samples: self.num_samples,
+            statistic: if deviation == 0. {
+                0.
+            } else {
+                deviation.abs() / standard_error
+            },
+            critical_value: normal_quantile_bound(alpha / 2.),
+        }
+    }
+
+    /// Test whether the mean is `expected`.
+    pub fn test_mean(&self, expected: f64, alpha: f64) -> TestOutcome {
+        self.z_test(
+            "mean",
+            self.mean - expected,
+            self.mean_standard_error(),
+            alpha,
+        )
+    }
+
+    /// Test whether the variance is `expected`.
+    pub fn test_variance(&self, expected: f64, alpha: f64) -> TestOutcome {
+        self.z_test(
+            "variance",
+            self.variance - expected,
+            self.variance_standard_error(),
+            alpha,
+        )
+    }
+}
+
+/// Assert that samples follow a target distribution, with false-positive probability [`DEFAULT_ALPHA`] per test:
+/// - `assert_distribution!(samples, pmf = pmf)` runs [`chi_square`] against `pmf: &[(T, f64)]`,
+/// - `assert_distribution!(samples, same_as = other)` runs [`chi_square_two_sample`],
+/// - `assert_distribution!(samples, cdf = cdf)` runs [`kolmogorov_smirnov`] on integer samples,
+/// - `assert_distribution!(samples, mean = m)` and `assert_distribution!(samples, mean = m, variance = v)` test the [`Moments`] of `f64` samples,
+/// - `assert_distribution!(outcome)` asserts that a [`TestOutcome`] passes.
+#[macro_export]
+macro_rules! assert_distribution {
+    ($samples:expr, pmf = $pmf:expr) => {
+        $crate::assert_distribution!($crate::test_utils::stats::chi_square(
+            $samples,
+            $pmf,
+            $crate::test_utils::stats::DEFAULT_ALPHA
+        ))
+    };
+    ($samples:expr, same_as = $other:expr) => {
+        $crate::assert_distribution!($crate::test_utils::stats::chi_square_two_sample(
+            $samples,
+            $other,
+            $crate::test_utils::stats::DEFAULT_ALPHA
+        ))
+    };
+    ($samples:expr, cdf = $cdf:expr) => {
+        $crate::assert_distribution!($crate::test_utils::stats::kolmogorov_smirnov(
+            $samples,
+            $cdf,
+            $crate::test_utils::stats::DEFAULT_ALPHA
+        ))
+    };
+    ($samples:expr, mean = $mean:expr, variance = $variance:expr) => {{
+        let moments = $crate::test_utils::stats::Moments::new($samples);
+        let alpha = $crate::test_utils::stats::DEFAULT_ALPHA;
+        $crate::assert_distribution!(moments.test_mean($mean, alpha));
+        $crate::assert_distribution!(moments.test_variance($variance, alpha));
+    }};
+    ($samples:expr, mean = $mean:expr) => {
+        $crate::assert_distribution!($crate::test_utils::stats::Moments::new($samples)
+            .test_mean($mean, $crate::test_utils::stats::DEFAULT_ALPHA))
+    };
+    ($outcome:expr) => {{
+        let outcome: $crate::test_utils::stats::TestOutcome = $outcome;
+        assert!(outcome.is_pass(), "{}", outcome);
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use ark_std::rand::Rng;
+    use ark_std::test_rng;
+
+    use super::*;
+
+    const NUM_SAMPLES: usize = 10000;
+
+    fn uniform_pmf(m: i64) -> Vec<(i64, f64)> {
+        (0..m).map(|i| (i, 1. / m as f64)).collect()
+    }
+
+    #[test]
+    fn test_chi_square_critical_value() {
+        // Reference values of the chi-square quantile function
+        let z3 = chi_square_critical_value(10, 0.001);
+        assert!(z3 > 29.59, "{z3}");
+        let c = chi_square_critical_value(100, 1e-9);
+        assert!((209.0..240.).contains(&c), "{c}");
+    }
+
+    #[test]
+    fn test_chi_square() {
+        let rng = &mut test_rng();
+        let samples: Vec<i64> = (0..NUM_SAMPLES).map(|_| rng.gen_range(0..10)).collect();
+        assert_distribution!(samples.iter().copied(), pmf = &uniform_pmf(10));
+
+        // Biased samples
+        let biased = samples.iter().map(|&x| if x == 9 { 0 } else { x });
+        assert!(!chi_square(biased, &uniform_pmf(10), DEFAULT_ALPHA).is_pass());
+        // Samples outside of the support
+        let outside = samples.iter().map(|&x| x + 1);
+        assert!(!chi_square(outside, &uniform_pmf(10), DEFAULT_ALPHA).is_pass());
+    }
+
+    #[test]
+    fn test_chi_square_pooling() {
+        let rng = &mut test_rng();
+        // The tail values have expected counts below the threshold and are pooled
+        let mut pmf = vec![(0, 0.5), (1, 0.5 - 1e-4)];
+        pmf.extend((2..12).map(|i| (i, 1e-5)));
+        let samples = (0..NUM_SAMPLES).map(|_| match rng.gen_range(0..100000) {
+            x if x < 50000 => 0,
+            x if x < 99990 => 1,
+            x => 2 + x - 99990,
+        });
+        assert_distribution!(samples, pmf = &pmf);
+    }
+
+    #[test]
+    fn test_chi_square_two_sample() {
+        let rng = &mut test_rng();
+        let a: Vec<i64> = (0..NUM_SAMPLES).map(|_| rng.gen_range(0..10)).collect();
+        let b: Vec<i64> = (0..2 * NUM_SAMPLES).map(|_| rng.gen_range(0..10)).collect();
+        assert_distribution!(a.iter(), same_as = b.iter());
+        let c = (0..NUM_SAMPLES).map(|_| rng.gen_range(0..11));
+        assert!(!chi_square_two_sample(a, c, DEFAULT_ALPHA).is_pass());
+    }
+
+    /// Return the CDF of the normal distribution with standard deviation `sigma`, using the Abramowitz-Stegun approximation of erf.
+    fn normal_cdf(sigma: f64) -> impl Fn(f64) -> f64 {
+        move |x: f64| {
+            let t = x.abs() / (sigma * 2f64.sqrt());
+            let s = 1. / (1. + 0.3275911 * t);
+            let poly = s
+                * (0.254829592
+                    + s * (-0.284496736
+                        + s * (1.421413741 + s * (-1.453152027 + s * 1.061405429))));
+            let erf = 1. - poly * (-t * t).exp();
+            0.5 * (1. + erf.copysign(x))
+        }
+    }
+
+    #[test]
+    fn test_kolmogorov_smirnov() {
+        let rng = &mut test_rng();
+        // A sum of 12 uniforms in [-1/2, 1/2) has variance 1, and is close to a normal distribution
+        let sample = |rng: &mut _, sigma: f64| {
+            let x: f64 = (0..12).map(|_| Rng::gen::<f64>(rng) - 0.5).sum();
+            (sigma * x).round() as i64
+        };
+        let samples: Vec<i64> = (0..NUM_SAMPLES).map(|_| sample(rng, 10.)).collect();
+        assert_distribution!(samples.iter().copied(), cdf = normal_cdf(10.));
+        assert!(!kolmogorov_smirnov(samples, normal_cdf(14.), DEFAULT_ALPHA).is_pass());
+    }
+
+    #[test]
+    fn test_moments() {
+        let rng = &mut test_rng();
+        // Uniform in [0, 1) has mean 1/2 and variance 1/12
+        let samples: Vec<f64> = (0..NUM_SAMPLES).map(|_| rng.gen()).collect();
+        assert_distribution!(samples.iter().copied(), mean = 0.5, variance = 1. / 12.);
+
+        let moments = Moments::new(samples);
+        let (lo, hi) = moments.mean_confidence_interval(DEFAULT_ALPHA);
+        assert!(lo < 0.5 && 0.5 < hi);
+        assert!(hi - lo < 0.05);
+        let (lo, hi) = moments.variance_confidence_interval(DEFAULT_ALPHA);
+        assert!(lo < 1. / 12. && 1. / 12. < hi);
+        assert!(!moments.test_mean(0.53, DEFAULT_ALPHA).is_pass());
+        assert!(!moments.test_variance(0.1, DEFAULT_ALPHA).is_pass());
+    }
+
+    #[test]
+    #[should_panic(expected = "chi-square on 100 samples")]
+    fn test_assert_distribution_fails() {
+        assert_distribution!((0..100).map(|_| 0), pmf = &uniform_pmf(2));
+    }
+}
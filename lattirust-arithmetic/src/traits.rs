@@ -98,6 +98,16 @@ pub trait FromRandomBytes<T> {
     /// Returns the minimum number of bytes required to generate an element
     fn needs_bytes() -> usize;
 
+    /// Returns an upper bound on the probability that `try_from_random_bytes` rejects uniformly random bytes, which determines how many candidates rejection sampling needs.
+    /// Biased distributions use modular reduction and never reject; for unbiased distributions, the default of $1/2$ is conservative for rejection sampling from the next power of two.
+    fn rejection_probability() -> f64 {
+        if Self::has_no_bias() {
+            0.5
+        } else {
+            0.
+        }
+    }
+
     /// Returns `Some(t)` for a `t` created from `bytes`, or `None` if there was an error.
     fn try_from_random_bytes(bytes: &[u8]) -> Option<T> {
         if bytes.len() < Self::byte_size() {
@@ -114,6 +124,21 @@ pub trait FromRandomBytes<T> {
     fn try_from_random_bytes_inner(bytes: &[u8]) -> Option<T>;
 }
 
+/// Returns `len` elements created by `A` from consecutive chunks of `A::byte_size()` bytes, or `None` if `bytes` is shorter than `len * A::byte_size()` or some element cannot be created.
+/// Trailing bytes are ignored.
+pub(crate) fn try_from_random_bytes_chunks<T, A: FromRandomBytes<T>>(
+    bytes: &[u8],
+    len: usize,
+) -> Option<Vec<T>> {
+    let chunk_size = A::byte_size();
+    if bytes.len() < len * chunk_size {
+        return None;
+    }
+    (0..len)
+        .map(|i| A::try_from_random_bytes(&bytes[i * chunk_size..(i + 1) * chunk_size]))
+        .collect()
+}
+
 impl<T, const N: usize> FromRandomBytes<[T; N]> for [T; N]
 where
     T: FromRandomBytes<T>,
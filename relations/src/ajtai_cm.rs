@@ -1,7 +1,11 @@
 use ark_std::rand;
+use ark_std::rand::rngs::StdRng;
+use ark_std::rand::SeedableRng;
 use lattirust_arithmetic::linear_algebra::Matrix;
 use lattirust_arithmetic::linear_algebra::Vector;
 use lattirust_arithmetic::ring::PolyRing;
+use lattirust_arithmetic::traits::WithL2Norm;
+use num_traits::ToPrimitive;
 
 pub struct Crs<R: PolyRing> {
     pub n: usize,
@@ -35,3 +39,181 @@ pub type Witness<R> = Vector<R>;
 pub fn is_satisfied<R: PolyRing>(crs: &Crs<R>, x: &Instance<R>, w: &Witness<R>) -> bool {
     *x == &crs.ck * w && w.iter().all(|a| a.l2_norm() <= crs.norm_bound as f64)
 }
+
+/// Commitment to a message under a [`CommitmentKey`].
+/// Commitments are additively homomorphic: the sum of two commitments opens to the sums of the messages and randomness.
+pub type Commitment<R> = Vector<R>;
+
+/// Commitment key of the Ajtai commitment scheme, i.e., a uniformly random matrix $A \in R\_q^{n \times (\ell\_m + \ell\_r)}$.
+/// The commitment to a message $m \in R\_q^{\ell\_m}$ with randomness $r \in R\_q^{\ell\_r}$ is $A (m \\| r)$.
+/// It is binding for openings with $\lVert (m \\| r) \rVert\_2 \leq \beta$ as long as Module-SIS is hard for $A$ and length bound $2 \beta$, see [`CommitmentKey::msis_parameters`].
+pub struct CommitmentKey<R: PolyRing> {
+    pub message_len: usize,
+    pub randomness_len: usize,
+    pub a: Matrix<R>,
+}
+
+impl<R: PolyRing> CommitmentKey<R> {
+    /// Sample a commitment key producing commitments in $R\_q^n$.
+    pub fn new<Rng: rand::Rng + ?Sized>(
+        n: usize,
+        message_len: usize,
+        randomness_len: usize,
+        rng: &mut Rng,
+    ) -> CommitmentKey<R> {
+        CommitmentKey {
+            message_len,
+            randomness_len,
+            a: Matrix::<R>::rand(n, message_len + randomness_len, rng),
+        }
+    }
+
+    /// Derive a commitment key from `seed`, such that it can be communicated as 32 bytes.
+    pub fn from_seed(
+        n: usize,
+        message_len: usize,
+        randomness_len: usize,
+        seed: [u8; 32],
+    ) -> CommitmentKey<R> {
+        Self::new(n, message_len, randomness_len, &mut StdRng::from_seed(seed))
+    }
+
+    fn opening(message: &Vector<R>, randomness: &Vector<R>) -> Vector<R> {
+        message
+            .iter()
+            .chain(randomness.iter())
+            .copied()
+            .collect::<Vec<R>>()
+            .into()
+    }
+
+    /// Commit to `message` with `randomness`.
+    /// Panics if their lengths do not match the commitment key.
+    pub fn commit(&self, message: &Vector<R>, randomness: &Vector<R>) -> Commitment<R> {
+        assert_eq!(message.len(), self.message_len, "invalid message length");
+        assert_eq!(
+            randomness.len(),
+            self.randomness_len,
+            "invalid randomness length"
+        );
+        &self.a * &Self::opening(message, randomness)
+    }
+
+    /// Return true iff `(message, randomness)` is an opening of `com` whose $\ell\_2$ norm is at most `norm_bound`.
+    pub fn verify_opening(
+        &self,
+        com: &Commitment<R>,
+        message: &Vector<R>,
+        randomness: &Vector<R>,
+        norm_bound: f64,
+    ) -> bool {
+        if message.len() != self.message_len
+            || randomness.len() != self.randomness_len
+            || com.len() != self.a.nrows()
+        {
+            return false;
+        }
+        let opening = Self::opening(message, randomness);
+        opening.l2_norm_squared().to_f64().unwrap() <= norm_bound * norm_bound
+            && *com == &self.a * &opening
+    }
+
+    /// Return the parameters `(h, w, d, length_bound)` of the Module-SIS instance `MSIS\[h, w, d, q, length_bound\]` (see `lattice_estimator::msis::MSIS`) whose hardness implies the binding of commitments with openings of $\ell\_2$ norm at most `norm_bound`, where $q$ is the modulus of `R::BaseRing`.
+    pub fn msis_parameters(&self, norm_bound: f64) -> (usize, usize, usize, f64) {
+        (
+            self.a.nrows(),
+            self.a.ncols(),
+            R::dimension(),
+            2. * norm_bound,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use lattirust_arithmetic::ring::ntt::ntt_prime;
+    use lattirust_arithmetic::ring::{Pow2CyclotomicPolyRingNTT, Zq1};
+    use num_traits::{One, Zero};
+
+    use super::*;
+
+    const Q: u64 = ntt_prime::<64>(32);
+    const D: usize = 64;
+
+    type BaseRing = Zq1<Q>;
+    type R = Pow2CyclotomicPolyRingNTT<BaseRing, D>;
+
+    const N: usize = 4;
+    const MESSAGE_LEN: usize = 8;
+    const RANDOMNESS_LEN: usize = 16;
+    const NORM_BOUND: f64 = 128.;
+
+    fn ternary_vector<Rng: rand::Rng + ?Sized>(n: usize, rng: &mut Rng) -> Vector<R> {
+        Vector::<R>::from_fn(n, |_, _| {
+            let coeffs: Vec<BaseRing> = (0..D)
+                .map(|_| match rng.gen_range(0..3) {
+                    0 => -BaseRing::one(),
+                    1 => BaseRing::zero(),
+                    _ => BaseRing::one(),
+                })
+                .collect();
+            R::from(coeffs)
+        })
+    }
+
+    #[test]
+    fn test_commit_open() {
+        let rng = &mut ark_std::test_rng();
+        let ck = CommitmentKey::<R>::from_seed(N, MESSAGE_LEN, RANDOMNESS_LEN, [7; 32]);
+        assert_eq!(
+            ck.a,
+            CommitmentKey::<R>::from_seed(N, MESSAGE_LEN, RANDOMNESS_LEN, [7; 32]).a
+        );
+        let message = ternary_vector(MESSAGE_LEN, rng);
+        let randomness = ternary_vector(RANDOMNESS_LEN, rng);
+        let com = ck.commit(&message, &randomness);
+        assert_eq!(com.len(), N);
+        assert!(ck.verify_opening(&com, &message, &randomness, NORM_BOUND));
+
+        let other = ternary_vector(MESSAGE_LEN, rng);
+        assert!(!ck.verify_opening(&com, &other, &randomness, NORM_BOUND));
+        assert!(!ck.verify_opening(&com, &message, &message, NORM_BOUND));
+        assert_eq!(
+            ck.msis_parameters(NORM_BOUND),
+            (N, MESSAGE_LEN + RANDOMNESS_LEN, D, 2. * NORM_BOUND)
+        );
+    }
+
+    #[test]
+    fn test_commit_homomorphic() {
+        let rng = &mut ark_std::test_rng();
+        let ck = CommitmentKey::<R>::new(N, MESSAGE_LEN, RANDOMNESS_LEN, rng);
+        let (m1, r1) = (
+            ternary_vector(MESSAGE_LEN, rng),
+            ternary_vector(RANDOMNESS_LEN, rng),
+        );
+        let (m2, r2) = (
+            ternary_vector(MESSAGE_LEN, rng),
+            ternary_vector(RANDOMNESS_LEN, rng),
+        );
+        let com = ck.commit(&m1, &r1) + ck.commit(&m2, &r2);
+        assert!(ck.verify_opening(&com, &(m1 + m2), &(r1 + r2), 2. * NORM_BOUND));
+    }
+
+    #[test]
+    fn test_commit_norm_bound() {
+        let rng = &mut ark_std::test_rng();
+        let ck = CommitmentKey::<R>::new(N, MESSAGE_LEN, RANDOMNESS_LEN, rng);
+        let message = ternary_vector(MESSAGE_LEN, rng);
+        let randomness = ternary_vector(RANDOMNESS_LEN, rng);
+        let com = ck.commit(&message, &randomness);
+        // A non-zero ternary opening has l2 norm at least 1
+        assert!(!ck.verify_opening(&com, &message, &randomness, 1.));
+
+        // A uniformly random opening of the same commitment is not short
+        let large = Vector::<R>::rand(RANDOMNESS_LEN, rng);
+        let com = ck.commit(&message, &large);
+        assert!(!ck.verify_opening(&com, &message, &large, NORM_BOUND));
+        assert!(ck.verify_opening(&com, &message, &large, f64::INFINITY));
+    }
+}
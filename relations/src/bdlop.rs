@@ -0,0 +1,271 @@
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand;
+use ark_std::rand::rngs::StdRng;
+use ark_std::rand::SeedableRng;
+use num_traits::ToPrimitive;
+
+use lattirust_arithmetic::linear_algebra::Matrix;
+use lattirust_arithmetic::linear_algebra::Vector;
+use lattirust_arithmetic::ring::PolyRing;
+use lattirust_arithmetic::traits::WithL2Norm;
+
+/// Public parameters of the BDLOP commitment scheme [BDLOP18], i.e., uniformly random matrices $A\_1 \in R\_q^{n \times k}$ and $A\_2 \in R\_q^{\ell \times k}$ for commitments to $\ell$ messages with randomness $r \in R\_q^k$.
+/// The commitment to $m \in R\_q^\ell$ is $(t\_0, t\_1) = (A\_1 r, A\_2 r + m)$: the binding part $t\_0$ is an Ajtai commitment to $r$ (binding under Module-SIS), and the hiding part $t\_1$ masks $m$ (hiding under Module-LWE for short $r$).
+pub struct PublicParameters<R: PolyRing> {
+    pub num_messages: usize,
+    pub randomness_len: usize,
+    pub a1: Matrix<R>,
+    pub a2: Matrix<R>,
+}
+
+/// Commitment $(t\_0, t\_1)$ under [`PublicParameters`].
+/// Commitments are additively homomorphic: the sum of two commitments opens to the sums of the messages and randomness.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Commitment<P: PolyRing> {
+    pub t0: Vector<P>,
+    pub t1: Vector<P>,
+}
+
+impl<P: PolyRing> std::ops::Add for Commitment<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Commitment {
+            t0: self.t0 + rhs.t0,
+            t1: self.t1 + rhs.t1,
+        }
+    }
+}
+
+/// (Relaxed) opening $(c, m, \bar{r})$ of a commitment $(t\_0, t\_1)$, which is valid iff $c t\_0 = A\_1 \bar{r}$, $c t\_1 = A\_2 \bar{r} + c m$ and $\bar{r}$ is short.
+/// Exact openings have $c = 1$ and $\bar{r} = r$; relaxed openings with a challenge $c$ (e.g., sampled from `WeightedTernaryPolyChallengeSet`) are what knowledge extractors of proofs on commitments output.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Opening<R: PolyRing> {
+    pub messages: Vec<R>,
+    pub randomness: Vector<R>,
+    pub challenge: R,
+}
+
+impl<R: PolyRing> Opening<R> {
+    /// Return the relaxed opening with challenge $c \cdot$ `challenge` and randomness $c \bar{r}$, for the same messages.
+    pub fn relax(&self, c: R) -> Opening<R> {
+        Opening {
+            messages: self.messages.clone(),
+            randomness: self.randomness.map(|r| c * r),
+            challenge: c * self.challenge,
+        }
+    }
+}
+
+impl<R: PolyRing> PublicParameters<R> {
+    /// Sample public parameters producing binding parts in $R\_q^n$.
+    pub fn new<Rng: rand::Rng + ?Sized>(
+        n: usize,
+        num_messages: usize,
+        randomness_len: usize,
+        rng: &mut Rng,
+    ) -> PublicParameters<R> {
+        PublicParameters {
+            num_messages,
+            randomness_len,
+            a1: Matrix::<R>::rand(n, randomness_len, rng),
+            a2: Matrix::<R>::rand(num_messages, randomness_len, rng),
+        }
+    }
+
+    /// Derive public parameters from `seed`, such that they can be communicated as 32 bytes.
+    pub fn from_seed(
+        n: usize,
+        num_messages: usize,
+        randomness_len: usize,
+        seed: [u8; 32],
+    ) -> PublicParameters<R> {
+        Self::new(
+            n,
+            num_messages,
+            randomness_len,
+            &mut StdRng::from_seed(seed),
+        )
+    }
+
+    /// Commit to `messages` with `randomness`.
+    /// Panics if their lengths do not match the public parameters.
+    pub fn commit(&self, messages: &[R], randomness: &Vector<R>) -> Commitment<R> {
+        assert_eq!(
+            messages.len(),
+            self.num_messages,
+            "invalid number of messages"
+        );
+        assert_eq!(
+            randomness.len(),
+            self.randomness_len,
+            "invalid randomness length"
+        );
+        Commitment {
+            t0: &self.a1 * randomness,
+            t1: &self.a2 * randomness + Vector::<R>::from(messages.to_vec()),
+        }
+    }
+
+    /// Return the exact opening of the commitment to `messages` with `randomness`.
+    pub fn open(&self, messages: &[R], randomness: &Vector<R>) -> Opening<R> {
+        Opening {
+            messages: messages.to_vec(),
+            randomness: randomness.clone(),
+            challenge: R::one(),
+        }
+    }
+
+    /// Return true iff `opening` is a valid (relaxed) opening of `com` whose randomness has $\ell\_2$ norm at most `norm_bound`.
+    /// The challenge must be non-zero, since $c = 0$ and $\bar{r} = 0$ trivially satisfy the opening equations for any messages.
+    pub fn verify(&self, com: &Commitment<R>, opening: &Opening<R>, norm_bound: f64) -> bool {
+        if opening.challenge.is_zero()
+            || opening.messages.len() != self.num_messages
+            || opening.randomness.len() != self.randomness_len
+            || com.t0.len() != self.a1.nrows()
+            || com.t1.len() != self.num_messages
+        {
+            return false;
+        }
+        let c = opening.challenge;
+        let messages = Vector::<R>::from(opening.messages.clone());
+        opening.randomness.l2_norm_squared().to_f64().unwrap() <= norm_bound * norm_bound
+            && com.t0.map(|t| c * t) == &self.a1 * &opening.randomness
+            && com.t1.map(|t| c * t) == &self.a2 * &opening.randomness + messages.map(|m| c * m)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use ark_std::UniformRand;
+    use num_traits::{One, Zero};
+
+    use lattirust_arithmetic::challenge_set::weighted_ternary::WeightedTernaryPolyChallengeSet;
+    use lattirust_arithmetic::ring::ntt::ntt_prime;
+    use lattirust_arithmetic::ring::{Pow2CyclotomicPolyRing, Zq1};
+    use lattirust_arithmetic::traits::FromRandomBytes;
+
+    use super::*;
+
+    const Q: u64 = ntt_prime::<64>(32);
+    const D: usize = 64;
+
+    type BaseRing = Zq1<Q>;
+    type R = Pow2CyclotomicPolyRing<BaseRing, D>;
+
+    const N: usize = 2;
+    const NUM_MESSAGES: usize = 2;
+    const RANDOMNESS_LEN: usize = 6;
+    const NORM_BOUND: f64 = 64.;
+
+    fn ternary<Rng: rand::Rng + ?Sized>(rng: &mut Rng) -> R {
+        let coeffs: Vec<BaseRing> = (0..D)
+            .map(|_| match rng.gen_range(0..3) {
+                0 => -BaseRing::one(),
+                1 => BaseRing::zero(),
+                _ => BaseRing::one(),
+            })
+            .collect();
+        R::from(coeffs)
+    }
+
+    fn ternary_vector<Rng: rand::Rng + ?Sized>(n: usize, rng: &mut Rng) -> Vector<R> {
+        Vector::<R>::from_fn(n, |_, _| ternary(rng))
+    }
+
+    fn challenge<Rng: rand::Rng + ?Sized>(rng: &mut Rng) -> R {
+        let bytes: Vec<u8> = (0..WeightedTernaryPolyChallengeSet::<R>::byte_size())
+            .map(|_| rng.gen())
+            .collect();
+        WeightedTernaryPolyChallengeSet::<R>::try_from_random_bytes(&bytes).unwrap()
+    }
+
+    fn setup() -> (PublicParameters<R>, Vec<R>, Vector<R>) {
+        let rng = &mut ark_std::test_rng();
+        let pp = PublicParameters::<R>::from_seed(N, NUM_MESSAGES, RANDOMNESS_LEN, [1; 32]);
+        let messages = (0..NUM_MESSAGES).map(|_| R::rand(rng)).collect();
+        let randomness = ternary_vector(RANDOMNESS_LEN, rng);
+        (pp, messages, randomness)
+    }
+
+    #[test]
+    fn test_bdlop_exact_opening() {
+        let (pp, messages, randomness) = setup();
+        let com = pp.commit(&messages, &randomness);
+        assert!(pp.verify(&com, &pp.open(&messages, &randomness), NORM_BOUND));
+
+        let mut wrong = messages.clone();
+        wrong[0] += R::one();
+        assert!(!pp.verify(&com, &pp.open(&wrong, &randomness), NORM_BOUND));
+        assert!(!pp.verify(&com, &pp.open(&messages[1..], &randomness), NORM_BOUND));
+    }
+
+    #[test]
+    fn test_bdlop_relaxed_opening() {
+        let rng = &mut ark_std::test_rng();
+        let (pp, messages, randomness) = setup();
+        let com = pp.commit(&messages, &randomness);
+        let c = challenge(rng);
+        let relaxed = pp.open(&messages, &randomness).relax(c);
+        assert_eq!(relaxed.challenge, c);
+        // The product of a ternary challenge and ternary randomness has coefficients of size at most D
+        assert!(pp.verify(&com, &relaxed, NORM_BOUND * D as f64));
+        // The relaxed randomness is not an exact opening
+        let exact = Opening {
+            challenge: R::one(),
+            ..relaxed.clone()
+        };
+        assert!(!pp.verify(&com, &exact, NORM_BOUND * D as f64));
+    }
+
+    #[test]
+    fn test_bdlop_zero_challenge() {
+        let rng = &mut ark_std::test_rng();
+        let (pp, messages, randomness) = setup();
+        let com = pp.commit(&messages, &randomness);
+        let forged = Opening {
+            messages: (0..NUM_MESSAGES).map(|_| R::rand(rng)).collect(),
+            randomness: Vector::<R>::zeros(RANDOMNESS_LEN),
+            challenge: R::zero(),
+        };
+        assert!(!pp.verify(&com, &forged, NORM_BOUND));
+        assert!(!pp.verify(
+            &com,
+            &pp.open(&messages, &randomness).relax(R::zero()),
+            NORM_BOUND
+        ));
+    }
+
+    #[test]
+    fn test_bdlop_norm_bound() {
+        let rng = &mut ark_std::test_rng();
+        let (pp, messages, _) = setup();
+        let randomness = Vector::<R>::rand(RANDOMNESS_LEN, rng);
+        let com = pp.commit(&messages, &randomness);
+        let opening = pp.open(&messages, &randomness);
+        assert!(!pp.verify(&com, &opening, NORM_BOUND));
+        assert!(pp.verify(&com, &opening, f64::INFINITY));
+    }
+
+    #[test]
+    fn test_bdlop_homomorphic() {
+        let rng = &mut ark_std::test_rng();
+        let (pp, m1, r1) = setup();
+        let m2: Vec<R> = (0..NUM_MESSAGES).map(|_| R::rand(rng)).collect();
+        let r2 = ternary_vector(RANDOMNESS_LEN, rng);
+        let com = pp.commit(&m1, &r1) + pp.commit(&m2, &r2);
+        let messages: Vec<R> = m1.iter().zip(&m2).map(|(a, b)| *a + *b).collect();
+        assert!(pp.verify(&com, &pp.open(&messages, &(r1 + r2)), 2. * NORM_BOUND));
+    }
+
+    #[test]
+    fn test_bdlop_serialization() {
+        let (pp, messages, randomness) = setup();
+        let com = pp.commit(&messages, &randomness);
+        let mut bytes = Vec::new();
+        com.serialize_compressed(&mut bytes).unwrap();
+        let com_ = Commitment::<R>::deserialize_compressed(bytes.as_slice()).unwrap();
+        assert_eq!(com, com_);
+    }
+}
@@ -0,0 +1,319 @@
+use ark_std::rand::thread_rng;
+use num_bigint::BigUint;
+use num_traits::{ToPrimitive, Zero};
+
+use lattirust_arithmetic::error::ArithmeticError;
+use lattirust_arithmetic::ring::PolyRing;
+use lattirust_arithmetic::traits::{Modulus, WithLinfNorm};
+
+use crate::util::rand_bounded;
+use crate::Relation;
+
+/// Relation of well-formed BFV encryptions: a ciphertext $(c\_1, c\_2)$ encrypts $m$ under the public key $(pk\_1, pk\_2)$ with randomness $(u, e\_1, e\_2)$ iff
+/// $$c\_1 = pk\_1 u + e\_1 + \Delta m, \quad c\_2 = pk\_2 u + e\_2,$$
+/// and $u$, $e\_1$ and $e\_2$ are short in $\ell\_\infty$ norm.
+/// The relaxed relation with challenge $c$ (as output by knowledge extractors of proofs of plaintext knowledge) instead checks $c c\_1 = pk\_1 \bar{u} + \bar{e}\_1 + c \Delta m$ and $c c\_2 = pk\_2 \bar{u} + \bar{e}\_2$; exact openings have $c = 1$.
+pub struct BfvOpeningRelation<R: PolyRing> {
+    _marker: std::marker::PhantomData<R>,
+}
+
+pub struct Index<R: PolyRing> {
+    pub pk1: R,
+    pub pk2: R,
+    /// Scaling factor $\Delta = \lfloor q / p \rfloor$ of the plaintext, where $p$ is the plaintext modulus
+    pub delta: R::BaseRing,
+    /// Bound on the $\ell\_\infty$ norm of $u$
+    pub u_norm_bound: u64,
+    /// Bound on the $\ell\_\infty$ norms of $e\_1$ and $e\_2$
+    pub e_norm_bound: u64,
+}
+
+pub struct Instance<R: PolyRing> {
+    pub c1: R,
+    pub c2: R,
+    pub plaintext: R,
+}
+
+pub struct Witness<R: PolyRing> {
+    pub u: R,
+    pub e1: R,
+    pub e2: R,
+    /// Relaxation factor $c$, which is 1 for exact openings
+    pub challenge: R,
+}
+
+pub struct Size {
+    pub plaintext_modulus: u64,
+    pub u_norm_bound: u64,
+    pub e_norm_bound: u64,
+}
+
+impl<R: PolyRing> Index<R> {
+    /// Return the index for the public key $(pk\_1, pk\_2)$ and plaintext modulus `plaintext_modulus`, or [`ArithmeticError::InvalidParameters`] unless $1 < p \leq q$.
+    pub fn new(
+        pk1: R,
+        pk2: R,
+        plaintext_modulus: u64,
+        u_norm_bound: u64,
+        e_norm_bound: u64,
+    ) -> Result<Self, ArithmeticError> {
+        let modulus = R::BaseRing::modulus();
+        if plaintext_modulus < 2 || BigUint::from(plaintext_modulus) > modulus {
+            return Err(ArithmeticError::InvalidParameters(format!(
+                "the plaintext modulus {plaintext_modulus} must be in [2, {modulus}]"
+            )));
+        }
+        let delta = modulus / BigUint::from(plaintext_modulus);
+        // delta < q, so the conversion only fails if q does not fit in a u128
+        let delta = delta
+            .to_u128()
+            .and_then(|d| R::BaseRing::try_from(d).ok())
+            .ok_or_else(|| ArithmeticError::ConversionOverflow {
+                value: delta.clone(),
+                modulus: BigUint::from(u128::MAX) + 1u32,
+            })?;
+        Ok(Index {
+            pk1,
+            pk2,
+            delta,
+            u_norm_bound,
+            e_norm_bound,
+        })
+    }
+}
+
+impl<R: PolyRing> Witness<R> {
+    /// Return the exact opening with randomness $(u, e\_1, e\_2)$.
+    pub fn new(u: R, e1: R, e2: R) -> Self {
+        Witness {
+            u,
+            e1,
+            e2,
+            challenge: R::one(),
+        }
+    }
+}
+
+/// Return the index of the first coefficient at which `a` and `b` differ, if any.
+fn first_mismatch<R: PolyRing>(a: &R, b: &R) -> Option<usize> {
+    (*a - *b).coefficients().iter().position(|x| !x.is_zero())
+}
+
+/// Return the index and absolute value of the first coefficient of `r` whose absolute value exceeds `bound`, if any.
+fn first_over_bound<R: PolyRing>(r: &R, bound: u64) -> Option<(usize, BigUint)> {
+    r.coefficients()
+        .iter()
+        .map(|x| x.linf_norm())
+        .enumerate()
+        .find(|(_, abs)| *abs > BigUint::from(bound))
+}
+
+impl<R: PolyRing> Relation for BfvOpeningRelation<R> {
+    type Size = Size;
+    type Index = Index<R>;
+    type Instance = Instance<R>;
+    type Witness = Witness<R>;
+
+    fn is_well_defined_err(
+        i: &Self::Index,
+        _x: &Self::Instance,
+        w: Option<&Self::Witness>,
+    ) -> anyhow::Result<()> {
+        if i.delta.is_zero() {
+            anyhow::bail!("The plaintext modulus must be at most the ciphertext modulus");
+        }
+        if let Some(w) = w {
+            if w.challenge.is_zero() {
+                anyhow::bail!("The relaxation factor must be non-zero");
+            }
+        }
+        Ok(())
+    }
+
+    fn is_satisfied_err(
+        i: &Self::Index,
+        x: &Self::Instance,
+        w: &Self::Witness,
+    ) -> anyhow::Result<()> {
+        Self::is_well_defined_err(i, x, Some(w))?;
+
+        let c = w.challenge;
+        if let Some(j) = first_mismatch(
+            &(c * x.c1),
+            &(i.pk1 * w.u + w.e1 + c * x.plaintext * i.delta),
+        ) {
+            anyhow::bail!(
+                "c1 is not consistent with the plaintext and randomness at coefficient {j}"
+            );
+        }
+        if let Some(j) = first_mismatch(&(c * x.c2), &(i.pk2 * w.u + w.e2)) {
+            anyhow::bail!("c2 is not consistent with the randomness at coefficient {j}");
+        }
+        for (name, r, bound) in [
+            ("u", &w.u, i.u_norm_bound),
+            ("e1", &w.e1, i.e_norm_bound),
+            ("e2", &w.e2, i.e_norm_bound),
+        ] {
+            if let Some((j, abs)) = first_over_bound(r, bound) {
+                anyhow::bail!("Coefficient {j} of {name} has absolute value {abs} > {bound}");
+            }
+        }
+        Ok(())
+    }
+
+    fn generate_satisfied_instance(
+        size: &Self::Size,
+    ) -> (Self::Index, Self::Instance, Self::Witness) {
+        let rng = &mut thread_rng();
+        let index = Index::new(
+            R::rand(rng),
+            R::rand(rng),
+            size.plaintext_modulus,
+            size.u_norm_bound,
+            size.e_norm_bound,
+        )
+        .expect("invalid plaintext modulus");
+        let witness = Witness::new(
+            rand_bounded(size.u_norm_bound, rng),
+            rand_bounded(size.e_norm_bound, rng),
+            rand_bounded(size.e_norm_bound, rng),
+        );
+        let plaintext = rand_bounded(size.plaintext_modulus / 2, rng);
+        let instance = Instance {
+            c1: index.pk1 * witness.u + witness.e1 + plaintext * index.delta,
+            c2: index.pk2 * witness.u + witness.e2,
+            plaintext,
+        };
+        (index, instance, witness)
+    }
+
+    fn generate_unsatisfied_instance(
+        size: &Self::Size,
+    ) -> (Self::Index, Self::Instance, Self::Witness) {
+        let (index, mut instance, witness) = Self::generate_satisfied_instance(size);
+        instance.c2 += R::one();
+        (index, instance, witness)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use num_traits::{One, Zero};
+
+    use lattirust_arithmetic::ring::ntt::ntt_prime;
+    use lattirust_arithmetic::ring::{Pow2CyclotomicPolyRingNTT, Zq1};
+
+    use crate::{test_generate_satisfied_instance, test_generate_unsatisfied_instance};
+
+    use super::*;
+
+    const Q: u64 = ntt_prime::<64>(32);
+    const D: usize = 64;
+
+    type BaseRing = Zq1<Q>;
+    type R = Pow2CyclotomicPolyRingNTT<BaseRing, D>;
+    type RELATION = BfvOpeningRelation<R>;
+
+    const TEST_SIZE: Size = Size {
+        plaintext_modulus: 256,
+        u_norm_bound: 1,
+        e_norm_bound: 19,
+    };
+
+    test_generate_satisfied_instance!(RELATION, TEST_SIZE);
+
+    test_generate_unsatisfied_instance!(RELATION, TEST_SIZE);
+
+    /// Return $a X^j$.
+    fn monomial(j: usize, a: u64) -> R {
+        let mut coeffs = vec![BaseRing::zero(); D];
+        coeffs[j] = BaseRing::try_from(a).unwrap();
+        R::from(coeffs)
+    }
+
+    #[test]
+    fn test_invalid_plaintext_modulus() {
+        for p in [0, 1, Q + 1] {
+            let result = Index::<R>::new(R::one(), R::one(), p, 1, 1);
+            assert!(matches!(result, Err(ArithmeticError::InvalidParameters(_))));
+        }
+        let index = Index::<R>::new(R::one(), R::one(), Q, 1, 1).unwrap();
+        assert!(index.delta.is_one());
+        let index = Index::<R>::new(R::one(), R::one(), 2, 1, 1).unwrap();
+        assert_eq!(index.delta, BaseRing::try_from(Q / 2).unwrap());
+    }
+
+    #[test]
+    fn test_perturbed_randomness() {
+        let (index, instance, mut witness) = RELATION::generate_satisfied_instance(&TEST_SIZE);
+        witness.e2 += R::one();
+        let err = RELATION::is_satisfied_err(&index, &instance, &witness).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "c2 is not consistent with the randomness at coefficient 0"
+        );
+    }
+
+    #[test]
+    fn test_over_norm_randomness() {
+        let (index, mut instance, mut witness) = RELATION::generate_satisfied_instance(&TEST_SIZE);
+        // Shift coefficient 3 of the error and adjust the ciphertext, so that only the bound check fails
+        let shift = monomial(3, 1000);
+        witness.e1 += shift;
+        instance.c1 += shift;
+        assert!(witness.e1.linf_norm() > BigUint::from(TEST_SIZE.e_norm_bound));
+        let err = RELATION::is_satisfied_err(&index, &instance, &witness).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Coefficient 3 of e1 has absolute value"),
+            "{err}"
+        );
+
+        let (index, mut instance, mut witness) = RELATION::generate_satisfied_instance(&TEST_SIZE);
+        let shift = monomial(7, 3);
+        witness.u += shift;
+        instance.c1 += index.pk1 * shift;
+        instance.c2 += index.pk2 * shift;
+        let err = RELATION::is_satisfied_err(&index, &instance, &witness).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Coefficient 7 of u has absolute value"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_plaintext_mismatch() {
+        let (index, mut instance, witness) = RELATION::generate_satisfied_instance(&TEST_SIZE);
+        instance.plaintext += monomial(5, 1);
+        let err = RELATION::is_satisfied_err(&index, &instance, &witness).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "c1 is not consistent with the plaintext and randomness at coefficient 5"
+        );
+    }
+
+    #[test]
+    fn test_relaxed_opening() {
+        let rng = &mut thread_rng();
+        let (mut index, instance, witness) = RELATION::generate_satisfied_instance(&TEST_SIZE);
+        let c = rand_bounded::<R, _>(1, rng);
+        let relaxed = Witness {
+            u: c * witness.u,
+            e1: c * witness.e1,
+            e2: c * witness.e2,
+            challenge: c,
+        };
+        // The relaxed randomness is larger by a factor of up to D
+        assert!(!RELATION::is_satisfied(&index, &instance, &relaxed));
+        index.u_norm_bound *= D as u64;
+        index.e_norm_bound *= D as u64;
+        RELATION::is_satisfied_err(&index, &instance, &relaxed).unwrap();
+        // The relaxation factor must match the randomness
+        let wrong = Witness {
+            challenge: R::one(),
+            ..relaxed
+        };
+        assert!(!RELATION::is_satisfied(&index, &instance, &wrong));
+    }
+}
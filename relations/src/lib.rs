@@ -1,9 +1,13 @@
 #![feature(associated_type_defaults)]
 
 pub mod ajtai_cm;
+pub mod bdlop;
+pub mod bfv_opening;
+pub mod linear;
 pub mod principal_relation;
 pub mod r1cs;
 pub mod reduction;
+mod util;
 
 pub trait Relation {
     type Size;
@@ -13,7 +17,9 @@ pub trait Relation {
 
     /// Returns true iff the index `i` and instance `x` (and witness `w`, if not `None`) are well-defined.
     /// For example, for R1CS, this function should check that the dimensions of the matrices A, B, and C are the same, are consistent with the public parameters, and that the witness has the correct length.
-    fn is_well_defined(i: &Self::Index, x: &Self::Instance, w: Option<&Self::Witness>) -> bool;
+    fn is_well_defined(i: &Self::Index, x: &Self::Instance, w: Option<&Self::Witness>) -> bool {
+        Self::is_well_defined_err(i, x, w).is_ok()
+    }
 
     /// Returns true iff the index `i` and instance `x` (and witness `w`, if not `None`) are well-defined.
     /// For example, for R1CS, this function should check that the dimensions of the matrices A, B, and C are the same, are consistent with the public parameters, and that the witness has the correct length.
@@ -25,10 +31,12 @@ pub trait Relation {
 
     /// Return true iff the index `i` and instance `x` and witness `w` satisfy the relation.
     /// For example, for R1CS, this function should check that Aw * Bw = Cw.
-    fn is_satisfied(i: &Self::Index, x: &Self::Instance, w: &Self::Witness) -> bool;
+    fn is_satisfied(i: &Self::Index, x: &Self::Instance, w: &Self::Witness) -> bool {
+        Self::is_satisfied_err(i, x, w).is_ok()
+    }
 
-    /// Return true iff the index `i` and instance `x` and witness `w` satisfy the relation.
-    /// For example, for R1CS, this function should check that Aw * Bw = Cw.
+    /// Return `Ok(())` iff the index `i` and instance `x` and witness `w` satisfy the relation, and otherwise an error describing the first violated constraint (e.g., its index).
+    /// For example, for R1CS, this function should check that Aw * Bw = Cw, and report which row differs.
     fn is_satisfied_err(
         i: &Self::Index,
         x: &Self::Instance,
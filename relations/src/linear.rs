@@ -0,0 +1,374 @@
+use std::fmt;
+use std::fmt::Display;
+
+use ark_std::rand;
+use ark_std::rand::thread_rng;
+use num_traits::ToPrimitive;
+
+use lattirust_arithmetic::linear_algebra::{Matrix, SparseMatrix, Vector};
+use lattirust_arithmetic::ring::PolyRing;
+use lattirust_arithmetic::traits::{WithL2Norm, WithLinfNorm};
+
+use crate::util::rand_bounded;
+use crate::Relation;
+
+/// Relation of short preimages of a linear map: a witness $w \in R\_q^m$ satisfies the instance $t \in R\_q^n$ iff $A w = t$ and $\lVert w \rVert \leq \beta$, in the $\ell\_2$ or $\ell\_\infty$ norm.
+/// This covers, e.g., openings of Ajtai commitments (see [`crate::ajtai_cm::CommitmentKey::verify_opening`]).
+/// The relaxed relation with challenge $c$ (as output by knowledge extractors) instead checks $A \bar{w} = c t$, usually for a bound scaled by a slack factor, see [`Index::with_slack`]; exact witnesses have $c = 1$.
+pub struct BoundedLinearRelation<R: PolyRing> {
+    _marker: std::marker::PhantomData<R>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Norm {
+    L2,
+    Linf,
+}
+
+impl Display for Norm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Norm::L2 => write!(f, "L2"),
+            Norm::Linf => write!(f, "Linf"),
+        }
+    }
+}
+
+/// The matrix $A$, which is stored densely or sparsely.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LinearMap<R: PolyRing> {
+    Dense(Matrix<R>),
+    Sparse(SparseMatrix<R>),
+}
+
+impl<R: PolyRing> LinearMap<R> {
+    pub fn nrows(&self) -> usize {
+        match self {
+            LinearMap::Dense(a) => a.nrows(),
+            LinearMap::Sparse(a) => a.nrows(),
+        }
+    }
+
+    pub fn ncols(&self) -> usize {
+        match self {
+            LinearMap::Dense(a) => a.ncols(),
+            LinearMap::Sparse(a) => a.ncols(),
+        }
+    }
+
+    /// Return $A w$.
+    pub fn apply(&self, w: &Vector<R>) -> Vector<R> {
+        match self {
+            LinearMap::Dense(a) => a * w,
+            LinearMap::Sparse(a) => a * w,
+        }
+    }
+}
+
+pub struct Index<R: PolyRing> {
+    pub a: LinearMap<R>,
+    pub norm: Norm,
+    /// Bound $\beta$ on the norm of the witness
+    pub bound: f64,
+}
+
+pub type Instance<R> = Vector<R>;
+
+pub struct Witness<R: PolyRing> {
+    pub w: Vector<R>,
+    /// Relaxation factor $c$, which is 1 for exact witnesses
+    pub challenge: R,
+}
+
+pub struct Size {
+    pub nrows: usize,
+    pub ncols: usize,
+    pub norm: Norm,
+    pub bound: f64,
+}
+
+impl<R: PolyRing> Index<R> {
+    /// Return the index with the bound multiplied by `slack`, e.g., the operator norm bound of the challenge differences, to check relaxed witnesses.
+    pub fn with_slack(self, slack: f64) -> Self {
+        Index {
+            bound: self.bound * slack,
+            ..self
+        }
+    }
+
+    /// Return the norm of `w` in [`Index::norm`].
+    pub fn norm_of(&self, w: &Vector<R>) -> f64 {
+        match self.norm {
+            Norm::L2 => w.l2_norm_squared().to_f64().unwrap().sqrt(),
+            Norm::Linf => w.linf_norm().to_f64().unwrap(),
+        }
+    }
+
+    /// Return a uniformly random witness whose coefficients are bounded such that its norm is at most [`Index::bound`], and the corresponding instance.
+    /// For the $\ell\_2$ norm, coefficients are bounded by $\lfloor \beta / \sqrt{m d} \rfloor$, where $d$ is the dimension of `R`.
+    pub fn random_satisfying_instance<Rng: rand::Rng + ?Sized>(
+        &self,
+        rng: &mut Rng,
+    ) -> (Instance<R>, Witness<R>) {
+        let coefficient_bound = match self.norm {
+            Norm::L2 => self.bound / ((self.a.ncols() * R::dimension()) as f64).sqrt(),
+            Norm::Linf => self.bound,
+        }
+        .floor() as u64;
+        let w = Vector::<R>::from_fn(self.a.ncols(), |_, _| rand_bounded(coefficient_bound, rng));
+        (self.a.apply(&w), Witness::new(w))
+    }
+}
+
+impl<R: PolyRing> Witness<R> {
+    /// Return the exact witness `w`.
+    pub fn new(w: Vector<R>) -> Self {
+        Witness {
+            w,
+            challenge: R::one(),
+        }
+    }
+}
+
+impl<R: PolyRing> Relation for BoundedLinearRelation<R> {
+    type Size = Size;
+    type Index = Index<R>;
+    type Instance = Instance<R>;
+    type Witness = Witness<R>;
+
+    fn is_well_defined_err(
+        i: &Self::Index,
+        x: &Self::Instance,
+        w: Option<&Self::Witness>,
+    ) -> anyhow::Result<()> {
+        if i.bound.is_nan() || i.bound < 0. {
+            anyhow::bail!("The norm bound must be non-negative");
+        }
+        if i.a.nrows() != x.len() {
+            anyhow::bail!("The number of rows of A must be equal to the length of t");
+        }
+        if let Some(w) = w {
+            if i.a.ncols() != w.w.len() {
+                anyhow::bail!("The number of columns of A must be equal to the length of w");
+            }
+            if w.challenge.is_zero() {
+                anyhow::bail!("The relaxation factor must be non-zero");
+            }
+        }
+        Ok(())
+    }
+
+    fn is_satisfied_err(
+        i: &Self::Index,
+        x: &Self::Instance,
+        w: &Self::Witness,
+    ) -> anyhow::Result<()> {
+        Self::is_well_defined_err(i, x, Some(w))?;
+
+        let aw = i.a.apply(&w.w);
+        if let Some(row) = aw
+            .iter()
+            .zip(x.iter())
+            .position(|(aw, t)| *aw != w.challenge * *t)
+        {
+            anyhow::bail!("t is not consistent with w at row {row}");
+        }
+        let norm = i.norm_of(&w.w);
+        if norm > i.bound {
+            anyhow::bail!("The {} norm of w is {norm} > {}", i.norm, i.bound);
+        }
+        Ok(())
+    }
+
+    fn generate_satisfied_instance(
+        size: &Self::Size,
+    ) -> (Self::Index, Self::Instance, Self::Witness) {
+        let rng = &mut thread_rng();
+        let index = Index {
+            a: LinearMap::Dense(Matrix::<R>::rand(size.nrows, size.ncols, rng)),
+            norm: size.norm,
+            bound: size.bound,
+        };
+        let (instance, witness) = index.random_satisfying_instance(rng);
+        (index, instance, witness)
+    }
+
+    fn generate_unsatisfied_instance(
+        size: &Self::Size,
+    ) -> (Self::Index, Self::Instance, Self::Witness) {
+        let (index, mut instance, witness) = Self::generate_satisfied_instance(size);
+        instance[0] += R::one();
+        (index, instance, witness)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use num_traits::{One, Zero};
+
+    use lattirust_arithmetic::ring::ntt::ntt_prime;
+    use lattirust_arithmetic::ring::{Pow2CyclotomicPolyRingNTT, Zq1};
+
+    use crate::ajtai_cm::CommitmentKey;
+    use crate::{test_generate_satisfied_instance, test_generate_unsatisfied_instance};
+
+    use super::*;
+
+    const Q: u64 = ntt_prime::<64>(32);
+    const D: usize = 64;
+
+    type BaseRing = Zq1<Q>;
+    type R = Pow2CyclotomicPolyRingNTT<BaseRing, D>;
+    type RELATION = BoundedLinearRelation<R>;
+
+    const TEST_SIZE: Size = Size {
+        nrows: 4,
+        ncols: 8,
+        norm: Norm::L2,
+        bound: 100.,
+    };
+
+    test_generate_satisfied_instance!(RELATION, TEST_SIZE);
+
+    test_generate_unsatisfied_instance!(RELATION, TEST_SIZE);
+
+    /// Return $a X^j$.
+    fn monomial(j: usize, a: u64) -> R {
+        let mut coeffs = vec![BaseRing::zero(); D];
+        coeffs[j] = BaseRing::try_from(a).unwrap();
+        R::from(coeffs)
+    }
+
+    fn triplets() -> Vec<(usize, usize, R)> {
+        vec![
+            (0, 0, R::one()),
+            (0, 2, monomial(1, 1)),
+            (1, 1, monomial(0, 3)),
+            (2, 2, -R::one()),
+        ]
+    }
+
+    fn sparse_index(norm: Norm, bound: f64) -> Index<R> {
+        Index {
+            a: LinearMap::Sparse(SparseMatrix::try_from_triplets(3, 4, triplets()).unwrap()),
+            norm,
+            bound,
+        }
+    }
+
+    #[test]
+    fn test_norms() {
+        let rng = &mut thread_rng();
+        for norm in [Norm::L2, Norm::Linf] {
+            let index = sparse_index(norm, 10.);
+            let (instance, witness) = index.random_satisfying_instance(rng);
+            RELATION::is_satisfied_err(&index, &instance, &witness).unwrap();
+        }
+
+        // w = (5, 0, 0, 0) has l2 and linf norm 5, w = (3, 4, 0, 0) has l2 norm 5 and linf norm 4
+        let w = |coeffs: [u64; 4]| Witness::new(Vector::from_fn(4, |i, _| monomial(0, coeffs[i])));
+        for (witness, l2, linf) in [(w([5, 0, 0, 0]), 5., 5.), (w([3, 4, 0, 0]), 5., 4.)] {
+            for (norm, value) in [(Norm::L2, l2), (Norm::Linf, linf)] {
+                let index = sparse_index(norm, value);
+                let instance = index.a.apply(&witness.w);
+                RELATION::is_satisfied_err(&index, &instance, &witness).unwrap();
+                let index = sparse_index(norm, value - 0.5);
+                let err = RELATION::is_satisfied_err(&index, &instance, &witness).unwrap_err();
+                assert_eq!(
+                    err.to_string(),
+                    format!("The {norm} norm of w is {value} > {}", value - 0.5)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_inconsistent_instance() {
+        let rng = &mut thread_rng();
+        let index = sparse_index(Norm::Linf, 1.);
+        let (mut instance, witness) = index.random_satisfying_instance(rng);
+        instance[1] += R::one();
+        let err = RELATION::is_satisfied_err(&index, &instance, &witness).unwrap_err();
+        assert_eq!(err.to_string(), "t is not consistent with w at row 1");
+
+        // Dimension mismatch
+        let instance = Vector::<R>::from(vec![R::one(); 4]);
+        assert!(!RELATION::is_well_defined(
+            &index,
+            &instance,
+            Some(&witness)
+        ));
+    }
+
+    #[test]
+    fn test_dense_and_sparse() {
+        let rng = &mut thread_rng();
+        let sparse = sparse_index(Norm::L2, 20.);
+        let dense = Index {
+            a: LinearMap::Dense(Matrix::from_fn(3, 4, |i, j| {
+                triplets()
+                    .into_iter()
+                    .filter(|t| (t.0, t.1) == (i, j))
+                    .map(|t| t.2)
+                    .sum()
+            })),
+            ..sparse_index(Norm::L2, 20.)
+        };
+        assert_eq!((dense.a.nrows(), dense.a.ncols()), (3, 4));
+        for _ in 0..10 {
+            let (instance, witness) = sparse.random_satisfying_instance(rng);
+            RELATION::is_satisfied_err(&dense, &instance, &witness).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_relaxed_witness() {
+        let rng = &mut thread_rng();
+        let (index, instance, witness) = RELATION::generate_satisfied_instance(&TEST_SIZE);
+        let c = rand_bounded::<R, _>(1, rng);
+        let relaxed = Witness {
+            w: witness.w.map(|x| c * x),
+            challenge: c,
+        };
+        // The relaxed witness is larger by a factor of up to D
+        assert!(!RELATION::is_satisfied(&index, &instance, &relaxed));
+        let index = index.with_slack(D as f64);
+        RELATION::is_satisfied_err(&index, &instance, &relaxed).unwrap();
+        // The relaxation factor must match the witness
+        let wrong = Witness {
+            challenge: R::one(),
+            ..relaxed
+        };
+        assert!(!RELATION::is_satisfied(&index, &instance, &wrong));
+        let zero = Witness {
+            challenge: R::zero(),
+            ..wrong
+        };
+        assert!(!RELATION::is_well_defined(&index, &instance, Some(&zero)));
+    }
+
+    #[test]
+    fn test_consistent_with_ajtai_opening() {
+        let rng = &mut thread_rng();
+        let ck = CommitmentKey::<R>::new(4, 3, 5, rng);
+        let index = |bound| Index {
+            a: LinearMap::Dense(ck.a.clone()),
+            norm: Norm::L2,
+            bound,
+        };
+        for bound in [1., 40.] {
+            let (com, witness) = index(bound).random_satisfying_instance(rng);
+            let (message, randomness) = witness.w.as_slice().split_at(3);
+            let (message, randomness) = (
+                Vector::<R>::from(message.to_vec()),
+                Vector::<R>::from(randomness.to_vec()),
+            );
+            for norm_bound in [0.5, 1., 39., 40.] {
+                assert_eq!(
+                    RELATION::is_satisfied(&index(norm_bound), &com, &witness),
+                    ck.verify_opening(&com, &message, &randomness, norm_bound)
+                );
+            }
+        }
+    }
+}
@@ -420,10 +420,6 @@ where
     type Instance = Instance<R>;
     type Witness = Witness<R>;
 
-    fn is_well_defined(i: &Self::Index, x: &Self::Instance, w: Option<&Self::Witness>) -> bool {
-        Self::is_well_defined_err(i, x, w).is_ok()
-    }
-
     fn is_well_defined_err(
         i: &Self::Index,
         x: &Self::Instance,
@@ -436,10 +432,6 @@ where
         }
     }
 
-    fn is_satisfied(i: &Self::Index, x: &Self::Instance, w: &Self::Witness) -> bool {
-        Self::is_satisfied_err(i, x, w).is_ok()
-    }
-
     fn is_satisfied_err(
         i: &Self::Index,
         x: &Self::Instance,
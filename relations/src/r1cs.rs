@@ -34,10 +34,6 @@ impl<R: Ring> Relation for R1CS<R> {
     type Instance = Instance<R>;
     type Witness = Witness<R>;
 
-    fn is_well_defined(i: &Self::Index, x: &Self::Instance, w: Option<&Self::Witness>) -> bool {
-        Self::is_well_defined_err(i, x, w).is_ok()
-    }
-
     fn is_well_defined_err(
         i: &Self::Index,
         x: &Self::Instance,
@@ -68,10 +64,6 @@ impl<R: Ring> Relation for R1CS<R> {
         }
     }
 
-    fn is_satisfied(i: &Self::Index, x: &Self::Instance, w: &Self::Witness) -> bool {
-        Self::is_satisfied_err(i, x, w).is_ok()
-    }
-
     fn is_satisfied_err(
         i: &Self::Index,
         x: &Self::Instance,
@@ -90,10 +82,11 @@ impl<R: Ring> Relation for R1CS<R> {
         let b_z = &i.b * &z;
         let c_z = &i.c * &z;
 
-        if a_z.component_mul(&b_z) != c_z {
-            anyhow::bail!("The R1CS constraint is not satisfied");
-        } else {
-            Ok(())
+        let ab_z = a_z.component_mul(&b_z);
+        let unsatisfied = ab_z.iter().zip(c_z.iter()).position(|(ab, c)| ab != c);
+        match unsatisfied {
+            Some(row) => anyhow::bail!("R1CS constraint {row} is not satisfied"),
+            None => Ok(()),
         }
     }
 
@@ -195,6 +188,97 @@ impl<R: Ring> Relation for R1CS<R> {
     }
 }
 
+/// Builds the [`Index`] of an R1CS constraint by constraint, over the variables $z = (x, w)$ of an [`Instance`] with `num_instance_variables` entries followed by a [`Witness`] with `num_witness_variables` entries.
+/// As in [`R1CS::generate_satisfied_instance`], the first instance variable is expected to be the constant 1.
+pub struct R1CSBuilder<R: Ring> {
+    num_instance_variables: usize,
+    num_witness_variables: usize,
+    a_triplets: Vec<(usize, usize, R)>,
+    b_triplets: Vec<(usize, usize, R)>,
+    c_triplets: Vec<(usize, usize, R)>,
+    num_constraints: usize,
+}
+
+impl<R: Ring> R1CSBuilder<R> {
+    pub fn new(num_instance_variables: usize, num_witness_variables: usize) -> Self {
+        Self {
+            num_instance_variables,
+            num_witness_variables,
+            a_triplets: vec![],
+            b_triplets: vec![],
+            c_triplets: vec![],
+            num_constraints: 0,
+        }
+    }
+
+    pub fn num_variables(&self) -> usize {
+        self.num_instance_variables + self.num_witness_variables
+    }
+
+    pub fn num_constraints(&self) -> usize {
+        self.num_constraints
+    }
+
+    /// Return the index in $z$ of the `i`-th instance variable.
+    pub fn instance_variable(&self, i: usize) -> usize {
+        assert!(
+            i < self.num_instance_variables,
+            "instance variable {i} out of range"
+        );
+        i
+    }
+
+    /// Return the index in $z$ of the `i`-th witness variable.
+    pub fn witness_variable(&self, i: usize) -> usize {
+        assert!(
+            i < self.num_witness_variables,
+            "witness variable {i} out of range"
+        );
+        self.num_instance_variables + i
+    }
+
+    /// Add the constraint $\langle a, z \rangle \cdot \langle b, z \rangle = \langle c, z \rangle$, where `a`, `b`, and `c` are sparse linear combinations given as `(variable, coefficient)` pairs, and return its index.
+    /// Coefficients of repeated variables are summed. Panics if a variable is out of range.
+    pub fn add_constraint(
+        &mut self,
+        a: &[(usize, R)],
+        b: &[(usize, R)],
+        c: &[(usize, R)],
+    ) -> usize {
+        let row = self.num_constraints;
+        let num_variables = self.num_variables();
+        for (lc, triplets) in [
+            (a, &mut self.a_triplets),
+            (b, &mut self.b_triplets),
+            (c, &mut self.c_triplets),
+        ] {
+            for &(variable, coefficient) in lc {
+                assert!(variable < num_variables, "variable {variable} out of range");
+                triplets.push((row, variable, coefficient));
+            }
+        }
+        self.num_constraints += 1;
+        row
+    }
+
+    pub fn size(&self) -> Size {
+        Size {
+            num_constraints: self.num_constraints,
+            num_instance_variables: self.num_instance_variables,
+            num_witness_variables: self.num_witness_variables,
+        }
+    }
+
+    pub fn build(self) -> Index<R> {
+        let (m, n) = (self.num_constraints, self.num_variables());
+        Index {
+            a: SparseMatrix::try_from_triplets(m, n, self.a_triplets).unwrap(),
+            b: SparseMatrix::try_from_triplets(m, n, self.b_triplets).unwrap(),
+            c: SparseMatrix::try_from_triplets(m, n, self.c_triplets).unwrap(),
+        }
+    }
+}
+
 pub fn sparse_matrix_from_ark_matrix<R: Scalar + Copy + Zero + AddAssign>(
     matrix: ark_relations::r1cs::Matrix<R>,
     nrows: usize,
@@ -234,4 +318,103 @@ mod test {
     test_generate_satisfied_instance!(RELATION, TEST_SIZE);
 
     test_generate_unsatisfied_instance!(RELATION, TEST_SIZE);
+
+    #[test]
+    fn test_unsatisfied_constraint_index() {
+        let one = R::ONE;
+        let index = Index {
+            a: SparseMatrix::try_from_triplets(3, 3, vec![(0, 1, one), (1, 1, one), (2, 1, one)])
+                .unwrap(),
+            b: SparseMatrix::try_from_triplets(3, 3, vec![(0, 0, one), (1, 0, one), (2, 0, one)])
+                .unwrap(),
+            c: SparseMatrix::try_from_triplets(3, 3, vec![(0, 1, one), (1, 1, one), (2, 2, one)])
+                .unwrap(),
+        };
+        let instance = Instance(vec![one]);
+        let witness = Witness(vec![R::try_from(2u64).unwrap(), R::try_from(3u64).unwrap()]);
+        let err = RELATION::is_satisfied_err(&index, &instance, &witness).unwrap_err();
+        assert_eq!(err.to_string(), "R1CS constraint 2 is not satisfied");
+        assert!(!RELATION::is_satisfied(&index, &instance, &witness));
+    }
+
+    fn r(x: u64) -> R {
+        R::try_from(x).unwrap()
+    }
+
+    /// Circuit for out = a^2 b + 1, with instance (1, out) and witness (a, b, t) for t = a b.
+    fn multiplication_circuit() -> Index<R> {
+        let mut builder = R1CSBuilder::<R>::new(2, 3);
+        let (one, out) = (builder.instance_variable(0), builder.instance_variable(1));
+        let (a, b, t) = (
+            builder.witness_variable(0),
+            builder.witness_variable(1),
+            builder.witness_variable(2),
+        );
+        assert_eq!(
+            builder.add_constraint(&[(a, r(1))], &[(b, r(1))], &[(t, r(1))]),
+            0
+        );
+        assert_eq!(
+            builder.add_constraint(&[(t, r(1))], &[(a, r(1))], &[(out, r(1)), (one, -r(1))]),
+            1
+        );
+        assert_eq!(builder.num_constraints(), 2);
+        assert_eq!(builder.num_variables(), 5);
+        builder.build()
+    }
+
+    #[test]
+    fn test_builder_multiplication_circuit() {
+        let index = multiplication_circuit();
+        let witness = Witness(vec![r(3), r(5), r(15)]);
+        let instance = Instance(vec![r(1), r(46)]);
+        RELATION::is_well_defined_err(&index, &instance, Some(&witness)).unwrap();
+        RELATION::is_satisfied_err(&index, &instance, &witness).unwrap();
+
+        // Wrong output
+        let instance = Instance(vec![r(1), r(45)]);
+        let err = RELATION::is_satisfied_err(&index, &instance, &witness).unwrap_err();
+        assert_eq!(err.to_string(), "R1CS constraint 1 is not satisfied");
+
+        // Wrong intermediate value
+        let instance = Instance(vec![r(1), r(46)]);
+        let witness = Witness(vec![r(3), r(5), r(14)]);
+        let err = RELATION::is_satisfied_err(&index, &instance, &witness).unwrap_err();
+        assert_eq!(err.to_string(), "R1CS constraint 0 is not satisfied");
+
+        // Wrong number of witness variables
+        let witness = Witness(vec![r(3), r(5)]);
+        assert!(!RELATION::is_well_defined(
+            &index,
+            &instance,
+            Some(&witness)
+        ));
+    }
+
+    #[test]
+    fn test_builder_sums_repeated_variables() {
+        let mut builder = R1CSBuilder::<R>::new(1, 1);
+        let (one, x) = (builder.instance_variable(0), builder.witness_variable(0));
+        // (x + x) * 1 = 6
+        builder.add_constraint(&[(x, r(1)), (x, r(1))], &[(one, r(1))], &[(one, r(6))]);
+        let index = builder.build();
+        let instance = Instance(vec![r(1)]);
+        assert!(RELATION::is_satisfied(
+            &index,
+            &instance,
+            &Witness(vec![r(3)])
+        ));
+        assert!(!RELATION::is_satisfied(
+            &index,
+            &instance,
+            &Witness(vec![r(6)])
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_builder_variable_out_of_range() {
+        let mut builder = R1CSBuilder::<R>::new(1, 1);
+        builder.add_constraint(&[(2, r(1))], &[], &[]);
+    }
 }
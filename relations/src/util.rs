@@ -0,0 +1,21 @@
+//! Helpers shared by the relations in this crate.
+
+use lattirust_arithmetic::ring::PolyRing;
+
+/// Return a polynomial whose coefficients have a uniformly random absolute value in $\[0, \texttt{bound}\]$ and a uniformly random sign.
+pub(crate) fn rand_bounded<R: PolyRing, Rng: ark_std::rand::Rng + ?Sized>(
+    bound: u64,
+    rng: &mut Rng,
+) -> R {
+    let coeffs: Vec<R::BaseRing> = (0..R::dimension())
+        .map(|_| {
+            let abs = R::BaseRing::try_from(rng.gen_range(0..=bound)).unwrap();
+            if rng.gen() {
+                -abs
+            } else {
+                abs
+            }
+        })
+        .collect();
+    R::from(coeffs)
+}